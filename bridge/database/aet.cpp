@@ -1 +1,279 @@
 #include "database/aet.hpp"
+
+extern "C" {
+aet_db_aet_file *
+kkdlib_aet_db_aet_file_new () {
+	return new aet_db_aet_file ();
+}
+
+uint32_t
+kkdlib_aet_db_aet_file_get_id (aet_db_aet_file *aet_file) {
+	return aet_file->id;
+}
+
+void
+kkdlib_aet_db_aet_file_set_id (aet_db_aet_file *aet_file, uint32_t id) {
+	aet_file->id = id;
+}
+
+const char *
+kkdlib_aet_db_aet_file_get_name (aet_db_aet_file *aet_file) {
+	return aet_file->name.c_str ();
+}
+
+void
+kkdlib_aet_db_aet_file_set_name (aet_db_aet_file *aet_file, const char *name) {
+	aet_file->name.assign (name);
+}
+
+uint16_t
+kkdlib_aet_db_aet_file_get_index (aet_db_aet_file *aet_file) {
+	return aet_file->index;
+}
+
+void
+kkdlib_aet_db_aet_file_set_index (aet_db_aet_file *aet_file, uint16_t index) {
+	aet_file->index = index;
+}
+
+void
+kkdlib_aet_db_aet_file_delete (aet_db_aet_file *aet_file) {
+	delete aet_file;
+}
+
+aet_db_aet_set_file *
+kkdlib_aet_db_aet_set_file_new () {
+	return new aet_db_aet_set_file ();
+}
+
+uint32_t
+kkdlib_aet_db_aet_set_file_get_id (aet_db_aet_set_file *set_file) {
+	return set_file->id;
+}
+
+void
+kkdlib_aet_db_aet_set_file_set_id (aet_db_aet_set_file *set_file, uint32_t id) {
+	set_file->id = id;
+}
+
+const char *
+kkdlib_aet_db_aet_set_file_get_name (aet_db_aet_set_file *set_file) {
+	return set_file->name.c_str ();
+}
+
+void
+kkdlib_aet_db_aet_set_file_set_name (aet_db_aet_set_file *set_file, const char *name) {
+	set_file->name.assign (name);
+}
+
+const char *
+kkdlib_aet_db_aet_set_file_get_file_name (aet_db_aet_set_file *set_file) {
+	return set_file->file_name.c_str ();
+}
+
+void
+kkdlib_aet_db_aet_set_file_set_file_name (aet_db_aet_set_file *set_file, const char *name) {
+	set_file->file_name.assign (name);
+}
+
+uint32_t
+kkdlib_aet_db_aet_set_file_get_sprite_set_id (aet_db_aet_set_file *set_file) {
+	return set_file->sprite_set_id;
+}
+
+void
+kkdlib_aet_db_aet_set_file_set_sprite_set_id (aet_db_aet_set_file *set_file, uint32_t sprite_set_id) {
+	set_file->sprite_set_id = sprite_set_id;
+}
+
+size_t
+kkdlib_aet_db_aet_set_file_get_aet_size (aet_db_aet_set_file *set_file) {
+	return set_file->aet.size ();
+}
+
+aet_db_aet_file *
+kkdlib_aet_db_aet_set_file_get_aet (aet_db_aet_set_file *set_file, size_t index) {
+	return set_file->aet.data () + index;
+}
+
+void
+kkdlib_aet_db_aet_set_file_add_aet (aet_db_aet_set_file *set_file, aet_db_aet_file *aet_file) {
+	set_file->aet.push_back (*aet_file);
+}
+
+void
+kkdlib_aet_db_aet_set_file_delete (aet_db_aet_set_file *set_file) {
+	delete set_file;
+}
+
+aet_database_file *
+kkdlib_aet_database_file_new () {
+	return new aet_database_file ();
+}
+
+bool
+kkdlib_aet_database_file_get_ready (aet_database_file *database_file) {
+	return database_file->ready;
+}
+
+void
+kkdlib_aet_database_file_set_ready (aet_database_file *database_file, bool ready) {
+	database_file->ready = ready;
+}
+
+bool
+kkdlib_aet_database_file_get_modern (aet_database_file *database_file) {
+	return database_file->modern;
+}
+
+void
+kkdlib_aet_database_file_set_modern (aet_database_file *database_file, bool modern) {
+	database_file->modern = modern;
+}
+
+bool
+kkdlib_aet_database_file_get_big_endian (aet_database_file *database_file) {
+	return database_file->big_endian;
+}
+
+void
+kkdlib_aet_database_file_set_big_endian (aet_database_file *database_file, bool big_endian) {
+	database_file->big_endian = big_endian;
+}
+
+bool
+kkdlib_aet_database_file_get_is_x (aet_database_file *database_file) {
+	return database_file->is_x;
+}
+
+void
+kkdlib_aet_database_file_set_is_x (aet_database_file *database_file, bool is_x) {
+	database_file->is_x = is_x;
+}
+
+size_t
+kkdlib_aet_database_file_get_aet_set_size (aet_database_file *database_file) {
+	return database_file->aet_set.size ();
+}
+
+aet_db_aet_set_file *
+kkdlib_aet_database_file_get_aet_set (aet_database_file *database_file, size_t index) {
+	return database_file->aet_set.data () + index;
+}
+
+void
+kkdlib_aet_database_file_add_aet_set (aet_database_file *database_file, aet_db_aet_set_file *set_file) {
+	set_file->index = database_file->aet_set.size ();
+	database_file->aet_set.push_back (*set_file);
+}
+
+void
+kkdlib_aet_database_file_read_file (aet_database_file *database_file, const char *path, bool modern) {
+	database_file->read (path, modern);
+}
+
+void
+kkdlib_aet_database_file_read_data (aet_database_file *database_file, void *data, size_t size, bool modern) {
+	database_file->read (data, size, modern);
+}
+
+void
+kkdlib_aet_database_file_write_file (aet_database_file *database_file, const char *path) {
+	database_file->write (path);
+}
+
+void
+kkdlib_aet_database_file_write_data (aet_database_file *database_file, void **data, size_t *size) {
+	database_file->write (data, size);
+}
+
+void
+kkdlib_aet_database_file_delete_packed_data (void *data) {
+	if (data) free_def (data);
+}
+
+void
+kkdlib_aet_database_file_delete (aet_database_file *database_file) {
+	delete database_file;
+}
+
+uint32_t
+kkdlib_aet_db_aet_get_id (const aet_db_aet *aet) {
+	return aet->id;
+}
+
+const char *
+kkdlib_aet_db_aet_get_name (const aet_db_aet *aet) {
+	return aet->name.c_str ();
+}
+
+uint16_t
+kkdlib_aet_db_aet_get_index (const aet_db_aet *aet) {
+	return aet->info.index;
+}
+
+uint16_t
+kkdlib_aet_db_aet_get_set_index (const aet_db_aet *aet) {
+	return aet->info.set_index;
+}
+
+uint32_t
+kkdlib_aet_db_aet_set_get_id (const aet_db_aet_set *set) {
+	return set->id;
+}
+
+const char *
+kkdlib_aet_db_aet_set_get_name (const aet_db_aet_set *set) {
+	return set->name.c_str ();
+}
+
+const char *
+kkdlib_aet_db_aet_set_get_file_name (const aet_db_aet_set *set) {
+	return set->file_name.c_str ();
+}
+
+uint32_t
+kkdlib_aet_db_aet_set_get_index (const aet_db_aet_set *set) {
+	return set->index;
+}
+
+uint32_t
+kkdlib_aet_db_aet_set_get_sprite_set_id (const aet_db_aet_set *set) {
+	return set->sprite_set_id;
+}
+
+aet_database *
+kkdlib_aet_database_new () {
+	return new aet_database ();
+}
+
+void
+kkdlib_aet_database_add_file (aet_database *database, aet_database_file *file) {
+	database->add (file);
+}
+
+const aet_db_aet_set *
+kkdlib_aet_database_get_aet_set_by_name (aet_database *database, const char *name) {
+	return database->get_aet_set_by_name (name);
+}
+
+const aet_db_aet_set *
+kkdlib_aet_database_get_aet_set_by_id (aet_database *database, uint32_t set_id) {
+	return database->get_aet_set_by_id (set_id);
+}
+
+const aet_db_aet *
+kkdlib_aet_database_get_aet_by_name (aet_database *database, const char *name) {
+	return database->get_aet_by_name (name);
+}
+
+const aet_db_aet *
+kkdlib_aet_database_get_aet_by_id (aet_database *database, uint32_t id) {
+	return database->get_aet_by_id (id);
+}
+
+void
+kkdlib_aet_database_delete (aet_database *database) {
+	delete database;
+}
+}
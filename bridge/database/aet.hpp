@@ -1,4 +1,65 @@
 #pragma once
 #include <database/aet.cpp>
 
-extern "C" {}
+extern "C" {
+aet_db_aet_file *kkdlib_aet_db_aet_file_new ();
+uint32_t kkdlib_aet_db_aet_file_get_id (aet_db_aet_file *aet_file);
+void kkdlib_aet_db_aet_file_set_id (aet_db_aet_file *aet_file, uint32_t id);
+const char *kkdlib_aet_db_aet_file_get_name (aet_db_aet_file *aet_file);
+void kkdlib_aet_db_aet_file_set_name (aet_db_aet_file *aet_file, const char *name);
+uint16_t kkdlib_aet_db_aet_file_get_index (aet_db_aet_file *aet_file);
+void kkdlib_aet_db_aet_file_set_index (aet_db_aet_file *aet_file, uint16_t index);
+void kkdlib_aet_db_aet_file_delete (aet_db_aet_file *aet_file);
+
+aet_db_aet_set_file *kkdlib_aet_db_aet_set_file_new ();
+uint32_t kkdlib_aet_db_aet_set_file_get_id (aet_db_aet_set_file *set_file);
+void kkdlib_aet_db_aet_set_file_set_id (aet_db_aet_set_file *set_file, uint32_t id);
+const char *kkdlib_aet_db_aet_set_file_get_name (aet_db_aet_set_file *set_file);
+void kkdlib_aet_db_aet_set_file_set_name (aet_db_aet_set_file *set_file, const char *name);
+const char *kkdlib_aet_db_aet_set_file_get_file_name (aet_db_aet_set_file *set_file);
+void kkdlib_aet_db_aet_set_file_set_file_name (aet_db_aet_set_file *set_file, const char *name);
+uint32_t kkdlib_aet_db_aet_set_file_get_sprite_set_id (aet_db_aet_set_file *set_file);
+void kkdlib_aet_db_aet_set_file_set_sprite_set_id (aet_db_aet_set_file *set_file, uint32_t sprite_set_id);
+size_t kkdlib_aet_db_aet_set_file_get_aet_size (aet_db_aet_set_file *set_file);
+aet_db_aet_file *kkdlib_aet_db_aet_set_file_get_aet (aet_db_aet_set_file *set_file, size_t index);
+void kkdlib_aet_db_aet_set_file_add_aet (aet_db_aet_set_file *set_file, aet_db_aet_file *aet_file);
+void kkdlib_aet_db_aet_set_file_delete (aet_db_aet_set_file *set_file);
+
+aet_database_file *kkdlib_aet_database_file_new ();
+bool kkdlib_aet_database_file_get_ready (aet_database_file *database_file);
+void kkdlib_aet_database_file_set_ready (aet_database_file *database_file, bool ready);
+bool kkdlib_aet_database_file_get_modern (aet_database_file *database_file);
+void kkdlib_aet_database_file_set_modern (aet_database_file *database_file, bool modern);
+bool kkdlib_aet_database_file_get_big_endian (aet_database_file *database_file);
+void kkdlib_aet_database_file_set_big_endian (aet_database_file *database_file, bool big_endian);
+bool kkdlib_aet_database_file_get_is_x (aet_database_file *database_file);
+void kkdlib_aet_database_file_set_is_x (aet_database_file *database_file, bool is_x);
+size_t kkdlib_aet_database_file_get_aet_set_size (aet_database_file *database_file);
+aet_db_aet_set_file *kkdlib_aet_database_file_get_aet_set (aet_database_file *database_file, size_t index);
+void kkdlib_aet_database_file_add_aet_set (aet_database_file *database_file, aet_db_aet_set_file *set_file);
+void kkdlib_aet_database_file_read_file (aet_database_file *database_file, const char *path, bool modern);
+void kkdlib_aet_database_file_read_data (aet_database_file *database_file, void *data, size_t size, bool modern);
+void kkdlib_aet_database_file_write_file (aet_database_file *database_file, const char *path);
+void kkdlib_aet_database_file_write_data (aet_database_file *database_file, void **data, size_t *size);
+void kkdlib_aet_database_file_delete_packed_data (void *data);
+void kkdlib_aet_database_file_delete (aet_database_file *database_file);
+
+uint32_t kkdlib_aet_db_aet_get_id (const aet_db_aet *aet);
+const char *kkdlib_aet_db_aet_get_name (const aet_db_aet *aet);
+uint16_t kkdlib_aet_db_aet_get_index (const aet_db_aet *aet);
+uint16_t kkdlib_aet_db_aet_get_set_index (const aet_db_aet *aet);
+
+uint32_t kkdlib_aet_db_aet_set_get_id (const aet_db_aet_set *set);
+const char *kkdlib_aet_db_aet_set_get_name (const aet_db_aet_set *set);
+const char *kkdlib_aet_db_aet_set_get_file_name (const aet_db_aet_set *set);
+uint32_t kkdlib_aet_db_aet_set_get_index (const aet_db_aet_set *set);
+uint32_t kkdlib_aet_db_aet_set_get_sprite_set_id (const aet_db_aet_set *set);
+
+aet_database *kkdlib_aet_database_new ();
+void kkdlib_aet_database_add_file (aet_database *database, aet_database_file *file);
+const aet_db_aet_set *kkdlib_aet_database_get_aet_set_by_name (aet_database *database, const char *name);
+const aet_db_aet_set *kkdlib_aet_database_get_aet_set_by_id (aet_database *database, uint32_t set_id);
+const aet_db_aet *kkdlib_aet_database_get_aet_by_name (aet_database *database, const char *name);
+const aet_db_aet *kkdlib_aet_database_get_aet_by_id (aet_database *database, uint32_t id);
+void kkdlib_aet_database_delete (aet_database *database);
+}
@@ -86,6 +86,16 @@ kkdlib_spr_db_spr_set_file_set_file_name (spr_db_spr_set_file *set_file, const c
 	set_file->file_name.assign (name);
 }
 
+uint32_t
+kkdlib_spr_db_spr_set_file_get_index (spr_db_spr_set_file *set_file) {
+	return set_file->index;
+}
+
+void
+kkdlib_spr_db_spr_set_file_set_index (spr_db_spr_set_file *set_file, uint32_t index) {
+	set_file->index = index;
+}
+
 size_t
 kkdlib_spr_db_spr_set_file_get_sprite_size (spr_db_spr_set_file *set_file) {
 	return set_file->sprite.size ();
@@ -101,6 +111,16 @@ kkdlib_spr_db_spr_set_file_add_sprite (spr_db_spr_set_file *set_file, spr_db_spr
 	set_file->sprite.push_back (*spr_file);
 }
 
+void
+kkdlib_spr_db_spr_set_file_replace_sprite (spr_db_spr_set_file *set_file, size_t index, spr_db_spr_file *spr_file) {
+	set_file->sprite[index] = *spr_file;
+}
+
+void
+kkdlib_spr_db_spr_set_file_remove_sprite (spr_db_spr_set_file *set_file, size_t index) {
+	set_file->sprite.erase (set_file->sprite.begin () + index);
+}
+
 void
 kkdlib_spr_db_spr_set_file_delete (spr_db_spr_set_file *set_file) {
 	delete set_file;
@@ -166,6 +186,17 @@ kkdlib_sprite_database_file_add_sprite_set (sprite_database_file *database_file,
 	database_file->sprite_set.push_back (*set_file);
 }
 
+void
+kkdlib_sprite_database_file_replace_sprite_set (sprite_database_file *database_file, size_t index, spr_db_spr_set_file *set_file) {
+	set_file->index = (uint32_t)index;
+	database_file->sprite_set[index] = *set_file;
+}
+
+void
+kkdlib_sprite_database_file_remove_sprite_set (sprite_database_file *database_file, size_t index) {
+	database_file->sprite_set.erase (database_file->sprite_set.begin () + index);
+}
+
 void
 kkdlib_sprite_database_file_read_file (sprite_database_file *database_file, const char *path, bool modern) {
 	database_file->read (path, modern);
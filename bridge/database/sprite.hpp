@@ -20,9 +20,13 @@ const char *kkdlib_spr_db_spr_set_file_get_name (spr_db_spr_set_file *set_file);
 void kkdlib_spr_db_spr_set_file_set_name (spr_db_spr_set_file *set_file, const char *name);
 const char *kkdlib_spr_db_spr_set_file_get_file_name (spr_db_spr_set_file *set_file);
 void kkdlib_spr_db_spr_set_file_set_file_name (spr_db_spr_set_file *set_file, const char *name);
+uint32_t kkdlib_spr_db_spr_set_file_get_index (spr_db_spr_set_file *set_file);
+void kkdlib_spr_db_spr_set_file_set_index (spr_db_spr_set_file *set_file, uint32_t index);
 size_t kkdlib_spr_db_spr_set_file_get_sprite_size (spr_db_spr_set_file *set_file);
 spr_db_spr_file *kkdlib_spr_db_spr_set_file_get_sprite (spr_db_spr_set_file *set_file, size_t index);
 void kkdlib_spr_db_spr_set_file_add_sprite (spr_db_spr_set_file *set_file, spr_db_spr_file *spr_file);
+void kkdlib_spr_db_spr_set_file_replace_sprite (spr_db_spr_set_file *set_file, size_t index, spr_db_spr_file *spr_file);
+void kkdlib_spr_db_spr_set_file_remove_sprite (spr_db_spr_set_file *set_file, size_t index);
 void kkdlib_spr_db_spr_set_file_delete (spr_db_spr_set_file *set_file);
 
 sprite_database_file *kkdlib_sprite_database_file_new ();
@@ -37,6 +41,8 @@ void kkdlib_sprite_database_file_set_is_x (sprite_database_file *database_file,
 size_t kkdlib_sprite_database_file_get_sprite_set_size (sprite_database_file *database_file);
 spr_db_spr_set_file *kkdlib_sprite_database_file_get_sprite_set (sprite_database_file *database_file, size_t index);
 void kkdlib_sprite_database_file_add_sprite_set (sprite_database_file *database_file, spr_db_spr_set_file *set_file);
+void kkdlib_sprite_database_file_replace_sprite_set (sprite_database_file *database_file, size_t index, spr_db_spr_set_file *set_file);
+void kkdlib_sprite_database_file_remove_sprite_set (sprite_database_file *database_file, size_t index);
 void kkdlib_sprite_database_file_read_file (sprite_database_file *database_file, const char *path, bool modern);
 void kkdlib_sprite_database_file_read_data (sprite_database_file *database_file, void *data, size_t size, bool modern);
 void kkdlib_sprite_database_file_write_file (sprite_database_file *database_file, const char *path);
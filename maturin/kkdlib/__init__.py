@@ -1,7 +1,21 @@
 # Under most circumstances maturin generates this automatically
 # However since `kkdlib` folder exists it treats this as a rust/python mixed project
+import sys
+from types import ModuleType
+
 from .kkdlib import *
 
 __doc__ = kkdlib.__doc__
 if hasattr(kkdlib, "__all__"):
-    __all__ = kkdlib.__all__
\ No newline at end of file
+    __all__ = kkdlib.__all__
+
+
+# The native submodules are only attributes, register them so `import kkdlib.farc` works
+def _register(module: ModuleType, name: str) -> None:
+    for attr, value in vars(module).items():
+        if isinstance(value, ModuleType) and not attr.startswith("_"):
+            sys.modules[f"{name}.{attr}"] = value
+            _register(value, f"{name}.{attr}")
+
+
+_register(kkdlib, __name__)
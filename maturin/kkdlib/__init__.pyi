@@ -0,0 +1,17 @@
+from typing_extensions import Buffer as _Buffer
+
+from . import aet as aet
+from . import database as database
+from . import farc as farc
+from . import hash as hash
+from . import key_val as key_val
+from . import spr as spr
+from . import txp as txp
+
+class Buffer(_Buffer):
+    """Read only bytes shared with Python without copying, `numpy.asarray` keeps the shape"""
+
+    def __len__(self) -> int: ...
+    def __buffer__(self, flags: int, /) -> memoryview: ...
+    @property
+    def shape(self) -> list[int]: ...
@@ -0,0 +1,389 @@
+# Values read from their parent stay tied to it, so `scene.root[0].video.opacity = curve` edits the
+# scene in place. Use `copy.copy(value)` for a detached copy
+# Blend modes and qualities are read back as plain ints so values the enums don't know survive
+from enum import IntEnum
+from typing import Literal
+
+from . import spr, txp
+from .database import sprite
+
+class BlendMode(IntEnum):
+    None_ = 0
+    Copy = 1
+    Behind = 2
+    Normal = 3
+    Dissolve = 4
+    Add = 5
+    Multiply = 6
+    Screen = 7
+    Overlay = 8
+    SoftLight = 9
+    HardLight = 10
+    Darken = 11
+    Lighten = 12
+    ClassicDifference = 13
+    Hue = 14
+    Saturation = 15
+    Color = 16
+    Luminosity = 17
+    StencilAlpha = 18
+    StencilLuma = 19
+    SilhouetteAlpha = 20
+    SilhouetteLuma = 21
+    LuminescentPremul = 22
+    AlphaAdd = 23
+    ClassicColorDodge = 24
+    ClassicColorBurn = 25
+    Exclusion = 26
+    Difference = 27
+    ColorDodge = 28
+    ColorBurn = 29
+    LinearDodge = 30
+    LinearBurn = 31
+    LinearLight = 32
+    VividLight = 33
+    PinLight = 34
+    HardMix = 35
+    LighterColor = 36
+    DarkerColor = 37
+    Subtract = 38
+    Divide = 39
+
+class LayerQuality(IntEnum):
+    None_ = 0
+    Wireframe = 1
+    Draft = 2
+    Best = 3
+
+class FCurveKey:
+    frame: float
+    value: float
+    tangent: float
+    def __init__(self, frame: float, value: float, tangent: float) -> None: ...
+
+class FCurve:
+    keys: list[FCurveKey]
+    def __init__(self) -> None: ...
+    @staticmethod
+    def constant(value: float) -> FCurve: ...
+    def interpolate(self, frame: float) -> float: ...
+    def scale_values(self, factor: float) -> None: ...
+    def derivative(self, frame: float) -> float:
+        """Slope in value per frame, flat outside the keys like interpolate"""
+    def insert_key(self, frame: float, value: float) -> int:
+        """Gives the new key and its neighbours auto clamped tangents, returns its index"""
+    def remove_key(self, index: int) -> FCurveKey | None: ...
+    def auto_tangents(self, clamped: bool) -> None: ...
+    def bake(self, start: float, end: float, step: float) -> None: ...
+    def simplify(self, tolerance: float) -> None: ...
+    def retime(self, scale: float, offset: float) -> None: ...
+
+class TransferMode:
+    mode: BlendMode | int
+    flag: int
+    matte: int
+    def __init__(self) -> None: ...
+
+class LayerVideo3D:
+    anchor_z: FCurve
+    pos_z: FCurve
+    dir_x: FCurve
+    dir_y: FCurve
+    dir_z: FCurve
+    rot_x: FCurve
+    rot_y: FCurve
+    scale_z: FCurve
+    def __init__(self) -> None: ...
+
+class LayerVideo:
+    transfer_mode: TransferMode
+    anchor_x: FCurve
+    anchor_y: FCurve
+    pos_x: FCurve
+    pos_y: FCurve
+    rot_z: FCurve
+    scale_x: FCurve
+    scale_y: FCurve
+    opacity: FCurve
+    video_3d: LayerVideo3D | None
+    def __init__(self) -> None: ...
+
+class LayerAudio:
+    volume_l: FCurve
+    volume_r: FCurve
+    pan_l: FCurve
+    pan_r: FCurve
+    def __init__(self) -> None: ...
+
+class Camera:
+    eye_x: FCurve
+    eye_y: FCurve
+    eye_z: FCurve
+    pos_x: FCurve
+    pos_y: FCurve
+    pos_z: FCurve
+    dir_x: FCurve
+    dir_y: FCurve
+    dir_z: FCurve
+    rot_x: FCurve
+    rot_y: FCurve
+    rot_z: FCurve
+    zoom: FCurve
+    def __init__(self) -> None: ...
+
+class VideoSource:
+    name: str
+    id: int
+    def __init__(self, name: str, id: int) -> None: ...
+
+class Video:
+    color: bytes
+    width: int
+    height: int
+    fpf: float
+    sources: list[VideoSource]
+    def __init__(self) -> None: ...
+
+class Audio:
+    sound_index: int
+    def __init__(self, sound_index: int) -> None: ...
+
+class Item:
+    """Index of a layer's item in the videos, audios or comps of its scene"""
+
+    class Video(Item):
+        def __init__(self, _0: int) -> None: ...
+        @property
+        def _0(self) -> int: ...
+        def __len__(self) -> int: ...
+        def __getitem__(self, index: int) -> int: ...
+
+    class Audio(Item):
+        def __init__(self, _0: int) -> None: ...
+        @property
+        def _0(self) -> int: ...
+        def __len__(self) -> int: ...
+        def __getitem__(self, index: int) -> int: ...
+
+    class Composition(Item):
+        def __init__(self, _0: int) -> None: ...
+        @property
+        def _0(self) -> int: ...
+        def __len__(self) -> int: ...
+        def __getitem__(self, index: int) -> int: ...
+
+    def __eq__(self, other: object) -> bool: ...
+
+class Issue:
+    """What is wrong with a video source"""
+
+    class Missing(Issue):
+        """Neither the id nor the name is in the database"""
+
+        def __init__(self) -> None: ...
+        def __len__(self) -> int: ...
+        def __getitem__(self, index: int) -> object: ...
+
+    class OtherSet(Issue):
+        """The sprite belongs to another set than the one the aet set draws from"""
+
+        def __init__(self, set: str) -> None: ...
+        @property
+        def set(self) -> str: ...
+
+    class Texture(Issue):
+        """The source points at a texture's entry rather than a sprite's"""
+
+        def __init__(self) -> None: ...
+        def __len__(self) -> int: ...
+        def __getitem__(self, index: int) -> object: ...
+
+    class StaleId(Issue):
+        """The name is in the database under another id, fix_source_ids rewrites it"""
+
+        def __init__(self, expected: int) -> None: ...
+        @property
+        def expected(self) -> int: ...
+
+    class WrongName(Issue):
+        """The id belongs to a sprite with another name"""
+
+        def __init__(self, expected: str) -> None: ...
+        @property
+        def expected(self) -> str: ...
+
+    class NotInSet(Issue):
+        """The database knows the sprite but the sprite set has nothing at its index"""
+
+        def __init__(self, index: int) -> None: ...
+        @property
+        def index(self) -> int: ...
+
+    class WrongSize(Issue):
+        """The sprite's size in the sprite set differs from the video's"""
+
+        def __init__(self, width: float, height: float) -> None: ...
+        @property
+        def width(self) -> float: ...
+        @property
+        def height(self) -> float: ...
+
+    def __eq__(self, other: object) -> bool: ...
+
+class SourceIssue:
+    @property
+    def scene(self) -> int: ...
+    @property
+    def video(self) -> int: ...
+    @property
+    def source(self) -> int: ...
+    @property
+    def name(self) -> str: ...
+    @property
+    def id(self) -> int: ...
+    @property
+    def issue(self) -> Issue: ...
+
+class Layer:
+    @property
+    def id(self) -> int:
+        """Kept when the layer is copied, parent links refer to layers by this"""
+    name: str
+    start_time: float
+    end_time: float
+    offset_time: float
+    time_scale: float
+    flags: int
+    quality: LayerQuality | int
+    item: Item | None
+    markers: list[tuple[str, float]]
+    video: LayerVideo | None
+    audio: LayerAudio | None
+    parent: int | None
+    def __init__(self, name: str) -> None: ...
+
+class Composition:
+    layers: list[Layer]
+    def __init__(self) -> None: ...
+    def __len__(self) -> int: ...
+    def __getitem__(self, index: int) -> Layer: ...
+    def __setitem__(self, index: int, layer: Layer) -> None: ...
+    def __delitem__(self, index: int) -> None: ...
+    def append(self, layer: Layer) -> None: ...
+    def insert(self, index: int, layer: Layer) -> None: ...
+    def remove_layer(self, index: int) -> Layer:
+        """Layers parented to the removed one are moved to its parent"""
+    def move_layer(self, index: int, to: int) -> None: ...
+    def duplicate_layer(self, index: int) -> int:
+        """Copies the layer and its children above it, returns the index of the copy"""
+
+class LayerPath:
+    """Where a layer sits in a scene, comp is an index into Scene.comps or None for the root"""
+
+    def __init__(self, comp: int | None, index: int) -> None: ...
+    @property
+    def comp(self) -> int | None: ...
+    @property
+    def index(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class EvaluatedLayer:
+    @property
+    def id(self) -> int: ...
+    @property
+    def name(self) -> str: ...
+    @property
+    def frame(self) -> float:
+        """Time inside the layer, what the video source is picked with"""
+    @property
+    def matrix(self) -> list[list[float]]:
+        """Row major 4x4 from layer space to scene space"""
+    @property
+    def opacity(self) -> float: ...
+    @property
+    def blend_mode(self) -> BlendMode | int: ...
+    @property
+    def is_3d(self) -> bool: ...
+    @property
+    def video(self) -> int:
+        """Index into Scene.videos"""
+    @property
+    def source(self) -> int | None: ...
+    @property
+    def visible(self) -> bool: ...
+    def matrix_2d(self) -> list[list[float]]: ...
+
+class Scene:
+    name: str
+    start_time: float
+    end_time: float
+    fps: float
+    color: bytes
+    width: int
+    height: int
+    camera: Camera | None
+    root: Composition
+    comps: list[Composition]
+    videos: list[Video]
+    audios: list[Audio]
+    def __init__(self) -> None: ...
+    def evaluate(self, frame: float) -> list[EvaluatedLayer]:
+        """Every video layer shown at the given frame, back to front"""
+    def layers_recursive(self) -> list[tuple[int, LayerPath, Layer]]:
+        """Depth, path and layer, each layer followed by the layers of the composition it shows"""
+    def layer(self, path: LayerPath) -> Layer | None: ...
+    def set_layer(self, path: LayerPath, layer: Layer) -> None: ...
+    def find_layer(self, id: int) -> LayerPath | None: ...
+    def find_layer_by_name(self, name: str) -> LayerPath | None: ...
+    def find_by_marker(self, marker: str) -> list[tuple[LayerPath, float]]: ...
+    def rename_sources(self, old: str, new: str) -> int: ...
+    def duplicate_comp(self, index: int) -> int | None:
+        """Deep copy of a composition and the ones nested in it, returns the index of the copy"""
+    def render(
+        self,
+        frame: float,
+        sprites: spr.Set | None = None,
+        database: sprite.Database | None = None,
+        set_name: str | None = None,
+    ) -> txp.Image:
+        """Draws the frame on the CPU, layers whose sprite isn't in the set_name set are skipped"""
+    def render_range(
+        self,
+        start: float,
+        end: float,
+        step: float = 1.0,
+        sprites: spr.Set | None = None,
+        database: sprite.Database | None = None,
+        set_name: str | None = None,
+    ) -> list[txp.Image]:
+        """Frames start, start + step, ... up to but not including end"""
+    def encode_range(
+        self,
+        format: Literal["apng", "gif", "webp"],
+        start: float,
+        end: float,
+        step: float = 1.0,
+        sprites: spr.Set | None = None,
+        database: sprite.Database | None = None,
+        set_name: str | None = None,
+    ) -> bytes:
+        """A looping animation of render_range, played at the scene's fps"""
+    def convert_resolution(self, from_mode: spr.ResolutionMode, to_mode: spr.ResolutionMode) -> None: ...
+
+class Set:
+    modern: bool
+    big_endian: bool
+    is_x: bool
+    scenes: list[Scene]
+    def __init__(self) -> None: ...
+    def rename_sources(self, old: str, new: str) -> int: ...
+    def validate_sources(
+        self, database: sprite.Database, sprite_set_id: int, sprites: spr.Set | None = None
+    ) -> list[SourceIssue]:
+        """Looks sources up in sprite_set_id's set, sizes are only checked when its sprites are given"""
+    def fix_source_ids(self, database: sprite.Database, sprite_set_id: int) -> int:
+        """Rewrites ids from names in the sprite set after the sprite database was renumbered"""
+    @staticmethod
+    def from_buf(data: bytes, modern: bool) -> Set: ...
+    def to_buf(self) -> bytes: ...
@@ -0,0 +1,3 @@
+from . import aet as aet
+from . import pv as pv
+from . import sprite as sprite
@@ -0,0 +1,33 @@
+from . import file as file
+
+class Database:
+    """Merged lookup over any number of file databases"""
+
+    def __init__(self) -> None: ...
+    def add_file(self, file: file.Database) -> None: ...
+    def get_aet_set_by_name(self, name: str) -> Set | None: ...
+    def get_aet_set_by_id(self, id: int) -> Set | None: ...
+    def get_aet_by_name(self, name: str) -> Scene | None: ...
+    def get_aet_by_id(self, id: int) -> Scene | None: ...
+
+class Set:
+    @property
+    def id(self) -> int: ...
+    @property
+    def name(self) -> str: ...
+    @property
+    def file_name(self) -> str: ...
+    @property
+    def index(self) -> int: ...
+    @property
+    def sprite_set_id(self) -> int: ...
+
+class Scene:
+    @property
+    def id(self) -> int: ...
+    @property
+    def name(self) -> str: ...
+    @property
+    def index(self) -> int: ...
+    @property
+    def set_index(self) -> int: ...
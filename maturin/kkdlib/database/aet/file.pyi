@@ -0,0 +1,63 @@
+class Scene:
+    def __init__(self) -> None: ...
+    @property
+    def id(self) -> int: ...
+    @id.setter
+    def id(self, value: int) -> None: ...
+    @property
+    def name(self) -> str: ...
+    @name.setter
+    def name(self, value: str) -> None: ...
+    @property
+    def index(self) -> int: ...
+    @index.setter
+    def index(self, value: int) -> None: ...
+
+class Set:
+    def __init__(self) -> None: ...
+    def add_scene(self, scene: Scene) -> None: ...
+    @property
+    def scenes(self) -> list[Scene]:
+        """Copies, edited scenes have to be added to a new set"""
+    @property
+    def id(self) -> int: ...
+    @id.setter
+    def id(self, value: int) -> None: ...
+    @property
+    def name(self) -> str: ...
+    @name.setter
+    def name(self, value: str) -> None: ...
+    @property
+    def file_name(self) -> str: ...
+    @file_name.setter
+    def file_name(self, value: str) -> None: ...
+    @property
+    def sprite_set_id(self) -> int: ...
+    @sprite_set_id.setter
+    def sprite_set_id(self, value: int) -> None: ...
+
+class Database:
+    def __init__(self) -> None: ...
+    def add_set(self, set: Set) -> None: ...
+    @staticmethod
+    def from_buf(data: bytes, modern: bool) -> Database: ...
+    def to_buf(self) -> bytes | None: ...
+    @property
+    def sets(self) -> list[Set]:
+        """Copies, edited sets have to be added to a new database"""
+    @property
+    def ready(self) -> bool: ...
+    @ready.setter
+    def ready(self, value: bool) -> None: ...
+    @property
+    def modern(self) -> bool: ...
+    @modern.setter
+    def modern(self, value: bool) -> None: ...
+    @property
+    def big_endian(self) -> bool: ...
+    @big_endian.setter
+    def big_endian(self, value: bool) -> None: ...
+    @property
+    def is_x(self) -> bool: ...
+    @is_x.setter
+    def is_x(self, value: bool) -> None: ...
@@ -0,0 +1,171 @@
+from typing import ClassVar
+
+from .. import key_val
+from . import aet, sprite
+
+class Difficulty:
+    Easy: ClassVar[Difficulty]
+    Normal: ClassVar[Difficulty]
+    Hard: ClassVar[Difficulty]
+    Extreme: ClassVar[Difficulty]
+    Encore: ClassVar[Difficulty]
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class Chart:
+    difficulty: Difficulty
+    index: int | None
+    """Slot under `difficulty.<name>`, the edition the game loads, None takes the first free one"""
+    edition: int | None
+    level: str | None
+    level_sort_index: int | None
+    script_file_name: str | None
+    script_format: str | None
+    version: int | None
+    original: bool | None
+    extra: bool | None
+    unknown: key_val.KeyValNode
+    """Keys under the chart without a field, written back with it"""
+    def __init__(self, difficulty: Difficulty) -> None: ...
+    def stars(self) -> float | None:
+        """7.5 for PV_LV_07_5"""
+
+class Performer:
+    chara: str | None
+    kind: str | None
+    pv_costume: int | None
+    unknown: key_val.KeyValNode
+    """Keys under the performer without a field, written back under its new index"""
+    def __init__(self) -> None: ...
+
+class Sabi:
+    start_time: float | None
+    play_time: float | None
+    def __init__(self) -> None: ...
+
+class SongInfo:
+    music: str | None
+    lyrics: str | None
+    arranger: str | None
+    guitar_player: str | None
+    manipulator: str | None
+    pv_editor: str | None
+    illustrator: str | None
+    def __init__(self) -> None: ...
+
+class Pv:
+    """One `pv_xxx` entry, keys without a field are kept in `unknown` relative to the pv"""
+
+    id: int
+    id_digits: int
+    """How many digits the id is padded to in `pv_xxx`, 3 unless the file spelled it otherwise"""
+    song_name: str | None
+    song_name_reading: str | None
+    song_name_en: str | None
+    song_file_name: str | None
+    bpm: int | None
+    date: int | None
+    sabi: Sabi
+    songinfo: SongInfo
+    songinfo_en: SongInfo
+    charts: list[Chart]
+    chart_lengths: dict[Difficulty, int]
+    """`length` of each difficulty as read, written back unless the charts need more slots"""
+    performers: list[Performer]
+    motions: list[list[str]]
+    movie_file_name: str | None
+    movie_surface: str | None
+    movies: list[str]
+    lyrics: dict[int, str]
+    lyrics_en: dict[int, str]
+    lyric_digits: int
+    """How many digits lyric numbers are padded to, like `id_digits`"""
+    unknown: key_val.KeyValNode
+    def __init__(self, id: int) -> None: ...
+    def key(self) -> str: ...
+    def sprite_set_name(self) -> str: ...
+    def aet_set_name(self) -> str: ...
+    def validate(self) -> list[Issue]: ...
+    def validate_sets(self, sprites: sprite.Database, aets: aet.Database) -> list[Issue]:
+        """Checks the song select sets named after the pv's id exist"""
+
+class Issue:
+    class DuplicateId(Issue):
+        """Another pv in the database has the same id"""
+
+        def __init__(self) -> None: ...
+        def __len__(self) -> int: ...
+        def __getitem__(self, index: int) -> object: ...
+
+    class MissingSongName(Issue):
+        def __init__(self) -> None: ...
+        def __len__(self) -> int: ...
+        def __getitem__(self, index: int) -> object: ...
+
+    class NoCharts(Issue):
+        def __init__(self) -> None: ...
+        def __len__(self) -> int: ...
+        def __getitem__(self, index: int) -> object: ...
+
+    class DuplicateEdition(Issue):
+        def __init__(self, difficulty: Difficulty, edition: int) -> None: ...
+        @property
+        def difficulty(self) -> Difficulty: ...
+        @property
+        def edition(self) -> int: ...
+
+    class MissingScript(Issue):
+        def __init__(self, difficulty: Difficulty, edition: int) -> None: ...
+        @property
+        def difficulty(self) -> Difficulty: ...
+        @property
+        def edition(self) -> int: ...
+
+    class BadLevel(Issue):
+        """The level isn't of the PV_LV_07_5 form"""
+
+        def __init__(self, difficulty: Difficulty, edition: int, level: str | None) -> None: ...
+        @property
+        def difficulty(self) -> Difficulty: ...
+        @property
+        def edition(self) -> int: ...
+        @property
+        def level(self) -> str | None: ...
+
+    class MotionsWithoutPerformer(Issue):
+        """There are motions for a performer past the last one"""
+
+        def __init__(self, performer: int) -> None: ...
+        @property
+        def performer(self) -> int: ...
+
+    class MissingSpriteSet(Issue):
+        def __init__(self, name: str) -> None: ...
+        @property
+        def name(self) -> str: ...
+
+    class MissingAetSet(Issue):
+        def __init__(self, name: str) -> None: ...
+        @property
+        def name(self) -> str: ...
+
+    def __eq__(self, other: object) -> bool: ...
+
+class PvIssue:
+    @property
+    def pv(self) -> int: ...
+    @property
+    def issue(self) -> Issue: ...
+
+class Database:
+    """A pv_db or mod_pv_db, str() writes it back with top level keys other than `pv_xxx` kept"""
+
+    pvs: list[Pv]
+    unknown: key_val.KeyValNode
+    def __init__(self) -> None: ...
+    @staticmethod
+    def parse(data: str) -> Database:
+        """Raises ValueError if a typed field can't be parsed"""
+    def get(self, id: int) -> Pv | None: ...
+    def validate(self) -> list[PvIssue]: ...
+    def validate_sets(self, sprites: sprite.Database, aets: aet.Database) -> list[PvIssue]: ...
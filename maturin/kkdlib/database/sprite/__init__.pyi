@@ -0,0 +1,75 @@
+from typing import ClassVar
+
+from . import file as file
+
+class Database:
+    """Merged lookup over any number of file databases"""
+
+    def __init__(self) -> None: ...
+    def add_file(self, file: file.Database) -> None: ...
+    def get_spr_set_by_name(self, name: str) -> Set | None: ...
+    def get_spr_set_by_id(self, id: int) -> Set | None: ...
+    def get_spr_set_by_index(self, index: int) -> Set | None: ...
+    def get_spr_by_name(self, name: str) -> Entry | None: ...
+    def get_spr_by_id(self, id: int) -> Entry | None: ...
+    def get_sprites_of_set(self, set_id: int) -> list[Entry]:
+        """Sprite and texture entries of the set, leaving out any another set took over"""
+    def get_set_of_spr(self, id: int) -> Set | None: ...
+    @property
+    def sets(self) -> list[Set]:
+        """Every set lookups can return, in the order they were first added"""
+    @property
+    def sprites(self) -> list[Entry]: ...
+    def conflicts(self) -> list[Conflict]:
+        """Ids and names used more than once across the added files"""
+    def merge(self) -> file.Database:
+        """One file holding the sets and sprites lookups return"""
+
+class Set:
+    @property
+    def id(self) -> int: ...
+    @property
+    def name(self) -> str: ...
+    @property
+    def file_name(self) -> str: ...
+    @property
+    def index(self) -> int: ...
+
+class Entry:
+    @property
+    def id(self) -> int: ...
+    @property
+    def name(self) -> str: ...
+    @property
+    def index(self) -> int: ...
+    @property
+    def set_index(self) -> int: ...
+    @property
+    def texture(self) -> bool: ...
+
+class ConflictKind:
+    SetId: ClassVar[ConflictKind]
+    SetName: ClassVar[ConflictKind]
+    SpriteId: ClassVar[ConflictKind]
+    SpriteName: ClassVar[ConflictKind]
+    def __eq__(self, other: object) -> bool: ...
+
+class Occurrence:
+    @property
+    def file(self) -> int:
+        """Files are numbered in the order they were added"""
+    @property
+    def set(self) -> str: ...
+    @property
+    def id(self) -> int: ...
+    @property
+    def name(self) -> str: ...
+
+class Conflict:
+    @property
+    def kind(self) -> ConflictKind: ...
+    @property
+    def occurrences(self) -> list[Occurrence]: ...
+    @property
+    def winner(self) -> int | None:
+        """Index into occurrences of the one lookups return"""
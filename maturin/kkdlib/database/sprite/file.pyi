@@ -0,0 +1,105 @@
+from typing import ClassVar
+
+from ... import spr
+
+class Entry:
+    def __init__(self) -> None: ...
+    @property
+    def id(self) -> int: ...
+    @id.setter
+    def id(self, value: int) -> None: ...
+    @property
+    def name(self) -> str: ...
+    @name.setter
+    def name(self, value: str) -> None: ...
+    @property
+    def index(self) -> int: ...
+    @index.setter
+    def index(self, value: int) -> None: ...
+    @property
+    def texture(self) -> bool: ...
+    @texture.setter
+    def texture(self, value: bool) -> None: ...
+    def assign_id(self) -> None:
+        """Sets the id to the murmurhash of the name, like the game expects"""
+
+class Set:
+    def __init__(self) -> None: ...
+    def add_sprite(self, entry: Entry) -> None: ...
+    def replace_sprite(self, index: int, entry: Entry) -> bool: ...
+    def remove_sprite(self, index: int) -> Entry | None: ...
+    def renumber(self) -> None:
+        """Numbers textures and sprites separately, in the order they appear"""
+    def assign_ids(self) -> None: ...
+    @staticmethod
+    def from_spr_set(spr_set: spr.Set, set_name: str, file_name: str) -> Set:
+        """Entries for every sprite and texture, named and numbered like the game's databases"""
+    @property
+    def sprites(self) -> list[Entry]:
+        """Copies, edited entries have to be put back with replace_sprite"""
+    @property
+    def id(self) -> int: ...
+    @id.setter
+    def id(self, value: int) -> None: ...
+    @property
+    def name(self) -> str: ...
+    @name.setter
+    def name(self, value: str) -> None: ...
+    @property
+    def file_name(self) -> str: ...
+    @file_name.setter
+    def file_name(self, value: str) -> None: ...
+    @property
+    def index(self) -> int: ...
+    @index.setter
+    def index(self, value: int) -> None: ...
+
+class Database:
+    def __init__(self) -> None: ...
+    def add_set(self, set: Set) -> None: ...
+    def replace_set(self, index: int, set: Set) -> bool: ...
+    def remove_set(self, index: int) -> Set | None: ...
+    def renumber(self) -> None: ...
+    def assign_ids(self) -> None: ...
+    @staticmethod
+    def from_buf(data: bytes, modern: bool) -> Database: ...
+    def to_buf(self) -> bytes | None: ...
+    @property
+    def sets(self) -> list[Set]:
+        """Copies, edited sets have to be put back with replace_set"""
+    @property
+    def ready(self) -> bool: ...
+    @ready.setter
+    def ready(self, value: bool) -> None: ...
+    @property
+    def modern(self) -> bool: ...
+    @modern.setter
+    def modern(self, value: bool) -> None: ...
+    @property
+    def big_endian(self) -> bool: ...
+    @big_endian.setter
+    def big_endian(self, value: bool) -> None: ...
+    @property
+    def is_x(self) -> bool: ...
+    @is_x.setter
+    def is_x(self, value: bool) -> None: ...
+
+class Change:
+    Added: ClassVar[Change]
+    Removed: ClassVar[Change]
+    Changed: ClassVar[Change]
+    def __eq__(self, other: object) -> bool: ...
+
+class Difference:
+    @property
+    def change(self) -> Change: ...
+    @property
+    def set(self) -> str: ...
+    @property
+    def sprite(self) -> str | None:
+        """None when the difference is the set itself"""
+    @property
+    def id(self) -> int: ...
+
+def diff(a: Database, b: Database) -> list[Difference]:
+    """Sets and sprites matched by id, added or removed sets are listed without their sprites"""
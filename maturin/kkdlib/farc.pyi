@@ -0,0 +1,52 @@
+from collections.abc import Iterator
+from typing import ClassVar
+
+class Signature:
+    Uncompressed: ClassVar[Signature]
+    Compressed: ClassVar[Signature]
+    Encrypted: ClassVar[Signature]
+
+class Flags:
+    NONE: ClassVar[Flags]
+    GZIP: ClassVar[Flags]
+    AES: ClassVar[Flags]
+    GZIP_AES: ClassVar[Flags]
+
+class Farc:
+    def __init__(self) -> None: ...
+    @staticmethod
+    def open(path: str) -> Farc: ...
+    @staticmethod
+    def from_buf(data: bytes, unpack: bool) -> Farc: ...
+    def to_buf(self) -> bytes | None: ...
+    def read(self, path: str, unpack: bool, save: bool) -> None: ...
+    def write(self, path: str, add_extension: bool, get_files: bool) -> None: ...
+    def add_file_data(self, name: str, data: bytes) -> None: ...
+    def names(self) -> list[str]: ...
+    def __len__(self) -> int: ...
+    def __contains__(self, name: str) -> bool: ...
+    def __getitem__(self, name: str) -> bytes: ...
+    def __iter__(self) -> Iterator[str]: ...
+    @property
+    def files(self) -> dict[str, bytes]:
+        """Copies every entry, prefer indexing by name for single files"""
+    @property
+    def flags(self) -> Flags: ...
+    @flags.setter
+    def flags(self, value: Flags) -> None: ...
+    @property
+    def signature(self) -> Signature: ...
+    @signature.setter
+    def signature(self, value: Signature) -> None: ...
+    @property
+    def compression_level(self) -> int: ...
+    @compression_level.setter
+    def compression_level(self, value: int) -> None: ...
+    @property
+    def alignment(self) -> int: ...
+    @alignment.setter
+    def alignment(self, value: int) -> None: ...
+    @property
+    def ft(self) -> bool: ...
+    @ft.setter
+    def ft(self, value: bool) -> None: ...
@@ -0,0 +1,5 @@
+def fnv1a64m(data: bytes | str) -> int: ...
+def murmurhash(data: bytes | str) -> int: ...
+def crc16_ccitt(data: bytes | str) -> int: ...
+def xxh3_64bits(data: bytes | str) -> int: ...
+def adler32(data: bytes | str, adler: int = 1) -> int: ...
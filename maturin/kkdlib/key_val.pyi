@@ -0,0 +1,69 @@
+from types import TracebackType
+
+class KeyValNode:
+    def __init__(self) -> None: ...
+    @staticmethod
+    def parse(data: str) -> KeyValNode: ...
+    def parse_into(self, data: str) -> None: ...
+    def insert(self, key: str, value: str) -> None: ...
+    @property
+    def value(self) -> str | None: ...
+    def is_empty(self) -> bool: ...
+    def has_key(self, key: str) -> bool: ...
+    def remove(self, key: str) -> KeyValNode | None:
+        """Removes `key` and everything under it, parents left empty are removed too"""
+    def take_value(self, key: str) -> str | None:
+        """Takes the value at `key` but leaves anything nested under it"""
+    def length(self) -> int | None:
+        """`xxx.length`, or the run of numbered children starting at 0 when it's missing"""
+    def read_bool(self, key: str) -> bool:
+        """Raises KeyError if `key` is missing and ValueError if it can't be parsed"""
+    def read_f32(self, key: str) -> float: ...
+    def read_i32(self, key: str) -> int: ...
+    def read_u32(self, key: str) -> int: ...
+    def read_str(self, key: str) -> str: ...
+    def get(self, key: str) -> KeyValNode | None: ...
+    def keys(self) -> list[str]: ...
+    def children(self) -> list[tuple[str, KeyValNode]]: ...
+    def items(self) -> list[KeyValNode | None]: ...
+
+class KeyVal:
+    def __init__(self) -> None: ...
+    def parse(self, data: str) -> None: ...
+    @staticmethod
+    def from_data(data: str) -> KeyVal: ...
+    def scope(self, key: str | int) -> Scope:
+        """Opens `key` for the duration of a `with` block, reads inside are relative to it"""
+    def has_key(self, key: str) -> bool: ...
+    def read_bool(self, key: str) -> bool | None: ...
+    def read_f32(self, key: str) -> float | None: ...
+    def read_i32(self, key: str) -> int | None: ...
+    def read_u32(self, key: str) -> int | None: ...
+    def read_str(self, key: str) -> str | None: ...
+    @property
+    def root(self) -> KeyValNode: ...
+
+class Scope:
+    def __enter__(self) -> KeyVal: ...
+    def __exit__(
+        self,
+        exc_type: type[BaseException] | None,
+        exc_value: BaseException | None,
+        traceback: TracebackType | None,
+        /,
+    ) -> bool: ...
+
+class KeyValWriter:
+    """Builds key_val text, str() gives the lines sorted by key"""
+    def __init__(self) -> None: ...
+    def is_empty(self) -> bool: ...
+    def write_str(self, key: str, value: str) -> None: ...
+    def write_bool(self, key: str, value: bool) -> None: ...
+    def write_f32(self, key: str, value: float) -> None: ...
+    def write_i32(self, key: str, value: int) -> None: ...
+    def write_u32(self, key: str, value: int) -> None: ...
+    def write_length(self, key: str, length: int) -> None:
+        """Writes `key.length`"""
+    def write_node(self, key: str, node: KeyValNode) -> None:
+        """Writes every value under `node`, an empty key writes it at the top level"""
+    def remove(self, key: str) -> str | None: ...
@@ -0,0 +1,107 @@
+from typing import ClassVar
+
+from . import txp
+
+class ResolutionMode:
+    QVGA: ClassVar[ResolutionMode]
+    VGA: ClassVar[ResolutionMode]
+    SVGA: ClassVar[ResolutionMode]
+    XGA: ClassVar[ResolutionMode]
+    SXGA: ClassVar[ResolutionMode]
+    SXGAPlus: ClassVar[ResolutionMode]
+    UXGA: ClassVar[ResolutionMode]
+    WVGA: ClassVar[ResolutionMode]
+    WSVGA: ClassVar[ResolutionMode]
+    WXGA: ClassVar[ResolutionMode]
+    FWXGA: ClassVar[ResolutionMode]
+    WUXGA: ClassVar[ResolutionMode]
+    WQXGA: ClassVar[ResolutionMode]
+    HD: ClassVar[ResolutionMode]
+    FHD: ClassVar[ResolutionMode]
+    UHD: ClassVar[ResolutionMode]
+    Res3KatUHD: ClassVar[ResolutionMode]
+    Res3K: ClassVar[ResolutionMode]
+    QuadHD: ClassVar[ResolutionMode]
+    WQVGA: ClassVar[ResolutionMode]
+    QuarterHD: ClassVar[ResolutionMode]
+    XGAPlus: ClassVar[ResolutionMode]
+    Res1176x664: ClassVar[ResolutionMode]
+    Res1200x960: ClassVar[ResolutionMode]
+    WXGA1280x900: ClassVar[ResolutionMode]
+    SXGAMinus: ClassVar[ResolutionMode]
+    FWXGA1366x768: ClassVar[ResolutionMode]
+    WXGAPlus: ClassVar[ResolutionMode]
+    HDPlus: ClassVar[ResolutionMode]
+    WSXGA: ClassVar[ResolutionMode]
+    WSXGAPlus: ClassVar[ResolutionMode]
+    Res1920x1440: ClassVar[ResolutionMode]
+    QWXGA: ClassVar[ResolutionMode]
+    def resolution(self) -> tuple[int, int]: ...
+
+class Info:
+    def __init__(self) -> None: ...
+    @property
+    def texid(self) -> int: ...
+    @texid.setter
+    def texid(self, value: int) -> None: ...
+    @property
+    def rotate(self) -> int: ...
+    @rotate.setter
+    def rotate(self, value: int) -> None: ...
+    @property
+    def px(self) -> float: ...
+    @px.setter
+    def px(self, value: float) -> None: ...
+    @property
+    def py(self) -> float: ...
+    @py.setter
+    def py(self, value: float) -> None: ...
+    @property
+    def width(self) -> float: ...
+    @width.setter
+    def width(self, value: float) -> None: ...
+    @property
+    def height(self) -> float: ...
+    @height.setter
+    def height(self, value: float) -> None: ...
+    @property
+    def attr(self) -> int: ...
+    @attr.setter
+    def attr(self, value: int) -> None: ...
+    @property
+    def resolution_mode(self) -> ResolutionMode: ...
+    @resolution_mode.setter
+    def resolution_mode(self, value: ResolutionMode) -> None: ...
+
+class Set:
+    def __init__(self) -> None: ...
+    def set_txp(self, txp: txp.Set, names: list[str]) -> None: ...
+    def add_spr(self, info: Info, name: str) -> None: ...
+    @staticmethod
+    def from_buf(data: bytes, modern: bool) -> Set: ...
+    def to_buf(self) -> bytes | None: ...
+    def convert_resolution(self, from_mode: ResolutionMode, to_mode: ResolutionMode) -> Set | None: ...
+    @property
+    def sprites(self) -> dict[str, Info]: ...
+    @property
+    def textures(self) -> dict[str, txp.Image]: ...
+    @property
+    def ready(self) -> bool: ...
+    @ready.setter
+    def ready(self, value: bool) -> None: ...
+    @property
+    def modern(self) -> bool: ...
+    @modern.setter
+    def modern(self, value: bool) -> None: ...
+    @property
+    def big_endian(self) -> bool: ...
+    @big_endian.setter
+    def big_endian(self, value: bool) -> None: ...
+    @property
+    def is_x(self) -> bool: ...
+    @is_x.setter
+    def is_x(self, value: bool) -> None: ...
+    @property
+    def flag(self) -> int: ...
+    @flag.setter
+    def flag(self, value: int) -> None: ...
@@ -0,0 +1,94 @@
+from typing_extensions import Buffer as _Buffer
+from typing import ClassVar
+
+from . import Buffer
+
+class Format:
+    A8: ClassVar[Format]
+    RGB8: ClassVar[Format]
+    RGBA8: ClassVar[Format]
+    RGB5: ClassVar[Format]
+    RGB5A1: ClassVar[Format]
+    RGBA4: ClassVar[Format]
+    BC1: ClassVar[Format]
+    BC1a: ClassVar[Format]
+    BC2: ClassVar[Format]
+    BC3: ClassVar[Format]
+    BC4: ClassVar[Format]
+    BC5: ClassVar[Format]
+    L8: ClassVar[Format]
+    L8A8: ClassVar[Format]
+    BC7: ClassVar[Format]
+    BC6H: ClassVar[Format]
+
+class Set:
+    def __init__(self) -> None: ...
+    def add_file(self, texture: Texture) -> None: ...
+    @staticmethod
+    def from_buf(data: bytes, big_endian: bool, modern: int | None) -> Set: ...
+    def to_buf(self, big_endian: bool, modern: int | None) -> bytes | None: ...
+    @property
+    def textures(self) -> list[Image]: ...
+
+class Image(_Buffer):
+    """Decoded first mipmap of a texture, exposes its rgba through the buffer protocol"""
+
+    def __buffer__(self, flags: int, /) -> memoryview: ...
+    @property
+    def width(self) -> int: ...
+    @property
+    def height(self) -> int: ...
+    @property
+    def rgba(self) -> memoryview:
+        """Shaped (height, width, 4), a view of the image itself"""
+
+class Texture:
+    def __init__(self) -> None: ...
+    def add_mipmap(self, mipmap: Mipmap) -> None: ...
+    def decode_ycbcr(self) -> bytes | None: ...
+    @staticmethod
+    def encode_ycbcr(width: int, height: int, data: bytes) -> Texture | None: ...
+    @staticmethod
+    def from_rgba(width: int, height: int, data: bytes, format: Format) -> Texture | None: ...
+    def resize(self, width: int, height: int) -> Texture | None: ...
+    @property
+    def is_ycbcr(self) -> bool: ...
+    @property
+    def has_cube_map(self) -> bool: ...
+    @has_cube_map.setter
+    def has_cube_map(self, value: bool) -> None: ...
+    @property
+    def array_size(self) -> int: ...
+    @array_size.setter
+    def array_size(self, value: int) -> None: ...
+    @property
+    def mipmaps_count(self) -> int: ...
+    @mipmaps_count.setter
+    def mipmaps_count(self, value: int) -> None: ...
+
+class Mipmap(_Buffer):
+    """Exposes its data through the buffer protocol, it can't be changed while a view of it is
+    alive"""
+
+    def __init__(self) -> None: ...
+    def __buffer__(self, flags: int, /) -> memoryview: ...
+    def data(self) -> memoryview | None: ...
+    def set_data(self, data: bytes) -> None: ...
+    def rgba(self) -> Buffer | None:
+        """Decoded pixels shaped (height, width, 4)"""
+    @staticmethod
+    def from_rgba(width: int, height: int, data: bytes, format: Format) -> Mipmap | None: ...
+    @property
+    def size(self) -> int: ...
+    @property
+    def width(self) -> int: ...
+    @width.setter
+    def width(self, value: int) -> None: ...
+    @property
+    def height(self) -> int: ...
+    @height.setter
+    def height(self, value: int) -> None: ...
+    @property
+    def format(self) -> Format: ...
+    @format.setter
+    def format(self, value: Format) -> None: ...
@@ -0,0 +1,89 @@
+# Edits made through values read from their parent have to land in the parent
+import copy
+import unittest
+
+from kkdlib import aet
+
+
+def scene_with(*names: str) -> aet.Scene:
+    scene = aet.Scene()
+    for name in names:
+        scene.root.append(aet.Layer(name))
+    return scene
+
+
+class NestedEdits(unittest.TestCase):
+    def test_layer_fields(self) -> None:
+        scene = scene_with("a")
+        scene.root.layers[0].name = "x"
+        self.assertEqual(scene.root[0].name, "x")
+        scene.root[-1].start_time = 5.0
+        self.assertEqual(scene.root.layers[0].start_time, 5.0)
+
+    def test_layer_list(self) -> None:
+        scene = scene_with("a")
+        scene.root.layers.append(aet.Layer("b"))
+        self.assertEqual([layer.name for layer in scene.root], ["a", "b"])
+        del scene.root.layers[0]
+        self.assertEqual([layer.name for layer in scene.root.layers], ["b"])
+
+    def test_video_curves(self) -> None:
+        scene = scene_with("a")
+        layer = scene.root[0]
+        layer.video = aet.LayerVideo()
+        layer.video.pos_x = aet.FCurve.constant(3.0)
+        self.assertEqual(scene.root[0].video.pos_x.keys[0].value, 3.0)
+        layer.video.pos_x.keys[0].value = 4.0
+        layer.video.opacity.keys.append(aet.FCurveKey(10.0, 0.5, 0.0))
+        video = scene.root.layers[0].video
+        self.assertEqual(video.pos_x.keys[0].value, 4.0)
+        self.assertEqual(len(video.opacity.keys), 2)
+
+    def test_unset_video(self) -> None:
+        self.assertIsNone(scene_with("a").root[0].video)
+
+    def test_set_scenes(self) -> None:
+        set = aet.Set()
+        set.scenes.append(scene_with("a"))
+        set.scenes[0].root[0].name = "x"
+        set.scenes[0].comps.append(aet.Composition())
+        set.scenes[0].comps[0].append(set.scenes[0].root[0])
+        self.assertEqual(set.scenes[0].comps[0][0].name, "x")
+
+    def test_layer_path(self) -> None:
+        scene = scene_with("a", "b")
+        scene.layer(aet.LayerPath(None, 1)).name = "x"
+        for _, _, layer in scene.layers_recursive():
+            layer.end_time = 2.0
+        self.assertEqual([layer.name for layer in scene.root], ["a", "x"])
+        self.assertEqual([layer.end_time for layer in scene.root], [2.0, 2.0])
+
+    def test_index_loop(self) -> None:
+        scene = scene_with("a", "b", "c")
+        layers = scene.root.layers
+        for i in range(len(layers)):
+            layers[i].name += "!"
+        self.assertEqual([layer.name for layer in scene.root], ["a!", "b!", "c!"])
+        self.assertEqual(layers[-1].name, "c!")
+        with self.assertRaises(IndexError):
+            layers[3]
+
+    def test_reentrant_callback(self) -> None:
+        scene = scene_with("b", "a")
+        scene.root.layers.sort(key=lambda layer: (scene.root[0].name, layer.name))
+        self.assertEqual([layer.name for layer in scene.root], ["a", "b"])
+
+    def test_encode_empty_range(self) -> None:
+        with self.assertRaises(ValueError):
+            scene_with("a").encode_range("apng", 1.0, 0.0)
+
+    def test_copy_is_detached(self) -> None:
+        scene = scene_with("a")
+        layer = copy.copy(scene.root[0])
+        self.assertIsInstance(layer, aet.Layer)
+        layer.name = "x"
+        self.assertEqual(scene.root[0].name, "a")
+
+
+if __name__ == "__main__":
+    unittest.main()
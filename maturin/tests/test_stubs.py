@@ -0,0 +1,25 @@
+# Members and signatures of the stubs are checked against the wheel by mypy.stubtest in CI, this
+# covers the packaging around them
+# Run after installing the wheel: python -m unittest discover maturin/tests
+import unittest
+from pathlib import Path
+
+import kkdlib
+
+STUBS = Path(kkdlib.__file__).parent
+
+
+class StubTest(unittest.TestCase):
+    def test_submodules_importable(self) -> None:
+        import kkdlib.database.sprite.file
+        import kkdlib.farc
+
+        self.assertIs(kkdlib.farc, kkdlib.kkdlib.farc)
+        self.assertIs(kkdlib.database.sprite.file, kkdlib.kkdlib.database.sprite.file)
+
+    def test_py_typed(self) -> None:
+        self.assertTrue((STUBS / "py.typed").exists())
+
+
+if __name__ == "__main__":
+    unittest.main()
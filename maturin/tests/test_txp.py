@@ -0,0 +1,40 @@
+# Texture data is handed to Python as views of the texture, not copies
+import unittest
+
+from kkdlib import txp
+
+
+def pixels(width: int, height: int) -> bytes:
+    return bytes(range(width * height * 4))
+
+
+class Views(unittest.TestCase):
+    def test_image_shares_memory(self) -> None:
+        set = txp.Set()
+        set.add_file(txp.Texture.from_rgba(2, 2, pixels(2, 2), txp.Format.RGBA8))
+        image = set.textures[0]
+        view = memoryview(image)
+        self.assertIs(view.obj, image)
+        self.assertIs(image.rgba.obj, image)
+        self.assertEqual(view.shape, (2, 2, 4))
+        self.assertEqual(view.tobytes(), pixels(2, 2))
+
+    def test_mipmap_shares_memory(self) -> None:
+        mipmap = txp.Mipmap.from_rgba(2, 2, pixels(2, 2), txp.Format.RGBA8)
+        view = mipmap.data()
+        self.assertIs(view.obj, mipmap)
+        self.assertEqual(view.tobytes(), pixels(2, 2))
+        self.assertEqual(memoryview(mipmap).tobytes(), pixels(2, 2))
+
+    def test_mipmap_locked_while_viewed(self) -> None:
+        mipmap = txp.Mipmap.from_rgba(2, 2, pixels(2, 2), txp.Format.RGBA8)
+        view = memoryview(mipmap)
+        with self.assertRaises(RuntimeError):
+            mipmap.set_data(bytes(16))
+        view.release()
+        mipmap.set_data(bytes(16))
+        self.assertEqual(mipmap.data().tobytes(), bytes(16))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,329 @@
+use super::*;
+use image::{ImageEncoder, ImageError, ImageFormat, ImageResult, RgbaImage};
+use std::ffi::c_void;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationFormat {
+	Apng,
+	Gif,
+	WebP,
+}
+
+// Frames start, start + step, ... up to but not including end, in scene frames
+pub struct RenderRange<'a> {
+	scene: &'a Scene,
+	renderer: &'a Renderer<'a>,
+	start: f32,
+	step: f32,
+	index: usize,
+	len: usize,
+}
+
+impl Scene {
+	pub fn render_range<'a>(
+		&'a self,
+		renderer: &'a Renderer<'a>,
+		start: f32,
+		end: f32,
+		step: f32,
+	) -> RenderRange<'a> {
+		let len = if step > 0.0 && end > start {
+			((end - start) / step).ceil() as usize
+		} else {
+			0
+		};
+		RenderRange {
+			scene: self,
+			renderer,
+			start,
+			step,
+			index: 0,
+			len,
+		}
+	}
+}
+
+impl Iterator for RenderRange<'_> {
+	type Item = RgbaImage;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.index == self.len {
+			return None;
+		}
+		let frame = self.start + self.index as f32 * self.step;
+		self.index += 1;
+		Some(self.renderer.render(self.scene, frame))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.len - self.index;
+		(len, Some(len))
+	}
+}
+
+impl ExactSizeIterator for RenderRange<'_> {}
+
+impl RenderRange<'_> {
+	// How long each rendered frame is shown for at the scene's frame rate
+	pub fn frame_duration_ms(&self) -> u32 {
+		let fps = if self.scene.fps > 0.0 {
+			self.scene.fps
+		} else {
+			60.0
+		};
+		(self.step / fps * 1000.0).round().max(1.0) as u32
+	}
+
+	// Writes <name>_0000.png, <name>_0001.png, ... into dir
+	pub fn save_pngs(self, dir: &Path, name: &str) -> ImageResult<Vec<PathBuf>> {
+		std::fs::create_dir_all(dir).map_err(ImageError::IoError)?;
+		self.enumerate()
+			.map(|(index, image)| {
+				let path = dir.join(format!("{name}_{index:04}.png"));
+				image.save_with_format(&path, ImageFormat::Png)?;
+				Ok(path)
+			})
+			.collect()
+	}
+
+	// Loops forever in every format
+	pub fn encode<W: Write>(self, format: AnimationFormat, out: W) -> Result<(), AnimationError> {
+		if self.len() == 0 {
+			return Err(AnimationError::Empty);
+		}
+
+		let (width, height) = (self.scene.width, self.scene.height);
+		let duration = self.frame_duration_ms();
+		match format {
+			AnimationFormat::Apng => encode_apng(self, width, height, duration, out)?,
+			AnimationFormat::Gif => {
+				let mut encoder = image::codecs::gif::GifEncoder::new_with_speed(out, 10);
+				encoder.set_repeat(image::codecs::gif::Repeat::Infinite)?;
+				encoder.encode_frames(self.map(|image| {
+					image::Frame::from_parts(
+						image,
+						0,
+						0,
+						image::Delay::from_numer_denom_ms(duration, 1),
+					)
+				}))?;
+			}
+			AnimationFormat::WebP => encode_webp(self, width, height, duration, out)?,
+		}
+		Ok(())
+	}
+}
+
+#[derive(Debug)]
+pub enum AnimationError {
+	// None of the formats can hold an animation without frames
+	Empty,
+	Image(ImageError),
+}
+
+impl std::fmt::Display for AnimationError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Empty => write!(f, "the range has no frames to encode"),
+			Self::Image(err) => write!(f, "{err}"),
+		}
+	}
+}
+
+impl std::error::Error for AnimationError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::Empty => None,
+			Self::Image(err) => Some(err),
+		}
+	}
+}
+
+impl From<ImageError> for AnimationError {
+	fn from(err: ImageError) -> Self {
+		Self::Image(err)
+	}
+}
+
+#[cfg(feature = "pyo3")]
+impl From<AnimationError> for PyErr {
+	fn from(err: AnimationError) -> Self {
+		match err {
+			AnimationError::Empty => pyo3::exceptions::PyValueError::new_err(err.to_string()),
+			AnimationError::Image(err) => pyo3::exceptions::PyIOError::new_err(err.to_string()),
+		}
+	}
+}
+
+// Chunks of a still PNG after its signature, or of a WebP after its RIFF header, as fourcc and data
+fn chunks(mut data: &[u8], png: bool) -> Vec<(&[u8], &[u8])> {
+	let mut chunks = Vec::new();
+	while data.len() >= 8 {
+		let (fourcc, size) = if png {
+			(
+				&data[4..8],
+				u32::from_be_bytes(data[..4].try_into().unwrap()),
+			)
+		} else {
+			(
+				&data[..4],
+				u32::from_le_bytes(data[4..8].try_into().unwrap()),
+			)
+		};
+		let size = size as usize;
+		chunks.push((fourcc, &data[8..(8 + size).min(data.len())]));
+		// PNG chunks end in a CRC, RIFF chunks are padded to an even size
+		let end = if png {
+			8 + size + 4
+		} else {
+			8 + size + size % 2
+		};
+		data = &data[end.min(data.len())..];
+	}
+	chunks
+}
+
+// The image crate only writes still PNGs and WebPs, so each frame is encoded on its own and its
+// image data is moved into the frame chunks of an animated container
+
+fn encode_apng<W: Write>(
+	frames: RenderRange,
+	width: u32,
+	height: u32,
+	duration: u32,
+	mut out: W,
+) -> ImageResult<()> {
+	fn chunk(out: &mut Vec<u8>, fourcc: &[u8; 4], data: &[u8]) {
+		out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+		let start = out.len();
+		out.extend_from_slice(fourcc);
+		out.extend_from_slice(data);
+		let crc = &out[start..];
+		let crc = unsafe {
+			libdeflate_sys::libdeflate_crc32(0, crc.as_ptr() as *const c_void, crc.len())
+		};
+		out.extend_from_slice(&crc.to_be_bytes());
+	}
+
+	let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+	let frame_count = frames.len() as u32;
+	// fcTL and fdAT chunks share one sequence
+	let mut sequence = 0u32;
+	for (index, image) in frames.enumerate() {
+		let mut still = Vec::new();
+		image::codecs::png::PngEncoder::new(&mut still).write_image(
+			&image,
+			width,
+			height,
+			image::ExtendedColorType::Rgba8,
+		)?;
+
+		let mut fctl = Some(sequence);
+		for (fourcc, data) in chunks(&still[8.min(still.len())..], true) {
+			match fourcc {
+				b"IHDR" if index == 0 => {
+					chunk(&mut png, b"IHDR", data);
+					// Frame count, loop forever
+					let mut actl = frame_count.to_be_bytes().to_vec();
+					actl.extend_from_slice(&0u32.to_be_bytes());
+					chunk(&mut png, b"acTL", &actl);
+				}
+				b"IDAT" => {
+					if let Some(sequence) = fctl.take() {
+						let mut fctl = Vec::new();
+						for value in [sequence, width, height, 0, 0] {
+							fctl.extend_from_slice(&value.to_be_bytes());
+						}
+						fctl.extend_from_slice(
+							&(duration.min(u16::MAX as u32) as u16).to_be_bytes(),
+						);
+						fctl.extend_from_slice(&1000u16.to_be_bytes());
+						// Don't dispose, every frame covers the whole canvas
+						fctl.extend_from_slice(&[0, 0]);
+						chunk(&mut png, b"fcTL", &fctl);
+					}
+					sequence += 1;
+
+					// The first frame is also the still image shown without APNG support
+					if index == 0 {
+						chunk(&mut png, b"IDAT", data);
+					} else {
+						let mut fdat = sequence.to_be_bytes().to_vec();
+						fdat.extend_from_slice(data);
+						chunk(&mut png, b"fdAT", &fdat);
+						sequence += 1;
+					}
+				}
+				_ => {}
+			}
+		}
+	}
+	chunk(&mut png, b"IEND", &[]);
+
+	out.write_all(&png)?;
+	Ok(())
+}
+
+fn encode_webp<W: Write>(
+	frames: RenderRange,
+	width: u32,
+	height: u32,
+	duration: u32,
+	mut out: W,
+) -> ImageResult<()> {
+	fn chunk(out: &mut Vec<u8>, fourcc: &[u8], data: &[u8]) {
+		out.extend_from_slice(fourcc);
+		out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+		out.extend_from_slice(data);
+		if data.len() % 2 == 1 {
+			out.push(0);
+		}
+	}
+	fn u24(out: &mut Vec<u8>, value: u32) {
+		out.extend_from_slice(&value.to_le_bytes()[..3]);
+	}
+
+	let mut body = Vec::new();
+	body.extend_from_slice(b"WEBP");
+
+	let mut vp8x = vec![0x10 | 0x02, 0, 0, 0];
+	u24(&mut vp8x, width.max(1) - 1);
+	u24(&mut vp8x, height.max(1) - 1);
+	chunk(&mut body, b"VP8X", &vp8x);
+	// Transparent background, loop forever
+	chunk(&mut body, b"ANIM", &[0, 0, 0, 0, 0, 0]);
+
+	for image in frames {
+		let mut still = Vec::new();
+		image::codecs::webp::WebPEncoder::new_lossless(&mut still).encode(
+			&image,
+			width,
+			height,
+			image::ExtendedColorType::Rgba8,
+		)?;
+
+		let mut anmf = Vec::new();
+		u24(&mut anmf, 0);
+		u24(&mut anmf, 0);
+		u24(&mut anmf, width.max(1) - 1);
+		u24(&mut anmf, height.max(1) - 1);
+		u24(&mut anmf, duration.min(0xFFFFFF));
+		// Don't blend with the previous frame, don't dispose
+		anmf.push(0x02);
+
+		// Skip RIFF size WEBP, then keep the image chunks
+		for (fourcc, data) in chunks(&still[12.min(still.len())..], false) {
+			if matches!(fourcc, b"ALPH" | b"VP8 " | b"VP8L") {
+				chunk(&mut anmf, fourcc, data);
+			}
+		}
+		chunk(&mut body, b"ANMF", &anmf);
+	}
+
+	out.write_all(b"RIFF")?;
+	out.write_all(&(body.len() as u32).to_le_bytes())?;
+	out.write_all(&body)?;
+	Ok(())
+}
@@ -0,0 +1,264 @@
+use super::*;
+
+// Row major, points are column vectors
+pub type Mat4 = [[f32; 4]; 4];
+
+pub(crate) const IDENTITY: Mat4 = [
+	[1.0, 0.0, 0.0, 0.0],
+	[0.0, 1.0, 0.0, 0.0],
+	[0.0, 0.0, 1.0, 0.0],
+	[0.0, 0.0, 0.0, 1.0],
+];
+
+pub(crate) fn mat4_mul(a: &Mat4, b: &Mat4) -> Mat4 {
+	let mut out = [[0.0; 4]; 4];
+	for (row, out_row) in out.iter_mut().enumerate() {
+		for (col, out) in out_row.iter_mut().enumerate() {
+			*out = (0..4).map(|i| a[row][i] * b[i][col]).sum();
+		}
+	}
+	out
+}
+
+fn translate(x: f32, y: f32, z: f32) -> Mat4 {
+	[
+		[1.0, 0.0, 0.0, x],
+		[0.0, 1.0, 0.0, y],
+		[0.0, 0.0, 1.0, z],
+		[0.0, 0.0, 0.0, 1.0],
+	]
+}
+
+fn scale(x: f32, y: f32, z: f32) -> Mat4 {
+	[
+		[x, 0.0, 0.0, 0.0],
+		[0.0, y, 0.0, 0.0],
+		[0.0, 0.0, z, 0.0],
+		[0.0, 0.0, 0.0, 1.0],
+	]
+}
+
+// Degrees, applied x then y then z like After Effects
+fn rotate(x: f32, y: f32, z: f32) -> Mat4 {
+	let (sx, cx) = x.to_radians().sin_cos();
+	let (sy, cy) = y.to_radians().sin_cos();
+	let (sz, cz) = z.to_radians().sin_cos();
+	let rot_x = [
+		[1.0, 0.0, 0.0, 0.0],
+		[0.0, cx, -sx, 0.0],
+		[0.0, sx, cx, 0.0],
+		[0.0, 0.0, 0.0, 1.0],
+	];
+	let rot_y = [
+		[cy, 0.0, sy, 0.0],
+		[0.0, 1.0, 0.0, 0.0],
+		[-sy, 0.0, cy, 0.0],
+		[0.0, 0.0, 0.0, 1.0],
+	];
+	let rot_z = [
+		[cz, -sz, 0.0, 0.0],
+		[sz, cz, 0.0, 0.0],
+		[0.0, 0.0, 1.0, 0.0],
+		[0.0, 0.0, 0.0, 1.0],
+	];
+	mat4_mul(&rot_z, &mat4_mul(&rot_y, &rot_x))
+}
+
+impl LayerVideo {
+	// Transform from the layer's own space into its composition at the given frame
+	// anchor -> scale -> rotation -> orientation -> position
+	pub fn matrix(&self, frame: f32) -> Mat4 {
+		let (anchor_z, pos_z, scale_z, rot_x, rot_y, dir) = match &self._3d {
+			Some(_3d) => (
+				_3d.anchor_z.interpolate(frame),
+				_3d.pos_z.interpolate(frame),
+				_3d.scale_z.interpolate(frame),
+				_3d.rot_x.interpolate(frame),
+				_3d.rot_y.interpolate(frame),
+				(
+					_3d.dir_x.interpolate(frame),
+					_3d.dir_y.interpolate(frame),
+					_3d.dir_z.interpolate(frame),
+				),
+			),
+			None => (0.0, 0.0, 1.0, 0.0, 0.0, (0.0, 0.0, 0.0)),
+		};
+
+		let anchor = translate(
+			-self.anchor_x.interpolate(frame),
+			-self.anchor_y.interpolate(frame),
+			-anchor_z,
+		);
+		let scale = scale(
+			self.scale_x.interpolate(frame),
+			self.scale_y.interpolate(frame),
+			scale_z,
+		);
+		let rotation = rotate(rot_x, rot_y, self.rot_z.interpolate(frame));
+		let orientation = rotate(dir.0, dir.1, dir.2);
+		let position = translate(
+			self.pos_x.interpolate(frame),
+			self.pos_y.interpolate(frame),
+			pos_z,
+		);
+
+		mat4_mul(
+			&position,
+			&mat4_mul(
+				&orientation,
+				&mat4_mul(&rotation, &mat4_mul(&scale, &anchor)),
+			),
+		)
+	}
+}
+
+// A video layer as it should be drawn on one frame
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "pyo3", pyclass(get_all))]
+pub struct EvaluatedLayer {
+	pub id: LayerId,
+	pub name: String,
+	// Time inside the layer, what the video source is picked with
+	pub frame: f32,
+	// Layer space to scene space, including parents and the compositions above it
+	pub matrix: Mat4,
+	pub opacity: f32,
+	pub blend_mode: BlendMode,
+	pub is_3d: bool,
+	// Index into Scene::videos
+	pub video: usize,
+	// Index into the video's sources, None if it has none
+	pub source: Option<usize>,
+	// False when the layer or a composition above it is hidden or fully transparent
+	pub visible: bool,
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl EvaluatedLayer {
+	// Affine 2D part of the matrix, for layers that aren't 3D
+	pub fn matrix_2d(&self) -> [[f32; 3]; 3] {
+		let m = &self.matrix;
+		[
+			[m[0][0], m[0][1], m[0][3]],
+			[m[1][0], m[1][1], m[1][3]],
+			[0.0, 0.0, 1.0],
+		]
+	}
+}
+
+impl EvaluatedLayer {
+	pub fn sprite<'a>(&self, scene: &'a Scene) -> Option<&'a VideoSource> {
+		scene.videos.get(self.video)?.sources.get(self.source?)
+	}
+}
+
+// Things passed down from the composition layer when evaluating a nested composition
+struct Parent {
+	matrix: Mat4,
+	opacity: f32,
+	visible: bool,
+	depth: usize,
+}
+
+impl Scene {
+	fn evaluate_comp(
+		&self,
+		comp: &Composition,
+		frame: f32,
+		parent: &Parent,
+		out: &mut Vec<EvaluatedLayer>,
+	) {
+		// A composition using itself would never end
+		if parent.depth > self.comps.len() {
+			return;
+		}
+
+		// The first layer is on top, so draw from the back
+		for layer in comp.layers.iter().rev() {
+			if frame < layer.start_time || frame >= layer.end_time {
+				continue;
+			}
+			let Some(video) = &layer.video else {
+				continue;
+			};
+
+			let matrix = mat4_mul(&parent.matrix, &layer_matrix(comp, layer, frame, 0));
+			let opacity = parent.opacity * video.opacity.interpolate(frame);
+			let visible = parent.visible && layer.flags.video_active() && opacity > 0.0;
+			let layer_frame = (frame - layer.start_time) * layer.time_scale + layer.offset_time;
+
+			match layer.item {
+				Some(Item::Composition(index)) => {
+					let Some(inner) = self.comps.get(index) else {
+						continue;
+					};
+					let parent = Parent {
+						matrix,
+						opacity,
+						visible,
+						depth: parent.depth + 1,
+					};
+					self.evaluate_comp(inner, layer_frame, &parent, out);
+				}
+				Some(Item::Video(index)) => {
+					let Some(item) = self.videos.get(index) else {
+						continue;
+					};
+					// Sprites hold on the last source once the layer runs past them
+					let source = if item.sources.is_empty() {
+						None
+					} else {
+						let source = (layer_frame * item.fpf).max(0.0) as usize;
+						Some(source.min(item.sources.len() - 1))
+					};
+
+					out.push(EvaluatedLayer {
+						id: layer.id,
+						name: layer.name.clone(),
+						frame: layer_frame,
+						matrix,
+						opacity,
+						blend_mode: video.transfer_mode.mode,
+						is_3d: layer.flags.is_3d() || video._3d.is_some(),
+						video: index,
+						source,
+						visible,
+					});
+				}
+				Some(Item::Audio(_)) | None => {}
+			}
+		}
+	}
+
+	pub(super) fn evaluate_root(&self, frame: f32) -> Vec<EvaluatedLayer> {
+		let mut out = Vec::new();
+		let parent = Parent {
+			matrix: IDENTITY,
+			opacity: 1.0,
+			visible: true,
+			depth: 0,
+		};
+		self.evaluate_comp(&self.root, frame, &parent, &mut out);
+		out
+	}
+}
+
+// Parents are in the same composition and only pass on their transform, not opacity
+fn layer_matrix(comp: &Composition, layer: &Layer, frame: f32, depth: usize) -> Mat4 {
+	let local = layer
+		.video
+		.as_ref()
+		.map_or(IDENTITY, |video| video.matrix(frame));
+
+	let parent = layer
+		.parent
+		.and_then(|parent| comp.layers.iter().find(|other| other.id == parent));
+	match parent {
+		// Parent loops in broken files stop once every layer has been visited
+		Some(parent) if depth < comp.layers.len() => {
+			mat4_mul(&layer_matrix(comp, parent, frame, depth + 1), &local)
+		}
+		_ => local,
+	}
+}
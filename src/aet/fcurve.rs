@@ -0,0 +1,303 @@
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "pyo3", pyclass(get_all, set_all))]
+pub struct FCurveKey {
+	pub frame: f32,
+	pub value: f32,
+	pub tangent: f32,
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl FCurveKey {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new(frame: f32, value: f32, tangent: f32) -> Self {
+		Self {
+			frame,
+			value,
+			tangent,
+		}
+	}
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[cfg_attr(feature = "pyo3", pyclass(get_all, set_all))]
+pub struct FCurve {
+	pub keys: Vec<FCurveKey>,
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl FCurve {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __getattribute__(
+		slf: &Bound<'_, Self>,
+		name: &Bound<'_, pyo3::types::PyString>,
+	) -> PyResult<Py<PyAny>> {
+		super::proxy::getattribute(slf, name)
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __setattr__(
+		slf: &Bound<'_, Self>,
+		name: &Bound<'_, pyo3::types::PyString>,
+		value: &Bound<'_, PyAny>,
+	) -> PyResult<()> {
+		super::proxy::setattr(slf.as_any(), name, value)
+	}
+
+	#[cfg_attr(feature = "pyo3", staticmethod)]
+	pub fn constant(value: f32) -> Self {
+		Self {
+			keys: vec![FCurveKey::new(0.0, value, 0.0)],
+		}
+	}
+
+	pub fn interpolate(&self, frame: f32) -> f32 {
+		if self.keys.is_empty() {
+			0.0
+		} else if self.keys.len() == 1 || frame <= self.keys[0].frame {
+			self.keys[0].value
+		} else if frame >= self.keys.last().unwrap().frame {
+			self.keys.last().unwrap().value
+		} else {
+			for [cur, next] in self.keys.array_windows() {
+				if next.frame >= frame {
+					let (f1, p1, t1) = (cur.frame, cur.value, cur.tangent);
+					let (f2, p2, t2) = (next.frame, next.value, next.tangent);
+
+					let df = f2 - f1;
+					let t = (frame - f1) / df;
+					let t_2 = t * t;
+					let t_3 = t_2 * t;
+					let t_23 = 3.0 * t_2;
+					let t_32 = 2.0 * t_3;
+
+					let h00 = t_32 - t_23 + 1.0;
+					let h01 = t_23 - t_32;
+					let h10 = t_3 - 2.0 * t_2 + t;
+					let h11 = t_3 - t_2;
+
+					return (h10 * t1 + h11 * t2) * df + (h01 * p2 + h00 * p1);
+				}
+			}
+
+			unreachable!()
+		}
+	}
+
+	pub fn scale_values(&mut self, factor: f32) {
+		for key in &mut self.keys {
+			key.value *= factor;
+			key.tangent *= factor;
+		}
+	}
+
+	// Slope in value per frame, flat outside the keys like interpolate
+	pub fn derivative(&self, frame: f32) -> f32 {
+		let (Some(first), Some(last)) = (self.keys.first(), self.keys.last()) else {
+			return 0.0;
+		};
+		if self.keys.len() == 1 || frame < first.frame || frame > last.frame {
+			return 0.0;
+		}
+
+		for [cur, next] in self.keys.array_windows() {
+			let df = next.frame - cur.frame;
+			if next.frame >= frame && df > 0.0 {
+				let t = (frame - cur.frame) / df;
+				let t_2 = t * t;
+
+				let dh00 = 6.0 * t_2 - 6.0 * t;
+				let dh10 = 3.0 * t_2 - 4.0 * t + 1.0;
+				let dh11 = 3.0 * t_2 - 2.0 * t;
+
+				return dh10 * cur.tangent
+					+ dh11 * next.tangent
+					+ dh00 * (cur.value - next.value) / df;
+			}
+		}
+
+		0.0
+	}
+
+	// Adds a key, or sets the value of the one already on that frame, and gives it and its
+	// neighbours auto clamped tangents. Returns the index of the key
+	pub fn insert_key(&mut self, frame: f32, value: f32) -> usize {
+		let index = self.keys.partition_point(|key| key.frame < frame);
+		match self.keys.get_mut(index) {
+			Some(key) if key.frame == frame => key.value = value,
+			_ => self.keys.insert(index, FCurveKey::new(frame, value, 0.0)),
+		}
+
+		for i in index.saturating_sub(1)..(index + 2).min(self.keys.len()) {
+			self.keys[i].tangent = self.auto_tangent(i, true);
+		}
+		index
+	}
+
+	pub fn remove_key(&mut self, index: usize) -> Option<FCurveKey> {
+		(index < self.keys.len()).then(|| self.keys.remove(index))
+	}
+
+	// Recomputes every tangent, Catmull-Rom unless clamped. Clamped tangents keep peaks flat and
+	// stop the curve from overshooting its keys
+	pub fn auto_tangents(&mut self, clamped: bool) {
+		let tangents = (0..self.keys.len())
+			.map(|i| self.auto_tangent(i, clamped))
+			.collect::<Vec<_>>();
+		for (key, tangent) in self.keys.iter_mut().zip(tangents) {
+			key.tangent = tangent;
+		}
+	}
+
+	// Replaces the keys with one every step frames from start to end, both included, each with
+	// the curve's exact slope so the shape in between is kept
+	pub fn bake(&mut self, start: f32, end: f32, step: f32) {
+		if step <= 0.0 || end < start {
+			return;
+		}
+
+		let count = ((end - start) / step).ceil() as usize;
+		let mut keys = (0..=count)
+			.map(|i| (start + i as f32 * step).min(end))
+			.map(|frame| FCurveKey::new(frame, self.interpolate(frame), self.derivative(frame)))
+			.collect::<Vec<_>>();
+		keys.dedup_by(|next, cur| next.frame <= cur.frame);
+		self.keys = keys;
+	}
+
+	// Drops keys for as long as the curve stays within tolerance of its current shape, meant
+	// for turning baked curves back into a few keys
+	pub fn simplify(&mut self, tolerance: f32) {
+		if self.keys.len() <= 2 {
+			return;
+		}
+
+		let mut keep = vec![false; self.keys.len()];
+		keep[0] = true;
+		keep[self.keys.len() - 1] = true;
+
+		// Split where the curve is off the most until every span fits
+		let mut spans = vec![(0, self.keys.len() - 1)];
+		while let Some((first, last)) = spans.pop() {
+			if let Some(split) = self.span_error(first, last, tolerance) {
+				keep[split] = true;
+				spans.push((first, split));
+				spans.push((split, last));
+			}
+		}
+
+		// Splitting picks keys greedily, some of them turn out not to be needed
+		let mut kept = (0..self.keys.len())
+			.filter(|&i| keep[i])
+			.collect::<Vec<_>>();
+		let mut i = 1;
+		while i + 1 < kept.len() {
+			if self
+				.span_error(kept[i - 1], kept[i + 1], tolerance)
+				.is_none()
+			{
+				keep[kept.remove(i)] = false;
+			} else {
+				i += 1;
+			}
+		}
+
+		let mut keep = keep.into_iter();
+		self.keys.retain(|_| keep.next().unwrap());
+	}
+
+	// Moves every key to frame * scale + offset, tangents are adjusted so the values stay the
+	// same. A scale of 0 leaves only the first key
+	pub fn retime(&mut self, scale: f32, offset: f32) {
+		if scale == 0.0 {
+			self.keys.truncate(1);
+		}
+
+		for key in &mut self.keys {
+			key.frame = key.frame * scale + offset;
+			key.tangent = if scale == 0.0 {
+				0.0
+			} else {
+				key.tangent / scale
+			};
+		}
+		if scale < 0.0 {
+			self.keys.reverse();
+		}
+	}
+}
+
+impl FCurve {
+	// Where a curve through only the first and last key is furthest off this one, if that's
+	// more than tolerance. Checked on the keys in between and halfway between each of them
+	fn span_error(&self, first: usize, last: usize, tolerance: f32) -> Option<usize> {
+		let span = FCurve {
+			keys: vec![self.keys[first].clone(), self.keys[last].clone()],
+		};
+
+		let mut worst = None;
+		let mut worst_error = tolerance;
+		for i in first + 1..=last {
+			let (prev, key) = (&self.keys[i - 1], &self.keys[i]);
+			let mid = (prev.frame + key.frame) * 0.5;
+			let mut error = (span.interpolate(mid) - self.interpolate(mid)).abs();
+			let split = if i < last {
+				error = error.max((span.interpolate(key.frame) - key.value).abs());
+				i
+			} else {
+				i - 1
+			};
+
+			if split > first && error > worst_error {
+				worst = Some(split);
+				worst_error = error;
+			}
+		}
+		worst
+	}
+
+	fn auto_tangent(&self, index: usize, clamped: bool) -> f32 {
+		let slope = |a: &FCurveKey, b: &FCurveKey| {
+			if b.frame > a.frame {
+				(b.value - a.value) / (b.frame - a.frame)
+			} else {
+				0.0
+			}
+		};
+
+		let key = &self.keys[index];
+		let prev = index.checked_sub(1).map(|i| &self.keys[i]);
+		let next = self.keys.get(index + 1);
+		match (prev, next) {
+			(Some(prev), Some(next)) => {
+				let tangent = slope(prev, next);
+				if !clamped {
+					return tangent;
+				}
+
+				let (before, after) = (slope(prev, key), slope(key, next));
+				if before * after <= 0.0 {
+					0.0
+				} else {
+					tangent.signum() * tangent.abs().min(3.0 * before.abs().min(after.abs()))
+				}
+			}
+			_ if clamped => 0.0,
+			(Some(prev), None) => slope(prev, key),
+			(None, Some(next)) => slope(key, next),
+			(None, None) => 0.0,
+		}
+	}
+}
@@ -0,0 +1,275 @@
+use super::*;
+
+// Where a layer sits in a scene, comp is an index into Scene::comps or None for the root
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "pyo3", pyclass(get_all, eq, hash, frozen))]
+pub struct LayerPath {
+	pub comp: Option<usize>,
+	pub index: usize,
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl LayerPath {
+	#[cfg_attr(feature = "pyo3", new)]
+	#[cfg_attr(feature = "pyo3", pyo3(signature = (comp, index)))]
+	pub fn new(comp: Option<usize>, index: usize) -> Self {
+		Self { comp, index }
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __repr__(&self) -> String {
+		match self.comp {
+			Some(comp) => format!("LayerPath({comp}, {})", self.index),
+			None => format!("LayerPath(None, {})", self.index),
+		}
+	}
+}
+
+// Depth first through composition items, a layer comes right before the layers of the
+// composition it shows. Compositions used by several layers are visited once for each
+pub struct LayersRecursive<'a> {
+	scene: &'a Scene,
+	// Composition and the next layer in it
+	stack: Vec<(Option<usize>, usize)>,
+}
+
+impl<'a> Iterator for LayersRecursive<'a> {
+	// Depth, path and layer
+	type Item = (usize, LayerPath, &'a Layer);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			let depth = self.stack.len().checked_sub(1)?;
+			let (comp, index) = self.stack.last_mut()?;
+			let (comp, layer_index) = (*comp, *index);
+			let Some(layer) = self.scene.comp(comp)?.layers.get(layer_index) else {
+				self.stack.pop();
+				continue;
+			};
+			*index += 1;
+
+			// A composition using itself would never end
+			if let Some(Item::Composition(inner)) = layer.item
+				&& inner < self.scene.comps.len()
+				&& depth < self.scene.comps.len()
+			{
+				self.stack.push((Some(inner), 0));
+			}
+			return Some((depth, LayerPath::new(comp, layer_index), layer));
+		}
+	}
+}
+
+impl Scene {
+	pub fn comp(&self, comp: Option<usize>) -> Option<&Composition> {
+		match comp {
+			Some(index) => self.comps.get(index),
+			None => Some(&self.root),
+		}
+	}
+
+	pub fn comp_mut(&mut self, comp: Option<usize>) -> Option<&mut Composition> {
+		match comp {
+			Some(index) => self.comps.get_mut(index),
+			None => Some(&mut self.root),
+		}
+	}
+
+	pub fn layer(&self, path: LayerPath) -> Option<&Layer> {
+		self.comp(path.comp)?.layers.get(path.index)
+	}
+
+	pub fn layer_mut(&mut self, path: LayerPath) -> Option<&mut Layer> {
+		self.comp_mut(path.comp)?.layers.get_mut(path.index)
+	}
+
+	pub fn layers_recursive(&self) -> LayersRecursive<'_> {
+		LayersRecursive {
+			scene: self,
+			stack: vec![(None, 0)],
+		}
+	}
+
+	// Every layer once, the root's first and then each composition's in table order
+	pub fn layers(&self) -> impl Iterator<Item = (LayerPath, &Layer)> {
+		std::iter::once((None, &self.root))
+			.chain(
+				self.comps
+					.iter()
+					.enumerate()
+					.map(|(i, comp)| (Some(i), comp)),
+			)
+			.flat_map(|(comp, composition)| {
+				composition
+					.layers
+					.iter()
+					.enumerate()
+					.map(move |(index, layer)| (LayerPath::new(comp, index), layer))
+			})
+	}
+
+	pub fn find_layer(&self, id: LayerId) -> Option<LayerPath> {
+		self.layers()
+			.find(|(_, layer)| layer.id == id)
+			.map(|(path, _)| path)
+	}
+
+	pub fn find_layer_by_name(&self, name: &str) -> Option<LayerPath> {
+		self.layers()
+			.find(|(_, layer)| layer.name == name)
+			.map(|(path, _)| path)
+	}
+
+	// Every layer with a marker of that name, with the marker's frame
+	pub fn find_by_marker(&self, marker: &str) -> Vec<(LayerPath, f32)> {
+		self.layers()
+			.flat_map(|(path, layer)| {
+				layer
+					.markers
+					.iter()
+					.filter(move |(name, _)| name == marker)
+					.map(move |(_, frame)| (path, *frame))
+			})
+			.collect()
+	}
+
+	// Returns how many sources were renamed
+	pub fn rename_sources(&mut self, old: &str, new: &str) -> usize {
+		let mut renamed = 0;
+		for source in self.videos.iter_mut().flat_map(|video| &mut video.sources) {
+			if source.name == old {
+				source.name = new.to_string();
+				renamed += 1;
+			}
+		}
+		renamed
+	}
+
+	// Copies a composition along with every composition nested in it, so the copy can be edited
+	// on its own. Layers in the copy get new ids. Returns the index of the copy
+	pub fn duplicate_comp(&mut self, index: usize) -> Option<usize> {
+		if index >= self.comps.len() {
+			return None;
+		}
+		let mut copies = HashMap::new();
+		Some(self.duplicate_comp_inner(index, &mut copies))
+	}
+
+	fn duplicate_comp_inner(&mut self, index: usize, copies: &mut HashMap<usize, usize>) -> usize {
+		if let Some(&copy) = copies.get(&index) {
+			return copy;
+		}
+
+		// Reserved before recursing so compositions that use themselves end up using the copy
+		let copy = self.comps.len();
+		copies.insert(index, copy);
+		let mut comp = self.comps[index].clone();
+		self.comps.push(Composition::new());
+
+		for layer in &mut comp.layers {
+			if let Some(Item::Composition(inner)) = layer.item
+				&& inner < copy
+			{
+				layer.item = Some(Item::Composition(self.duplicate_comp_inner(inner, copies)));
+			}
+		}
+		renew_ids(&mut comp.layers);
+		self.comps[copy] = comp;
+		copy
+	}
+}
+
+impl Composition {
+	// Clamps the index like Vec::insert doesn't
+	pub fn insert_layer(&mut self, index: usize, layer: Layer) {
+		self.layers.insert(index.min(self.layers.len()), layer);
+	}
+
+	// Layers parented to the removed one are moved to its parent
+	pub fn remove_layer(&mut self, index: usize) -> Option<Layer> {
+		if index >= self.layers.len() {
+			return None;
+		}
+		let layer = self.layers.remove(index);
+		for other in &mut self.layers {
+			if other.parent == Some(layer.id) {
+				other.parent = layer.parent;
+			}
+		}
+		Some(layer)
+	}
+
+	// Parent links follow the layer since they go by id
+	pub fn move_layer(&mut self, index: usize, to: usize) -> bool {
+		if index >= self.layers.len() {
+			return false;
+		}
+		let layer = self.layers.remove(index);
+		self.insert_layer(to, layer);
+		true
+	}
+
+	// Copies a layer and every layer parented to it, directly or through others. The copies get
+	// new ids, link to each other instead of the originals and are put above the layer as one
+	// block. Returns the index of the copy of the layer
+	pub fn duplicate_layer(&mut self, index: usize) -> Option<usize> {
+		let root = self.layers.get(index)?.id;
+		let mut ids = HashSet::from([root]);
+		// Parents can come after their children, so go until nothing is added
+		loop {
+			let before = ids.len();
+			for layer in &self.layers {
+				if layer.parent.is_some_and(|parent| ids.contains(&parent)) {
+					ids.insert(layer.id);
+				}
+			}
+			if ids.len() == before {
+				break;
+			}
+		}
+
+		let mut copies = self
+			.layers
+			.iter()
+			.filter(|layer| ids.contains(&layer.id))
+			.cloned()
+			.collect::<Vec<_>>();
+		let root_offset = copies.iter().position(|layer| layer.id == root).unwrap();
+		renew_ids(&mut copies);
+
+		let at = self.layers[..index]
+			.iter()
+			.position(|layer| ids.contains(&layer.id))
+			.unwrap_or(index);
+		self.layers.splice(at..at, copies);
+		Some(at + root_offset)
+	}
+}
+
+// Gives the layers new ids and points parent links between them at the new ones
+fn renew_ids(layers: &mut [Layer]) {
+	let ids = layers
+		.iter_mut()
+		.map(|layer| {
+			let old = layer.id;
+			layer.id = LayerId::unique();
+			(old, layer.id)
+		})
+		.collect::<HashMap<_, _>>();
+	for layer in layers {
+		if let Some(parent) = layer.parent.and_then(|parent| ids.get(&parent)) {
+			layer.parent = Some(*parent);
+		}
+	}
+}
+
+impl Set {
+	pub fn rename_sources(&mut self, old: &str, new: &str) -> usize {
+		self.scenes
+			.iter_mut()
+			.map(|scene| scene.rename_sources(old, new))
+			.sum()
+	}
+}
@@ -0,0 +1,2337 @@
+use std::collections::*;
+use std::ffi::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+
+mod animation;
+mod evaluate;
+mod fcurve;
+mod graph;
+#[cfg(feature = "pyo3")]
+mod proxy;
+mod render;
+#[cfg(feature = "serde")]
+mod serialize;
+mod validate;
+pub use animation::{AnimationError, AnimationFormat, RenderRange};
+pub use evaluate::{EvaluatedLayer, Mat4};
+pub use fcurve::{FCurve, FCurveKey};
+pub use graph::{LayerPath, LayersRecursive};
+pub use render::Renderer;
+pub use validate::{Issue, SourceIssue};
+
+#[cfg(feature = "pyo3")]
+#[cfg_attr(feature = "pyo3", pymodule(name = "aet"))]
+pub(crate) mod aet_module {
+	#[pymodule_export]
+	use super::Audio;
+	#[pymodule_export]
+	use super::Camera;
+	#[pymodule_export]
+	use super::Composition;
+	#[pymodule_export]
+	use super::EvaluatedLayer;
+	#[pymodule_export]
+	use super::FCurve;
+	#[pymodule_export]
+	use super::FCurveKey;
+	#[pymodule_export]
+	use super::Issue;
+	#[pymodule_export]
+	use super::Item;
+	#[pymodule_export]
+	use super::Layer;
+	#[pymodule_export]
+	use super::LayerAudio;
+	#[pymodule_export]
+	use super::LayerPath;
+	#[pymodule_export]
+	use super::LayerVideo;
+	#[pymodule_export]
+	use super::LayerVideo3D;
+	#[pymodule_export]
+	use super::Scene;
+	#[pymodule_export]
+	use super::Set;
+	#[pymodule_export]
+	use super::SourceIssue;
+	#[pymodule_export]
+	use super::TransferMode;
+	#[pymodule_export]
+	use super::Video;
+	#[pymodule_export]
+	use super::VideoSource;
+
+	#[pymodule_init]
+	fn init(m: &pyo3::Bound<'_, pyo3::types::PyModule>) -> pyo3::PyResult<()> {
+		super::add_int_enum(m, "BlendMode", &super::BlendMode::KNOWN)?;
+		super::add_int_enum(m, "LayerQuality", &super::LayerQuality::KNOWN)
+	}
+}
+
+// Values outside the known range are kept as Unknown so modded files are written back unchanged
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BlendMode {
+	None,
+	Copy,
+	Behind,
+	#[default]
+	Normal,
+	Dissolve,
+	Add,
+	Multiply,
+	Screen,
+	Overlay,
+	SoftLight,
+	HardLight,
+	Darken,
+	Lighten,
+	ClassicDifference,
+	Hue,
+	Saturation,
+	Color,
+	Luminosity,
+	StencilAlpha,
+	StencilLuma,
+	SilhouetteAlpha,
+	SilhouetteLuma,
+	LuminescentPremul,
+	AlphaAdd,
+	ClassicColorDodge,
+	ClassicColorBurn,
+	Exclusion,
+	Difference,
+	ColorDodge,
+	ColorBurn,
+	LinearDodge,
+	LinearBurn,
+	LinearLight,
+	VividLight,
+	PinLight,
+	HardMix,
+	LighterColor,
+	DarkerColor,
+	Subtract,
+	Divide,
+	Unknown(u8),
+}
+
+impl BlendMode {
+	const KNOWN: [BlendMode; 40] = [
+		BlendMode::None,
+		BlendMode::Copy,
+		BlendMode::Behind,
+		BlendMode::Normal,
+		BlendMode::Dissolve,
+		BlendMode::Add,
+		BlendMode::Multiply,
+		BlendMode::Screen,
+		BlendMode::Overlay,
+		BlendMode::SoftLight,
+		BlendMode::HardLight,
+		BlendMode::Darken,
+		BlendMode::Lighten,
+		BlendMode::ClassicDifference,
+		BlendMode::Hue,
+		BlendMode::Saturation,
+		BlendMode::Color,
+		BlendMode::Luminosity,
+		BlendMode::StencilAlpha,
+		BlendMode::StencilLuma,
+		BlendMode::SilhouetteAlpha,
+		BlendMode::SilhouetteLuma,
+		BlendMode::LuminescentPremul,
+		BlendMode::AlphaAdd,
+		BlendMode::ClassicColorDodge,
+		BlendMode::ClassicColorBurn,
+		BlendMode::Exclusion,
+		BlendMode::Difference,
+		BlendMode::ColorDodge,
+		BlendMode::ColorBurn,
+		BlendMode::LinearDodge,
+		BlendMode::LinearBurn,
+		BlendMode::LinearLight,
+		BlendMode::VividLight,
+		BlendMode::PinLight,
+		BlendMode::HardMix,
+		BlendMode::LighterColor,
+		BlendMode::DarkerColor,
+		BlendMode::Subtract,
+		BlendMode::Divide,
+	];
+}
+
+impl TryFrom<u8> for BlendMode {
+	type Error = u8;
+
+	fn try_from(value: u8) -> Result<Self, u8> {
+		Self::KNOWN.get(value as usize).copied().ok_or(value)
+	}
+}
+
+impl From<BlendMode> for u8 {
+	fn from(mode: BlendMode) -> u8 {
+		match mode {
+			BlendMode::Unknown(value) => value,
+			mode => BlendMode::KNOWN
+				.iter()
+				.position(|known| *known == mode)
+				.unwrap() as u8,
+		}
+	}
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LayerQuality {
+	None,
+	Wireframe,
+	Draft,
+	#[default]
+	Best,
+	Unknown(u8),
+}
+
+impl LayerQuality {
+	const KNOWN: [LayerQuality; 4] = [
+		LayerQuality::None,
+		LayerQuality::Wireframe,
+		LayerQuality::Draft,
+		LayerQuality::Best,
+	];
+}
+
+impl TryFrom<u8> for LayerQuality {
+	type Error = u8;
+
+	fn try_from(value: u8) -> Result<Self, u8> {
+		Self::KNOWN.get(value as usize).copied().ok_or(value)
+	}
+}
+
+impl From<LayerQuality> for u8 {
+	fn from(quality: LayerQuality) -> u8 {
+		match quality {
+			LayerQuality::Unknown(value) => value,
+			quality => LayerQuality::KNOWN
+				.iter()
+				.position(|known| *known == quality)
+				.unwrap() as u8,
+		}
+	}
+}
+
+// Python sees both as ints, with IntEnum classes in the module for the known values
+#[cfg(feature = "pyo3")]
+fn add_int_enum<T: Copy + std::fmt::Debug + Into<u8>>(
+	module: &pyo3::Bound<'_, PyModule>,
+	name: &str,
+	values: &[T],
+) -> PyResult<()> {
+	let members = values
+		.iter()
+		.map(|value| {
+			let name = format!("{value:?}");
+			// None can't be used as an attribute name
+			let name = if name == "None" {
+				"None_".to_string()
+			} else {
+				name
+			};
+			(name, (*value).into())
+		})
+		.collect::<Vec<(String, u8)>>();
+
+	let kwargs = pyo3::types::PyDict::new(module.py());
+	kwargs.set_item("module", module.name()?)?;
+	let int_enum = module.py().import("enum")?.getattr("IntEnum")?;
+	module.add(name, int_enum.call((name, members), Some(&kwargs))?)
+}
+
+#[cfg(feature = "pyo3")]
+impl<'py> IntoPyObject<'py> for BlendMode {
+	type Target = pyo3::types::PyInt;
+	type Output = pyo3::Bound<'py, Self::Target>;
+	type Error = std::convert::Infallible;
+
+	fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+		u8::from(self).into_pyobject(py)
+	}
+}
+
+#[cfg(feature = "pyo3")]
+impl<'a, 'py> FromPyObject<'a, 'py> for BlendMode {
+	type Error = PyErr;
+
+	fn extract(obj: pyo3::Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+		let value = obj.extract::<u8>()?;
+		Ok(Self::try_from(value).unwrap_or(Self::Unknown(value)))
+	}
+}
+
+#[cfg(feature = "pyo3")]
+impl<'py> IntoPyObject<'py> for LayerQuality {
+	type Target = pyo3::types::PyInt;
+	type Output = pyo3::Bound<'py, Self::Target>;
+	type Error = std::convert::Infallible;
+
+	fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+		u8::from(self).into_pyobject(py)
+	}
+}
+
+#[cfg(feature = "pyo3")]
+impl<'a, 'py> FromPyObject<'a, 'py> for LayerQuality {
+	type Error = PyErr;
+
+	fn extract(obj: pyo3::Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+		let value = obj.extract::<u8>()?;
+		Ok(Self::try_from(value).unwrap_or(Self::Unknown(value)))
+	}
+}
+
+#[bitfields::bitfield(u16)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct LayerFlags {
+	pub video_active: bool,
+	pub audio_active: bool,
+	pub effects_active: bool,
+	pub motion_blur: bool,
+	pub frame_blending: bool,
+	pub locked: bool,
+	pub shy: bool,
+	pub collapse: bool,
+	pub auto_orient_rotation: bool,
+	pub adjustment_layer: bool,
+	pub time_remappingg: bool,
+	pub is_3d: bool,
+	pub look_at_camera: bool,
+	pub look_at_point_of_interest: bool,
+	pub solo: bool,
+	pub markers_locked: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "pyo3", pyclass(get_all, set_all))]
+pub struct TransferMode {
+	pub mode: BlendMode,
+	pub flag: u8,
+	pub matte: u8,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "pyo3", pyclass(get_all, set_all))]
+pub struct LayerVideo3D {
+	pub anchor_z: FCurve,
+	pub pos_z: FCurve,
+	pub dir_x: FCurve,
+	pub dir_y: FCurve,
+	pub dir_z: FCurve,
+	pub rot_x: FCurve,
+	pub rot_y: FCurve,
+	pub scale_z: FCurve,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pyclass(get_all, set_all))]
+pub struct LayerVideo {
+	pub transfer_mode: TransferMode,
+	pub anchor_x: FCurve,
+	pub anchor_y: FCurve,
+	pub pos_x: FCurve,
+	pub pos_y: FCurve,
+	pub rot_z: FCurve,
+	pub scale_x: FCurve,
+	pub scale_y: FCurve,
+	pub opacity: FCurve,
+	#[cfg_attr(feature = "pyo3", pyo3(name = "video_3d"))]
+	#[cfg_attr(feature = "serde", serde(rename = "video_3d"))]
+	pub _3d: Option<LayerVideo3D>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "pyo3", pyclass(get_all, set_all))]
+pub struct LayerAudio {
+	pub volume_l: FCurve,
+	pub volume_r: FCurve,
+	pub pan_l: FCurve,
+	pub pan_r: FCurve,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "pyo3", pyclass(get_all, set_all))]
+pub struct Camera {
+	pub eye_x: FCurve,
+	pub eye_y: FCurve,
+	pub eye_z: FCurve,
+	pub pos_x: FCurve,
+	pub pos_y: FCurve,
+	pub pos_z: FCurve,
+	pub dir_x: FCurve,
+	pub dir_y: FCurve,
+	pub dir_z: FCurve,
+	pub rot_x: FCurve,
+	pub rot_y: FCurve,
+	pub rot_z: FCurve,
+	pub zoom: FCurve,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "pyo3", pyclass(get_all, set_all))]
+pub struct VideoSource {
+	pub name: String,
+	pub id: u32,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "pyo3", pyclass(get_all, set_all))]
+pub struct Video {
+	pub color: [u8; 3],
+	pub width: u16,
+	pub height: u16,
+	pub fpf: f32,
+	pub sources: Vec<VideoSource>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "pyo3", pyclass(get_all, set_all))]
+pub struct Audio {
+	pub sound_index: u32,
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl TransferMode {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+}
+
+impl Default for LayerVideo3D {
+	fn default() -> Self {
+		Self {
+			anchor_z: FCurve::new(),
+			pos_z: FCurve::new(),
+			dir_x: FCurve::new(),
+			dir_y: FCurve::new(),
+			dir_z: FCurve::new(),
+			rot_x: FCurve::new(),
+			rot_y: FCurve::new(),
+			scale_z: FCurve::constant(1.0),
+		}
+	}
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl LayerVideo3D {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __getattribute__(
+		slf: &pyo3::Bound<'_, Self>,
+		name: &pyo3::Bound<'_, pyo3::types::PyString>,
+	) -> PyResult<Py<PyAny>> {
+		proxy::getattribute(slf, name)
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __setattr__(
+		slf: &pyo3::Bound<'_, Self>,
+		name: &pyo3::Bound<'_, pyo3::types::PyString>,
+		value: &pyo3::Bound<'_, PyAny>,
+	) -> PyResult<()> {
+		proxy::setattr(slf.as_any(), name, value)
+	}
+}
+
+impl Default for LayerVideo {
+	fn default() -> Self {
+		Self {
+			transfer_mode: TransferMode::default(),
+			anchor_x: FCurve::new(),
+			anchor_y: FCurve::new(),
+			pos_x: FCurve::new(),
+			pos_y: FCurve::new(),
+			rot_z: FCurve::new(),
+			scale_x: FCurve::constant(1.0),
+			scale_y: FCurve::constant(1.0),
+			opacity: FCurve::constant(1.0),
+			_3d: None,
+		}
+	}
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl LayerVideo {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __getattribute__(
+		slf: &pyo3::Bound<'_, Self>,
+		name: &pyo3::Bound<'_, pyo3::types::PyString>,
+	) -> PyResult<Py<PyAny>> {
+		proxy::getattribute(slf, name)
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __setattr__(
+		slf: &pyo3::Bound<'_, Self>,
+		name: &pyo3::Bound<'_, pyo3::types::PyString>,
+		value: &pyo3::Bound<'_, PyAny>,
+	) -> PyResult<()> {
+		proxy::setattr(slf.as_any(), name, value)
+	}
+}
+
+impl Default for LayerAudio {
+	fn default() -> Self {
+		Self {
+			volume_l: FCurve::constant(1.0),
+			volume_r: FCurve::constant(1.0),
+			pan_l: FCurve::new(),
+			pan_r: FCurve::new(),
+		}
+	}
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl LayerAudio {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __getattribute__(
+		slf: &pyo3::Bound<'_, Self>,
+		name: &pyo3::Bound<'_, pyo3::types::PyString>,
+	) -> PyResult<Py<PyAny>> {
+		proxy::getattribute(slf, name)
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __setattr__(
+		slf: &pyo3::Bound<'_, Self>,
+		name: &pyo3::Bound<'_, pyo3::types::PyString>,
+		value: &pyo3::Bound<'_, PyAny>,
+	) -> PyResult<()> {
+		proxy::setattr(slf.as_any(), name, value)
+	}
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl Camera {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __getattribute__(
+		slf: &pyo3::Bound<'_, Self>,
+		name: &pyo3::Bound<'_, pyo3::types::PyString>,
+	) -> PyResult<Py<PyAny>> {
+		proxy::getattribute(slf, name)
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __setattr__(
+		slf: &pyo3::Bound<'_, Self>,
+		name: &pyo3::Bound<'_, pyo3::types::PyString>,
+		value: &pyo3::Bound<'_, PyAny>,
+	) -> PyResult<()> {
+		proxy::setattr(slf.as_any(), name, value)
+	}
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl VideoSource {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new(name: String, id: u32) -> Self {
+		Self { name, id }
+	}
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl Video {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __getattribute__(
+		slf: &pyo3::Bound<'_, Self>,
+		name: &pyo3::Bound<'_, pyo3::types::PyString>,
+	) -> PyResult<Py<PyAny>> {
+		proxy::getattribute(slf, name)
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __setattr__(
+		slf: &pyo3::Bound<'_, Self>,
+		name: &pyo3::Bound<'_, pyo3::types::PyString>,
+		value: &pyo3::Bound<'_, PyAny>,
+	) -> PyResult<()> {
+		proxy::setattr(slf.as_any(), name, value)
+	}
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl Audio {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new(sound_index: u32) -> Self {
+		Self { sound_index }
+	}
+}
+
+// Items live in the scene and layers refer to them by index, so an item used by several layers is
+// only stored once, same as in the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "pyo3", pyclass(eq))]
+pub enum Item {
+	// Index into Scene::videos
+	Video(usize),
+	// Index into Scene::audios
+	Audio(usize),
+	// Index into Scene::comps
+	Composition(usize),
+}
+
+// Identifies a layer for parent links. Ids are unique within the process and are kept when a
+// layer is cloned, so parent links inside a cloned composition still point at the right layers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LayerId(u64);
+
+static NEXT_LAYER_ID: AtomicU64 = AtomicU64::new(1);
+
+impl LayerId {
+	pub fn unique() -> Self {
+		Self(NEXT_LAYER_ID.fetch_add(1, Ordering::Relaxed))
+	}
+
+	pub fn get(self) -> u64 {
+		self.0
+	}
+}
+
+#[cfg(feature = "pyo3")]
+impl<'py> IntoPyObject<'py> for LayerId {
+	type Target = pyo3::types::PyInt;
+	type Output = pyo3::Bound<'py, Self::Target>;
+	type Error = std::convert::Infallible;
+
+	fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+		self.0.into_pyobject(py)
+	}
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pyclass)]
+pub struct Layer {
+	#[cfg_attr(feature = "serde", serde(skip, default = "LayerId::unique"))]
+	pub id: LayerId,
+	#[cfg_attr(feature = "pyo3", pyo3(get, set))]
+	pub name: String,
+	#[cfg_attr(feature = "pyo3", pyo3(get, set))]
+	pub start_time: f32,
+	#[cfg_attr(feature = "pyo3", pyo3(get, set))]
+	pub end_time: f32,
+	#[cfg_attr(feature = "pyo3", pyo3(get, set))]
+	pub offset_time: f32,
+	#[cfg_attr(feature = "pyo3", pyo3(get, set))]
+	pub time_scale: f32,
+	pub flags: LayerFlags,
+	#[cfg_attr(feature = "pyo3", pyo3(get, set))]
+	pub quality: LayerQuality,
+	#[cfg_attr(feature = "pyo3", pyo3(get, set))]
+	pub item: Option<Item>,
+	#[cfg_attr(feature = "pyo3", pyo3(get, set))]
+	pub markers: Vec<(String, f32)>,
+	#[cfg_attr(feature = "pyo3", pyo3(get, set))]
+	pub video: Option<LayerVideo>,
+	#[cfg_attr(feature = "pyo3", pyo3(get, set))]
+	pub audio: Option<LayerAudio>,
+
+	// NOTE: this is NOT the layer whos item is a comp that has this as a child, this is some other thing
+	// Sega pls
+	// Written by Composition as the parent's index since ids don't survive serializing
+	#[cfg_attr(feature = "serde", serde(skip))]
+	pub parent: Option<LayerId>,
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl Layer {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new(name: &str) -> Self {
+		let mut flags = LayerFlags::from_bits(0);
+		flags.set_video_active(true);
+		Self {
+			id: LayerId::unique(),
+			name: name.to_string(),
+			start_time: 0.0,
+			end_time: 0.0,
+			offset_time: 0.0,
+			time_scale: 1.0,
+			flags,
+			quality: LayerQuality::default(),
+			item: None,
+			markers: Vec::new(),
+			video: None,
+			audio: None,
+			parent: None,
+		}
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __getattribute__(
+		slf: &pyo3::Bound<'_, Self>,
+		name: &pyo3::Bound<'_, pyo3::types::PyString>,
+	) -> PyResult<Py<PyAny>> {
+		proxy::getattribute(slf, name)
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __setattr__(
+		slf: &pyo3::Bound<'_, Self>,
+		name: &pyo3::Bound<'_, pyo3::types::PyString>,
+		value: &pyo3::Bound<'_, PyAny>,
+	) -> PyResult<()> {
+		proxy::setattr(slf.as_any(), name, value)
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", getter(id))]
+	fn py_id(&self) -> u64 {
+		self.id.get()
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", getter(flags))]
+	fn py_flags(&self) -> u16 {
+		self.flags.into_bits()
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", setter(flags))]
+	fn set_py_flags(&mut self, flags: u16) {
+		self.flags = LayerFlags::from_bits(flags);
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", getter(parent))]
+	fn py_parent(&self) -> Option<u64> {
+		self.parent.map(LayerId::get)
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", setter(parent))]
+	fn set_py_parent(&mut self, parent: Option<u64>) {
+		self.parent = parent.map(LayerId);
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __repr__(&self) -> String {
+		format!("Layer({:?})", self.name)
+	}
+}
+
+// Ids differ every time a set is loaded, so they're left out here and parent links are compared
+// by Composition instead
+impl PartialEq for Layer {
+	fn eq(&self, other: &Self) -> bool {
+		self.name == other.name
+			&& self.start_time == other.start_time
+			&& self.end_time == other.end_time
+			&& self.offset_time == other.offset_time
+			&& self.time_scale == other.time_scale
+			&& self.flags == other.flags
+			&& self.quality == other.quality
+			&& self.item == other.item
+			&& self.markers == other.markers
+			&& self.video == other.video
+			&& self.audio == other.audio
+	}
+}
+
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "pyo3", pyclass(get_all, set_all))]
+pub struct Composition {
+	pub layers: Vec<Layer>,
+}
+
+#[cfg(feature = "pyo3")]
+fn py_index(len: usize, index: isize) -> PyResult<usize> {
+	let index = if index < 0 {
+		index + len as isize
+	} else {
+		index
+	};
+	if index < 0 || index as usize >= len {
+		Err(pyo3::exceptions::PyIndexError::new_err(
+			"layer index out of range",
+		))
+	} else {
+		Ok(index as usize)
+	}
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl Composition {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __getattribute__(
+		slf: &pyo3::Bound<'_, Self>,
+		name: &pyo3::Bound<'_, pyo3::types::PyString>,
+	) -> PyResult<Py<PyAny>> {
+		proxy::getattribute(slf, name)
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __setattr__(
+		slf: &pyo3::Bound<'_, Self>,
+		name: &pyo3::Bound<'_, pyo3::types::PyString>,
+		value: &pyo3::Bound<'_, PyAny>,
+	) -> PyResult<()> {
+		proxy::setattr(slf.as_any(), name, value)
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __len__(&self) -> usize {
+		self.layers.len()
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __getitem__(slf: &pyo3::Bound<'_, Self>, index: isize) -> PyResult<proxy::Ref> {
+		let index = py_index(slf.borrow().layers.len(), index)?;
+		Ok(proxy::Ref::new(slf, vec![proxy::Step::Index(index)]))
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __setitem__(&mut self, index: isize, layer: &pyo3::Bound<'_, PyAny>) -> PyResult<()> {
+		let index = py_index(self.layers.len(), index)?;
+		self.layers[index] = proxy::extract(layer)?;
+		Ok(())
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __delitem__(&mut self, index: isize) -> PyResult<()> {
+		let index = py_index(self.layers.len(), index)?;
+		self.remove_layer(index);
+		Ok(())
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn append(&mut self, layer: &pyo3::Bound<'_, PyAny>) -> PyResult<()> {
+		self.layers.push(proxy::extract(layer)?);
+		Ok(())
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn insert(&mut self, index: isize, layer: &pyo3::Bound<'_, PyAny>) -> PyResult<()> {
+		let len = self.layers.len() as isize;
+		let index = if index < 0 { index + len } else { index };
+		self.insert_layer(index.max(0) as usize, proxy::extract(layer)?);
+		Ok(())
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "remove_layer"))]
+	fn py_remove_layer(&mut self, index: isize) -> PyResult<Layer> {
+		let index = py_index(self.layers.len(), index)?;
+		Ok(self.remove_layer(index).unwrap())
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "move_layer"))]
+	fn py_move_layer(&mut self, index: isize, to: usize) -> PyResult<()> {
+		let index = py_index(self.layers.len(), index)?;
+		self.move_layer(index, to);
+		Ok(())
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "duplicate_layer"))]
+	fn py_duplicate_layer(&mut self, index: isize) -> PyResult<usize> {
+		let index = py_index(self.layers.len(), index)?;
+		Ok(self.duplicate_layer(index).unwrap())
+	}
+}
+
+impl PartialEq for Composition {
+	fn eq(&self, other: &Self) -> bool {
+		// Parents are compared by their position since ids change between loads
+		let position = |comp: &Composition, parent: Option<LayerId>| {
+			parent.map(|parent| comp.layers.iter().position(|layer| layer.id == parent))
+		};
+		self.layers == other.layers
+			&& self
+				.layers
+				.iter()
+				.zip(other.layers.iter())
+				.all(|(own, other_layer)| {
+					position(self, own.parent) == position(other, other_layer.parent)
+				})
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "pyo3", pyclass(get_all, set_all))]
+pub struct Scene {
+	pub name: String,
+	pub start_time: f32,
+	pub end_time: f32,
+	pub fps: f32,
+	pub color: [u8; 3],
+	pub width: u32,
+	pub height: u32,
+	pub camera: Option<Camera>,
+	pub root: Composition,
+	// Compositions used as layer items, the root is not part of these
+	pub comps: Vec<Composition>,
+	pub videos: Vec<Video>,
+	pub audios: Vec<Audio>,
+}
+
+impl Default for Scene {
+	fn default() -> Self {
+		Self {
+			name: String::new(),
+			start_time: 0.0,
+			end_time: 0.0,
+			fps: 60.0,
+			color: [0; 3],
+			width: 0,
+			height: 0,
+			camera: None,
+			root: Composition::new(),
+			comps: Vec::new(),
+			videos: Vec::new(),
+			audios: Vec::new(),
+		}
+	}
+}
+
+impl Composition {
+	fn convert_resolution(&mut self, scale_x: f32, scale_y: f32) {
+		for layer in &mut self.layers {
+			if let Some(video) = &mut layer.video {
+				video.anchor_x.scale_values(scale_x);
+				video.anchor_y.scale_values(scale_y);
+				video.pos_x.scale_values(scale_x);
+				video.pos_y.scale_values(scale_y);
+			}
+		}
+	}
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl Scene {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __getattribute__(
+		slf: &pyo3::Bound<'_, Self>,
+		name: &pyo3::Bound<'_, pyo3::types::PyString>,
+	) -> PyResult<Py<PyAny>> {
+		proxy::getattribute(slf, name)
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __setattr__(
+		slf: &pyo3::Bound<'_, Self>,
+		name: &pyo3::Bound<'_, pyo3::types::PyString>,
+		value: &pyo3::Bound<'_, PyAny>,
+	) -> PyResult<()> {
+		proxy::setattr(slf.as_any(), name, value)
+	}
+
+	// Every video layer shown at the given frame in draw order, back to front
+	// Layer curves use the time of the composition the layer is in, items use the layer's own
+	// time, (frame - start_time) * time_scale + offset_time
+	pub fn evaluate(&self, frame: f32) -> Vec<EvaluatedLayer> {
+		self.evaluate_root(frame)
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "layers_recursive"))]
+	fn py_layers_recursive(slf: &pyo3::Bound<'_, Self>) -> Vec<(usize, LayerPath, proxy::Ref)> {
+		slf.borrow()
+			.layers_recursive()
+			.map(|(depth, path, _)| (depth, path, proxy::Ref::new(slf, path.steps())))
+			.collect()
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "layer"))]
+	fn py_layer(slf: &pyo3::Bound<'_, Self>, path: LayerPath) -> Option<proxy::Ref> {
+		slf.borrow()
+			.layer(path)
+			.map(|_| proxy::Ref::new(slf, path.steps()))
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn set_layer(&mut self, path: LayerPath, layer: &pyo3::Bound<'_, PyAny>) -> PyResult<()> {
+		let slot = self
+			.layer_mut(path)
+			.ok_or_else(|| pyo3::exceptions::PyIndexError::new_err("layer path out of range"))?;
+		*slot = proxy::extract(layer)?;
+		Ok(())
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "find_layer"))]
+	fn py_find_layer(&self, id: u64) -> Option<LayerPath> {
+		self.find_layer(LayerId(id))
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "find_layer_by_name"))]
+	fn py_find_layer_by_name(&self, name: &str) -> Option<LayerPath> {
+		self.find_layer_by_name(name)
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "find_by_marker"))]
+	fn py_find_by_marker(&self, marker: &str) -> Vec<(LayerPath, f32)> {
+		self.find_by_marker(marker)
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "rename_sources"))]
+	fn py_rename_sources(&mut self, old: &str, new: &str) -> usize {
+		self.rename_sources(old, new)
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "duplicate_comp"))]
+	fn py_duplicate_comp(&mut self, index: usize) -> Option<usize> {
+		self.duplicate_comp(index)
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "render", signature = (frame, sprites=None, database=None, set_name=None)))]
+	fn py_render(
+		&self,
+		frame: f32,
+		sprites: Option<&crate::spr::Set>,
+		database: Option<&crate::database::sprite::Database>,
+		set_name: Option<&str>,
+	) -> crate::txp::PyTexture {
+		let mut renderer = Renderer::new(database);
+		if let Some(sprites) = sprites {
+			renderer.load_sprites(sprites, set_name);
+		}
+		let image = renderer.render(self, frame);
+		crate::txp::PyTexture {
+			width: image.width() as i32,
+			height: image.height() as i32,
+			rgba: image.into_raw(),
+		}
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "render_range", signature = (start, end, step=1.0, sprites=None, database=None, set_name=None)))]
+	fn py_render_range(
+		&self,
+		start: f32,
+		end: f32,
+		step: f32,
+		sprites: Option<&crate::spr::Set>,
+		database: Option<&crate::database::sprite::Database>,
+		set_name: Option<&str>,
+	) -> Vec<crate::txp::PyTexture> {
+		let mut renderer = Renderer::new(database);
+		if let Some(sprites) = sprites {
+			renderer.load_sprites(sprites, set_name);
+		}
+		self.render_range(&renderer, start, end, step)
+			.map(|image| crate::txp::PyTexture {
+				width: image.width() as i32,
+				height: image.height() as i32,
+				rgba: image.into_raw(),
+			})
+			.collect()
+	}
+
+	// format is one of "apng", "gif" or "webp"
+	#[cfg(feature = "pyo3")]
+	#[allow(clippy::too_many_arguments)]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "encode_range", signature = (format, start, end, step=1.0, sprites=None, database=None, set_name=None)))]
+	fn py_encode_range(
+		&self,
+		format: &str,
+		start: f32,
+		end: f32,
+		step: f32,
+		sprites: Option<&crate::spr::Set>,
+		database: Option<&crate::database::sprite::Database>,
+		set_name: Option<&str>,
+	) -> PyResult<Vec<u8>> {
+		let format = match format.to_ascii_lowercase().as_str() {
+			"apng" => AnimationFormat::Apng,
+			"gif" => AnimationFormat::Gif,
+			"webp" => AnimationFormat::WebP,
+			_ => {
+				return Err(pyo3::exceptions::PyValueError::new_err(format!(
+					"Unknown animation format {format:?}"
+				)));
+			}
+		};
+		let mut renderer = Renderer::new(database);
+		if let Some(sprites) = sprites {
+			renderer.load_sprites(sprites, set_name);
+		}
+		let mut out = Vec::new();
+		self.render_range(&renderer, start, end, step)
+			.encode(format, &mut out)?;
+		Ok(out)
+	}
+
+	// Companion to spr::Set::convert_resolution, rescales all 2D positions and video sizes
+	pub fn convert_resolution(
+		&mut self,
+		from_mode: crate::spr::ResolutionMode,
+		to_mode: crate::spr::ResolutionMode,
+	) {
+		let (from_width, from_height) = from_mode.resolution();
+		let (to_width, to_height) = to_mode.resolution();
+		let scale_x = to_width as f32 / from_width as f32;
+		let scale_y = to_height as f32 / from_height as f32;
+
+		self.width = (self.width as f32 * scale_x).round() as u32;
+		self.height = (self.height as f32 * scale_y).round() as u32;
+
+		if let Some(camera) = &mut self.camera {
+			camera.eye_x.scale_values(scale_x);
+			camera.eye_y.scale_values(scale_y);
+			camera.pos_x.scale_values(scale_x);
+			camera.pos_y.scale_values(scale_y);
+		}
+
+		self.root.convert_resolution(scale_x, scale_y);
+		for comp in &mut self.comps {
+			comp.convert_resolution(scale_x, scale_y);
+		}
+
+		for video in &mut self.videos {
+			video.width = (video.width as f32 * scale_x).round() as u16;
+			video.height = (video.height as f32 * scale_y).round() as u16;
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+	// Layer items are none, video, audio or composition, anything else can't be pointed at
+	UnknownItemType { layer: String, item_type: u8 },
+	// The item isn't in the scene's table for its type, the reader leaves those null
+	DanglingItem { layer: String, item_type: u8 },
+}
+
+impl std::fmt::Display for DecodeError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::UnknownItemType { layer, item_type } => {
+				write!(f, "layer {layer:?} has unknown item type {item_type}")
+			}
+			Self::DanglingItem { layer, item_type } => {
+				write!(
+					f,
+					"layer {layer:?} has an item of type {item_type} that isn't in the scene"
+				)
+			}
+		}
+	}
+}
+
+impl std::error::Error for DecodeError {}
+
+#[cfg(feature = "pyo3")]
+impl From<DecodeError> for PyErr {
+	fn from(err: DecodeError) -> Self {
+		pyo3::exceptions::PyValueError::new_err(err.to_string())
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodeError {
+	// The item isn't in the scene's table for its type, from_buf would refuse the written set
+	DanglingItem { layer: String, item_type: u8 },
+	// No layer in the scene has the parent id
+	DanglingParent { layer: String },
+}
+
+impl std::fmt::Display for EncodeError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::DanglingItem { layer, item_type } => {
+				write!(
+					f,
+					"layer {layer:?} has an item of type {item_type} that isn't in the scene"
+				)
+			}
+			Self::DanglingParent { layer } => {
+				write!(f, "layer {layer:?} has a parent that isn't in the scene")
+			}
+		}
+	}
+}
+
+impl std::error::Error for EncodeError {}
+
+#[cfg(feature = "pyo3")]
+impl From<EncodeError> for PyErr {
+	fn from(err: EncodeError) -> Self {
+		pyo3::exceptions::PyValueError::new_err(err.to_string())
+	}
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "pyo3", pyclass(get_all, set_all))]
+pub struct Set {
+	pub modern: bool,
+	pub big_endian: bool,
+	pub is_x: bool,
+	pub scenes: Vec<Scene>,
+}
+
+// Sets get parsed on worker threads, keep them shareable
+const _: fn() = || {
+	fn assert_send_sync<T: Send + Sync>() {}
+	assert_send_sync::<Set>();
+};
+
+#[derive(Debug, Default)]
+struct SetCounts {
+	audio_count: usize,
+	camera_count: usize,
+	comp_count: usize,
+	fcurve_key_count: usize,
+	layer_audio_count: usize,
+	layer_count: usize,
+	layer_video_3d_count: usize,
+	layer_video_count: usize,
+	marker_count: usize,
+	name_count: usize,
+	scene_count: usize,
+	video_count: usize,
+	video_source_count: usize,
+}
+
+fn count_fcurve(fcurve: &FCurve, count: &mut SetCounts) {
+	if fcurve.keys.is_empty() || (fcurve.keys.len() == 1 && fcurve.keys[0].value == 0.0) {
+	} else if fcurve.keys.len() == 1 {
+		count.fcurve_key_count += 1;
+	} else {
+		count.fcurve_key_count += fcurve.keys.len() * 3;
+	}
+}
+
+fn count_comp(comp: &Composition, count: &mut SetCounts) {
+	count.comp_count += 1;
+	for layer in &comp.layers {
+		count.layer_count += 1;
+		count.name_count += 1;
+		count.marker_count += layer.markers.len();
+		count.name_count += layer.markers.len();
+		if let Some(layer_video) = &layer.video {
+			count.layer_video_count += 1;
+			count_fcurve(&layer_video.anchor_x, count);
+			count_fcurve(&layer_video.anchor_y, count);
+			count_fcurve(&layer_video.pos_x, count);
+			count_fcurve(&layer_video.pos_y, count);
+			count_fcurve(&layer_video.rot_z, count);
+			count_fcurve(&layer_video.scale_x, count);
+			count_fcurve(&layer_video.scale_y, count);
+			count_fcurve(&layer_video.opacity, count);
+
+			if let Some(_3d) = &layer_video._3d {
+				count.layer_video_3d_count += 1;
+				count_fcurve(&_3d.anchor_z, count);
+				count_fcurve(&_3d.pos_z, count);
+				count_fcurve(&_3d.dir_x, count);
+				count_fcurve(&_3d.dir_y, count);
+				count_fcurve(&_3d.dir_z, count);
+				count_fcurve(&_3d.rot_x, count);
+				count_fcurve(&_3d.rot_y, count);
+				count_fcurve(&_3d.scale_z, count);
+			}
+		}
+
+		if let Some(layer_audio) = &layer.audio {
+			count.layer_audio_count += 1;
+			count_fcurve(&layer_audio.volume_l, count);
+			count_fcurve(&layer_audio.volume_r, count);
+			count_fcurve(&layer_audio.pan_l, count);
+			count_fcurve(&layer_audio.pan_r, count);
+		}
+	}
+}
+
+struct SetMemory {
+	audios: Vec<aet_audio>,
+	cameras: Vec<aet_camera>,
+	comps: Vec<aet_comp>,
+	fcurves: Vec<aet_fcurve>,
+	fcurve_keys: Vec<f32>,
+	layer_audios: Vec<aet_layer_audio>,
+	layers: Vec<aet_layer>,
+	layer_video_3ds: Vec<aet_layer_video_3d>,
+	layer_videos: Vec<aet_layer_video>,
+	markers: Vec<aet_marker>,
+	names: Vec<CString>,
+	scenes: Vec<Box<aet_scene>>,
+	videos: Vec<aet_video>,
+	video_sources: Vec<aet_video_src>,
+}
+
+fn alloc_fcurve(in_fcurve: &FCurve, memory: &mut SetMemory) -> aet_fcurve {
+	let mut out = aet_fcurve {
+		keys_count: in_fcurve.keys.len() as u32,
+		keys: std::ptr::null(),
+	};
+
+	if in_fcurve.keys.is_empty() || (in_fcurve.keys.len() == 1 && in_fcurve.keys[0].value == 0.0) {
+		out.keys_count = 0;
+		return out;
+	}
+
+	for aet_fcurve in &memory.fcurves {
+		if fcurve_eq(in_fcurve, aet_fcurve) {
+			out.keys = aet_fcurve.keys;
+			return out;
+		}
+	}
+
+	if in_fcurve.keys.len() == 1 {
+		out.keys = memory.fcurve_keys.push_mut(in_fcurve.keys[0].value) as *const _;
+		memory.fcurves.push(aet_fcurve {
+			keys_count: in_fcurve.keys.len() as u32,
+			keys: out.keys,
+		});
+	} else {
+		out.keys = memory.fcurve_keys.push_mut(in_fcurve.keys[0].frame) as *const _;
+		for key in in_fcurve.keys.iter().skip(1) {
+			memory.fcurve_keys.push(key.frame);
+		}
+		for key in in_fcurve.keys.iter() {
+			memory.fcurve_keys.push(key.value);
+			memory.fcurve_keys.push(key.tangent);
+		}
+		memory.fcurves.push(aet_fcurve {
+			keys_count: in_fcurve.keys.len() as u32,
+			keys: out.keys,
+		});
+	}
+
+	out
+}
+
+fn fcurve_eq(own_fcurve: &FCurve, aet_fcurve: &aet_fcurve) -> bool {
+	if own_fcurve.keys.len() as u32 != aet_fcurve.keys_count {
+		return false;
+	}
+
+	if own_fcurve.keys.is_empty() {
+		true
+	} else if own_fcurve.keys.len() == 1 {
+		if unsafe { aet_fcurve.keys.read() } == own_fcurve.keys[0].value {
+			true
+		} else {
+			false
+		}
+	} else {
+		for i in 0..own_fcurve.keys.len() {
+			if unsafe { aet_fcurve.keys.offset(i as isize).read() } != own_fcurve.keys[i].frame
+				|| unsafe {
+					aet_fcurve
+						.keys
+						.offset(own_fcurve.keys.len() as isize + i as isize * 2)
+						.read()
+				} != own_fcurve.keys[i].value
+				|| unsafe {
+					aet_fcurve
+						.keys
+						.offset(own_fcurve.keys.len() as isize + i as isize * 2 + 1)
+						.read()
+				} != own_fcurve.keys[i].tangent
+			{
+				return false;
+			}
+		}
+
+		true
+	}
+}
+
+fn alloc_video(video: &Video, memory: &mut SetMemory) -> aet_video {
+	let sources = memory
+		.video_sources
+		.last_mut()
+		.map(|ptr| ptr as *const aet_video_src)
+		.map(|ptr| unsafe { ptr.offset(1) })
+		.unwrap_or(memory.video_sources.as_ptr());
+
+	for source in &video.sources {
+		let name = CString::new(source.name.clone()).unwrap_or_default();
+		let name = memory.names.push_mut(name).as_ptr();
+		let source = aet_video_src {
+			sprite_name: name,
+			sprite_index: source.id,
+		};
+		memory.video_sources.push(source);
+	}
+
+	aet_video {
+		color: video.color,
+		width: video.width,
+		height: video.height,
+		fpf: video.fpf,
+		sources_count: video.sources.len() as u32,
+		sources,
+	}
+}
+
+// Compositions in the order they're written, layers point into the comp table so the root goes
+// last like in the file
+fn scene_comps(scene: &Scene) -> impl Iterator<Item = &Composition> {
+	scene.comps.iter().chain(std::iter::once(&scene.root))
+}
+
+// Parents are looked up in the layer's own composition first, a cloned composition elsewhere in
+// the set has the same layer ids. Returns the composition and layer index in scene_comps order
+fn find_parent(scene: &Scene, comp: usize, parent: LayerId) -> Option<(usize, usize)> {
+	let own = scene_comps(scene)
+		.nth(comp)
+		.and_then(|own| own.layers.iter().position(|layer| layer.id == parent))
+		.map(|layer| (comp, layer));
+	own.or_else(|| {
+		scene_comps(scene).enumerate().find_map(|(i, comp)| {
+			let layer = comp.layers.iter().position(|layer| layer.id == parent)?;
+			Some((i, layer))
+		})
+	})
+}
+
+// Everything a layer points at has to be in its scene, the packer can't write anything else
+fn check_links(scene: &Scene) -> Result<(), EncodeError> {
+	for (i, comp) in scene_comps(scene).enumerate() {
+		for layer in &comp.layers {
+			let dangling = match layer.item {
+				Some(Item::Video(index)) if index >= scene.videos.len() => Some(1),
+				Some(Item::Audio(index)) if index >= scene.audios.len() => Some(2),
+				Some(Item::Composition(index)) if index >= scene.comps.len() => Some(3),
+				_ => None,
+			};
+			if let Some(item_type) = dangling {
+				return Err(EncodeError::DanglingItem {
+					layer: layer.name.clone(),
+					item_type,
+				});
+			}
+
+			if let Some(parent) = layer.parent
+				&& find_parent(scene, i, parent).is_none()
+			{
+				return Err(EncodeError::DanglingParent {
+					layer: layer.name.clone(),
+				});
+			}
+		}
+	}
+
+	Ok(())
+}
+
+// Items point into the scene tables, check_links already rejected indices past their end
+fn alloc_item(item: Option<Item>, out_scene: &aet_scene) -> (u8, *const c_void) {
+	match item {
+		Some(Item::Video(index)) if index < out_scene.video_count as usize => {
+			(1, unsafe { out_scene.video.add(index) } as *const c_void)
+		}
+		Some(Item::Audio(index)) if index < out_scene.audio_count as usize => {
+			(2, unsafe { out_scene.audio.add(index) } as *const c_void)
+		}
+		Some(Item::Composition(index)) if index < out_scene.comp_count as usize - 1 => {
+			(3, unsafe { out_scene.comp.add(index) } as *const c_void)
+		}
+		_ => (0, std::ptr::null()),
+	}
+}
+
+fn alloc_comp(
+	in_comp: &Composition,
+	out_comp: &mut aet_comp,
+	out_scene: &aet_scene,
+	memory: &mut SetMemory,
+) {
+	out_comp.layers_count = in_comp.layers.len() as u32;
+	out_comp.layers = memory
+		.layers
+		.last_mut()
+		.map(|ptr| ptr as *const aet_layer)
+		.map(|ptr| unsafe { ptr.offset(1) })
+		.unwrap_or(memory.layers.as_ptr());
+
+	for layer in &in_comp.layers {
+		let name = CString::new(layer.name.clone()).unwrap_or_default();
+		let name = memory.names.push_mut(name).as_ptr();
+
+		let markers = if layer.markers.is_empty() {
+			std::ptr::null()
+		} else {
+			let markers = memory
+				.markers
+				.last_mut()
+				.map(|ptr| ptr as *const aet_marker)
+				.map(|ptr| unsafe { ptr.offset(1) })
+				.unwrap_or(memory.markers.as_ptr());
+
+			for (name, time) in &layer.markers {
+				let name = CString::new(name.clone()).unwrap_or_default();
+				let name = memory.names.push_mut(name).as_ptr();
+				memory.markers.push(aet_marker { name, time: *time })
+			}
+
+			markers
+		};
+
+		let video = if let Some(video) = &layer.video {
+			let _3d = if let Some(_3d) = &video._3d {
+				let _3d = aet_layer_video_3d {
+					anchor_z: alloc_fcurve(&_3d.anchor_z, memory),
+					pos_z: alloc_fcurve(&_3d.pos_z, memory),
+					dir_x: alloc_fcurve(&_3d.dir_x, memory),
+					dir_y: alloc_fcurve(&_3d.dir_y, memory),
+					dir_z: alloc_fcurve(&_3d.dir_z, memory),
+					rot_x: alloc_fcurve(&_3d.rot_x, memory),
+					rot_y: alloc_fcurve(&_3d.rot_y, memory),
+					scale_z: alloc_fcurve(&_3d.scale_z, memory),
+				};
+				memory.layer_video_3ds.push_mut(_3d) as *const aet_layer_video_3d
+			} else {
+				std::ptr::null()
+			};
+
+			let video = aet_layer_video {
+				transfer_mode: aet_transfer_mode {
+					mode: video.transfer_mode.mode.into(),
+					flag: video.transfer_mode.flag,
+					matte: video.transfer_mode.matte,
+				},
+				anchor_x: alloc_fcurve(&video.anchor_x, memory),
+				anchor_y: alloc_fcurve(&video.anchor_y, memory),
+				pos_x: alloc_fcurve(&video.pos_x, memory),
+				pos_y: alloc_fcurve(&video.pos_y, memory),
+				rot_z: alloc_fcurve(&video.rot_z, memory),
+				scale_x: alloc_fcurve(&video.scale_x, memory),
+				scale_y: alloc_fcurve(&video.scale_y, memory),
+				opacity: alloc_fcurve(&video.opacity, memory),
+				_3d,
+			};
+			memory.layer_videos.push_mut(video) as *const aet_layer_video
+		} else {
+			std::ptr::null()
+		};
+
+		let audio = if let Some(audio) = &layer.audio {
+			let audio = aet_layer_audio {
+				volume_l: alloc_fcurve(&audio.volume_l, memory),
+				volume_r: alloc_fcurve(&audio.volume_r, memory),
+				pan_l: alloc_fcurve(&audio.pan_l, memory),
+				pan_r: alloc_fcurve(&audio.pan_r, memory),
+			};
+			memory.layer_audios.push_mut(audio) as *const aet_layer_audio
+		} else {
+			std::ptr::null()
+		};
+
+		let (item_type, item) = alloc_item(layer.item, out_scene);
+		let aet_layer = aet_layer {
+			name,
+			start_time: layer.start_time,
+			end_time: layer.end_time,
+			offset_time: layer.offset_time,
+			time_scale: layer.time_scale,
+			flags: layer.flags.into_bits(),
+			quality: layer.quality.into(),
+			item_type,
+			item,
+			parent: std::ptr::null(),
+			markers_count: layer.markers.len() as u32,
+			markers,
+			video,
+			audio,
+		};
+
+		memory.layers.push(aet_layer);
+	}
+}
+
+// Parents can be in any composition of the scene, so they're filled in once all of its layers
+// are allocated. `out_layers` are the scene's layers in scene_comps order
+fn alloc_parents(scene: &Scene, out_layers: &mut [aet_layer]) {
+	let starts = scene_comps(scene)
+		.scan(0, |start, comp| {
+			let this = *start;
+			*start += comp.layers.len();
+			Some(this)
+		})
+		.collect::<Vec<_>>();
+
+	for (i, comp) in scene_comps(scene).enumerate() {
+		for (j, layer) in comp.layers.iter().enumerate() {
+			let parent = layer
+				.parent
+				.and_then(|parent| find_parent(scene, i, parent));
+			if let Some((comp, parent)) = parent {
+				out_layers[starts[i] + j].parent = &raw const out_layers[starts[comp] + parent];
+			}
+		}
+	}
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl Set {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __getattribute__(
+		slf: &pyo3::Bound<'_, Self>,
+		name: &pyo3::Bound<'_, pyo3::types::PyString>,
+	) -> PyResult<Py<PyAny>> {
+		proxy::getattribute(slf, name)
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __setattr__(
+		slf: &pyo3::Bound<'_, Self>,
+		name: &pyo3::Bound<'_, pyo3::types::PyString>,
+		value: &pyo3::Bound<'_, PyAny>,
+	) -> PyResult<()> {
+		proxy::setattr(slf.as_any(), name, value)
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "rename_sources"))]
+	fn py_rename_sources(&mut self, old: &str, new: &str) -> usize {
+		self.rename_sources(old, new)
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "validate_sources", signature = (database, sprite_set_id, sprites=None)))]
+	fn py_validate_sources(
+		&self,
+		database: &crate::database::sprite::Database,
+		sprite_set_id: u32,
+		sprites: Option<&crate::spr::Set>,
+	) -> Vec<SourceIssue> {
+		self.validate_sources(database, sprite_set_id, sprites)
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "fix_source_ids"))]
+	fn py_fix_source_ids(
+		&mut self,
+		database: &crate::database::sprite::Database,
+		sprite_set_id: u32,
+	) -> usize {
+		self.fix_source_ids(database, sprite_set_id)
+	}
+
+	#[cfg_attr(feature = "pyo3", staticmethod)]
+	pub fn from_buf(data: &[u8], modern: bool) -> Result<Self, DecodeError> {
+		let set = unsafe {
+			let set = kkdlib_aet_set_new();
+			kkdlib_aet_set_unpack_file(set, data.as_ptr() as *const c_void, data.len(), modern);
+			&mut *set
+		};
+
+		let mut real = Self {
+			modern: set.modern,
+			big_endian: set.big_endian,
+			is_x: set.is_x,
+			scenes: Vec::new(),
+		};
+
+		let scenes = std::ptr::slice_from_raw_parts(set.scenes, set.scenes_count as usize);
+		let scenes = unsafe { &*scenes };
+		for scene in scenes {
+			let scene = unsafe { &**scene };
+
+			if scene.comp_count == 0 || scene.comp.is_null() {
+				continue;
+			}
+
+			// The root is the last composition in the table
+			let comps = std::ptr::slice_from_raw_parts(scene.comp, scene.comp_count as usize);
+			let comps = unsafe { &*comps };
+			let mut ids = HashMap::new();
+			let comps = comps
+				.iter()
+				.map(|comp| comp.decode(scene, &mut ids))
+				.collect::<Result<Vec<_>, _>>();
+			let mut comps = match comps {
+				Ok(comps) => comps,
+				Err(err) => {
+					unsafe { kkdlib_aet_set_delete(set) };
+					return Err(err);
+				}
+			};
+			let root = comps.pop().unwrap_or_default();
+
+			let videos = if scene.video.is_null() {
+				Vec::new()
+			} else {
+				let videos =
+					std::ptr::slice_from_raw_parts(scene.video, scene.video_count as usize);
+				let videos = unsafe { &*videos };
+				videos.iter().map(|video| video.decode()).collect()
+			};
+
+			let audios = if scene.audio.is_null() {
+				Vec::new()
+			} else {
+				let audios =
+					std::ptr::slice_from_raw_parts(scene.audio, scene.audio_count as usize);
+				let audios = unsafe { &*audios };
+				audios
+					.iter()
+					.map(|audio| Audio {
+						sound_index: audio.sound_index,
+					})
+					.collect()
+			};
+
+			let name = unsafe { CStr::from_ptr(scene.name) };
+			let camera = if !scene.camera.is_null() {
+				let camera = unsafe { scene.camera.read() };
+				Some(Camera {
+					eye_x: camera.eye_x.into(),
+					eye_y: camera.eye_y.into(),
+					eye_z: camera.eye_z.into(),
+					pos_x: camera.pos_x.into(),
+					pos_y: camera.pos_y.into(),
+					pos_z: camera.pos_z.into(),
+					dir_x: camera.dir_x.into(),
+					dir_y: camera.dir_y.into(),
+					dir_z: camera.dir_z.into(),
+					rot_x: camera.rot_x.into(),
+					rot_y: camera.rot_y.into(),
+					rot_z: camera.rot_z.into(),
+					zoom: camera.zoom.into(),
+				})
+			} else {
+				None
+			};
+
+			real.scenes.push(Scene {
+				name: name.to_string_lossy().to_string(),
+				start_time: scene.start_time,
+				end_time: scene.end_time,
+				fps: scene.fps,
+				color: scene.color,
+				width: scene.width,
+				height: scene.height,
+				camera,
+				root,
+				comps,
+				videos,
+				audios,
+			});
+		}
+
+		unsafe { kkdlib_aet_set_delete(set) };
+
+		Ok(real)
+	}
+
+	pub fn to_buf(&self) -> Result<Vec<u8>, EncodeError> {
+		for scene in &self.scenes {
+			check_links(scene)?;
+		}
+
+		// Iteration one: get the amount of memory to allocate
+		let mut count = SetCounts::default();
+
+		for scene in &self.scenes {
+			count.scene_count += 1;
+			count.name_count += 1;
+			if let Some(camera) = &scene.camera {
+				count.camera_count += 1;
+				count_fcurve(&camera.eye_x, &mut count);
+				count_fcurve(&camera.eye_y, &mut count);
+				count_fcurve(&camera.eye_z, &mut count);
+				count_fcurve(&camera.pos_x, &mut count);
+				count_fcurve(&camera.pos_y, &mut count);
+				count_fcurve(&camera.pos_z, &mut count);
+				count_fcurve(&camera.dir_x, &mut count);
+				count_fcurve(&camera.dir_y, &mut count);
+				count_fcurve(&camera.dir_z, &mut count);
+				count_fcurve(&camera.rot_x, &mut count);
+				count_fcurve(&camera.rot_y, &mut count);
+				count_fcurve(&camera.rot_z, &mut count);
+				count_fcurve(&camera.zoom, &mut count);
+			}
+
+			count_comp(&scene.root, &mut count);
+			for comp in &scene.comps {
+				count_comp(comp, &mut count);
+			}
+
+			count.video_count += scene.videos.len();
+			for video in &scene.videos {
+				count.video_source_count += video.sources.len();
+				count.name_count += video.sources.len();
+			}
+
+			count.audio_count += scene.audios.len();
+		}
+
+		// Iteration two: allocate memory and fill the set pointers to the correct memory
+		let mut memory = SetMemory {
+			audios: Vec::with_capacity(count.audio_count),
+			cameras: Vec::with_capacity(count.camera_count),
+			comps: Vec::with_capacity(count.comp_count),
+			fcurves: Vec::new(),
+			fcurve_keys: Vec::with_capacity(count.fcurve_key_count),
+			layer_audios: Vec::with_capacity(count.layer_audio_count),
+			layers: Vec::with_capacity(count.layer_count),
+			layer_video_3ds: Vec::with_capacity(count.layer_video_3d_count),
+			layer_videos: Vec::with_capacity(count.layer_video_count),
+			markers: Vec::with_capacity(count.marker_count),
+			names: Vec::with_capacity(count.name_count),
+			scenes: Vec::with_capacity(count.scene_count),
+			videos: Vec::with_capacity(count.video_count),
+			video_sources: Vec::with_capacity(count.video_source_count),
+		};
+		let set = unsafe {
+			let set = kkdlib_aet_set_new();
+			&mut *set
+		};
+
+		for scene in &self.scenes {
+			let name = CString::new(scene.name.clone()).unwrap_or_default();
+			let name = memory.names.push_mut(name).as_ptr();
+
+			let camera = if let Some(camera) = &scene.camera {
+				let camera = aet_camera {
+					eye_x: alloc_fcurve(&camera.eye_x, &mut memory),
+					eye_y: alloc_fcurve(&camera.eye_y, &mut memory),
+					eye_z: alloc_fcurve(&camera.eye_z, &mut memory),
+					pos_x: alloc_fcurve(&camera.pos_x, &mut memory),
+					pos_y: alloc_fcurve(&camera.pos_y, &mut memory),
+					pos_z: alloc_fcurve(&camera.pos_z, &mut memory),
+					dir_x: alloc_fcurve(&camera.dir_x, &mut memory),
+					dir_y: alloc_fcurve(&camera.dir_y, &mut memory),
+					dir_z: alloc_fcurve(&camera.dir_z, &mut memory),
+					rot_x: alloc_fcurve(&camera.rot_x, &mut memory),
+					rot_y: alloc_fcurve(&camera.rot_y, &mut memory),
+					rot_z: alloc_fcurve(&camera.rot_z, &mut memory),
+					zoom: alloc_fcurve(&camera.zoom, &mut memory),
+				};
+				memory.cameras.push_mut(camera) as *const aet_camera
+			} else {
+				std::ptr::null()
+			};
+
+			let mut aet_scene = aet_scene {
+				name,
+				start_time: scene.start_time,
+				end_time: scene.end_time,
+				fps: scene.fps,
+				color: scene.color,
+				width: scene.width,
+				height: scene.height,
+				camera,
+				comp_count: scene.comps.len() as u32 + 1,
+				comp: memory
+					.comps
+					.last_mut()
+					.map(|ptr| ptr as *const aet_comp)
+					.map(|ptr| unsafe { ptr.offset(1) })
+					.unwrap_or(memory.comps.as_ptr()),
+				video_count: scene.videos.len() as u32,
+				video: memory
+					.videos
+					.last_mut()
+					.map(|ptr| ptr as *const aet_video)
+					.map(|ptr| unsafe { ptr.offset(1) })
+					.unwrap_or(memory.videos.as_ptr()),
+				audio_count: scene.audios.len() as u32,
+				audio: memory
+					.audios
+					.last_mut()
+					.map(|ptr| ptr as *const aet_audio)
+					.map(|ptr| unsafe { ptr.offset(1) })
+					.unwrap_or(memory.audios.as_ptr()),
+			};
+
+			for video in &scene.videos {
+				let video = alloc_video(video, &mut memory);
+				memory.videos.push(video);
+			}
+
+			for audio in &scene.audios {
+				memory.audios.push(aet_audio {
+					sound_index: audio.sound_index,
+				});
+			}
+
+			let layers_start = memory.layers.len();
+			for comp in scene_comps(scene) {
+				let mut out_comp = aet_comp {
+					layers_count: 0,
+					layers: std::ptr::null(),
+				};
+				alloc_comp(comp, &mut out_comp, &aet_scene, &mut memory);
+				memory.comps.push(out_comp);
+			}
+			alloc_parents(scene, &mut memory.layers[layers_start..]);
+
+			if aet_scene.video_count == 0 {
+				aet_scene.video = std::ptr::null();
+			}
+			if aet_scene.audio_count == 0 {
+				aet_scene.audio = std::ptr::null();
+			}
+
+			memory.scenes.push(Box::new(aet_scene));
+		}
+
+		set.ready = true;
+		set.modern = self.modern;
+		set.big_endian = self.big_endian;
+		set.is_x = self.is_x;
+
+		let scenes = memory
+			.scenes
+			.iter()
+			.map(|scene| scene.as_ref() as *const aet_scene)
+			.collect::<Vec<_>>();
+		set.scenes_count = scenes.len() as u32;
+		set.scenes = scenes.as_ptr();
+
+		let mut ptr = std::ptr::null_mut();
+		let mut size = 0usize;
+
+		unsafe {
+			kkdlib_aet_set_pack_file(set, &mut ptr, &mut size);
+		}
+
+		let slice = std::ptr::slice_from_raw_parts(ptr as *const u8, size);
+		let slice = unsafe { &*slice };
+
+		let mut vec = Vec::with_capacity(size);
+		vec.extend_from_slice(slice);
+		unsafe {
+			kkdlib_aet_set_delete_packed_file(ptr);
+			kkdlib_aet_set_delete(set);
+		}
+
+		Ok(vec)
+	}
+}
+
+#[repr(C)]
+struct aet_fcurve {
+	keys_count: u32,
+	keys: *const f32,
+}
+
+impl Into<FCurve> for aet_fcurve {
+	fn into(self) -> FCurve {
+		if self.keys_count == 0 {
+			FCurve { keys: Vec::new() }
+		} else if self.keys_count == 1 {
+			FCurve {
+				keys: vec![FCurveKey {
+					frame: 0.0,
+					value: unsafe { self.keys.read() },
+					tangent: 0.0,
+				}],
+			}
+		} else {
+			let frames = std::ptr::slice_from_raw_parts(self.keys, self.keys_count as usize);
+			let values = unsafe { self.keys.offset(self.keys_count as isize) } as *const (f32, f32);
+			let values = std::ptr::slice_from_raw_parts(values, self.keys_count as usize);
+			let (frames, values) = unsafe { (&*frames, &*values) };
+			let keys = frames
+				.iter()
+				.zip(values.iter())
+				.map(|(frame, (value, tangent))| FCurveKey {
+					frame: *frame,
+					value: *value,
+					tangent: *tangent,
+				})
+				.collect();
+
+			FCurve { keys }
+		}
+	}
+}
+
+#[repr(C)]
+struct aet_layer_video_3d {
+	anchor_z: aet_fcurve,
+	pos_z: aet_fcurve,
+	dir_x: aet_fcurve,
+	dir_y: aet_fcurve,
+	dir_z: aet_fcurve,
+	rot_x: aet_fcurve,
+	rot_y: aet_fcurve,
+	scale_z: aet_fcurve,
+}
+
+#[repr(C)]
+struct aet_transfer_mode {
+	mode: u8,
+	flag: u8,
+	matte: u8,
+}
+
+#[repr(C)]
+struct aet_layer_video {
+	transfer_mode: aet_transfer_mode,
+	anchor_x: aet_fcurve,
+	anchor_y: aet_fcurve,
+	pos_x: aet_fcurve,
+	pos_y: aet_fcurve,
+	rot_z: aet_fcurve,
+	scale_x: aet_fcurve,
+	scale_y: aet_fcurve,
+	opacity: aet_fcurve,
+	_3d: *const aet_layer_video_3d,
+}
+
+#[repr(C)]
+struct aet_layer_audio {
+	volume_l: aet_fcurve,
+	volume_r: aet_fcurve,
+	pan_l: aet_fcurve,
+	pan_r: aet_fcurve,
+}
+
+#[repr(C)]
+struct aet_camera {
+	eye_x: aet_fcurve,
+	eye_y: aet_fcurve,
+	eye_z: aet_fcurve,
+	pos_x: aet_fcurve,
+	pos_y: aet_fcurve,
+	pos_z: aet_fcurve,
+	dir_x: aet_fcurve,
+	dir_y: aet_fcurve,
+	dir_z: aet_fcurve,
+	rot_x: aet_fcurve,
+	rot_y: aet_fcurve,
+	rot_z: aet_fcurve,
+	zoom: aet_fcurve,
+}
+
+#[repr(C)]
+struct aet_video_src {
+	sprite_name: *const c_char,
+	sprite_index: u32,
+}
+
+#[repr(C)]
+struct aet_video {
+	color: [u8; 3],
+	width: u16,
+	height: u16,
+	fpf: f32,
+	sources_count: u32,
+	sources: *const aet_video_src,
+}
+
+#[repr(C)]
+struct aet_audio {
+	sound_index: u32,
+}
+
+#[repr(C)]
+struct aet_marker {
+	time: f32,
+	name: *const c_char,
+}
+
+#[repr(C)]
+struct aet_layer {
+	name: *const c_char,
+	start_time: f32,
+	end_time: f32,
+	offset_time: f32,
+	time_scale: f32,
+	flags: u16,
+	quality: u8,
+	item_type: u8,
+	item: *const c_void,
+	parent: *const aet_layer,
+	markers_count: u32,
+	markers: *const aet_marker,
+	video: *const aet_layer_video,
+	audio: *const aet_layer_audio,
+}
+
+#[repr(C)]
+struct aet_comp {
+	layers_count: u32,
+	layers: *const aet_layer,
+}
+
+// Index of an item in one of the scene tables
+fn table_index<T>(table: *const T, count: u32, item: *const c_void) -> Option<usize> {
+	let offset = (item as usize).checked_sub(table as usize)?;
+	if table.is_null() || offset % size_of::<T>() != 0 || offset / size_of::<T>() >= count as usize
+	{
+		None
+	} else {
+		Some(offset / size_of::<T>())
+	}
+}
+
+impl aet_video {
+	fn decode(&self) -> Video {
+		let sources = std::ptr::slice_from_raw_parts(self.sources, self.sources_count as usize);
+		let sources = unsafe { &*sources };
+		let sources = sources
+			.iter()
+			.map(|source| {
+				let name = unsafe { CStr::from_ptr(source.sprite_name) };
+				VideoSource {
+					name: name.to_string_lossy().to_string(),
+					id: source.sprite_index,
+				}
+			})
+			.collect();
+
+		Video {
+			color: self.color,
+			width: self.width,
+			height: self.height,
+			fpf: self.fpf,
+			sources,
+		}
+	}
+}
+
+impl aet_comp {
+	// ids maps layer addresses to ids so parents can be linked before the parent is decoded
+	fn decode(
+		&self,
+		scene: &aet_scene,
+		ids: &mut HashMap<usize, LayerId>,
+	) -> Result<Composition, DecodeError> {
+		let layers = std::ptr::slice_from_raw_parts(self.layers, self.layers_count as usize);
+		let layers = unsafe { &*layers };
+
+		let mut real = Composition {
+			layers: Vec::with_capacity(self.layers_count as usize),
+		};
+		for (i, layer) in layers.iter().enumerate() {
+			let name = unsafe { CStr::from_ptr(layer.name) };
+
+			let markers =
+				std::ptr::slice_from_raw_parts(layer.markers, layer.markers_count as usize);
+			let markers = unsafe { &*markers };
+			let markers = markers
+				.iter()
+				.map(|marker| {
+					let name = unsafe { CStr::from_ptr(marker.name) };
+					(name.to_string_lossy().to_string(), marker.time)
+				})
+				.collect::<Vec<_>>();
+
+			let video = if !layer.video.is_null() {
+				let video = unsafe { layer.video.read() };
+				let _3d = if !video._3d.is_null() {
+					let _3d = unsafe { video._3d.read() };
+					Some(LayerVideo3D {
+						anchor_z: _3d.anchor_z.into(),
+						pos_z: _3d.pos_z.into(),
+						dir_x: _3d.dir_x.into(),
+						dir_y: _3d.dir_y.into(),
+						dir_z: _3d.dir_z.into(),
+						rot_x: _3d.rot_x.into(),
+						rot_y: _3d.rot_y.into(),
+						scale_z: _3d.scale_z.into(),
+					})
+				} else {
+					None
+				};
+
+				Some(LayerVideo {
+					transfer_mode: TransferMode {
+						mode: BlendMode::try_from(video.transfer_mode.mode)
+							.unwrap_or_else(BlendMode::Unknown),
+						flag: video.transfer_mode.flag,
+						matte: video.transfer_mode.matte,
+					},
+					anchor_x: video.anchor_x.into(),
+					anchor_y: video.anchor_y.into(),
+					pos_x: video.pos_x.into(),
+					pos_y: video.pos_y.into(),
+					rot_z: video.rot_z.into(),
+					scale_x: video.scale_x.into(),
+					scale_y: video.scale_y.into(),
+					opacity: video.opacity.into(),
+					_3d,
+				})
+			} else {
+				None
+			};
+
+			let audio = if !layer.audio.is_null() {
+				let audio = unsafe { layer.audio.read() };
+				Some(LayerAudio {
+					volume_l: audio.volume_l.into(),
+					volume_r: audio.volume_r.into(),
+					pan_l: audio.pan_l.into(),
+					pan_r: audio.pan_r.into(),
+				})
+			} else {
+				None
+			};
+
+			let item = match layer.item_type {
+				0 => None,
+				1 => table_index(scene.video, scene.video_count, layer.item).map(Item::Video),
+				2 => table_index(scene.audio, scene.audio_count, layer.item).map(Item::Audio),
+				3 => {
+					table_index(scene.comp, scene.comp_count - 1, layer.item).map(Item::Composition)
+				}
+				item_type => {
+					return Err(DecodeError::UnknownItemType {
+						layer: name.to_string_lossy().to_string(),
+						item_type,
+					});
+				}
+			};
+			if layer.item_type != 0 && item.is_none() {
+				return Err(DecodeError::DanglingItem {
+					layer: name.to_string_lossy().to_string(),
+					item_type: layer.item_type,
+				});
+			}
+
+			let id = *ids
+				.entry(unsafe { self.layers.add(i) } as usize)
+				.or_insert_with(LayerId::unique);
+			let parent = if layer.parent.is_null() {
+				None
+			} else {
+				Some(
+					*ids.entry(layer.parent as usize)
+						.or_insert_with(LayerId::unique),
+				)
+			};
+
+			real.layers.push(Layer {
+				id,
+				name: name.to_string_lossy().to_string(),
+				start_time: layer.start_time,
+				end_time: layer.end_time,
+				offset_time: layer.offset_time,
+				time_scale: layer.time_scale,
+				flags: LayerFlags::from_bits(layer.flags),
+				quality: LayerQuality::try_from(layer.quality)
+					.unwrap_or_else(LayerQuality::Unknown),
+				item,
+				markers,
+				video,
+				audio,
+				parent,
+			});
+		}
+
+		Ok(real)
+	}
+}
+
+#[repr(C)]
+struct aet_scene {
+	name: *const c_char,
+	start_time: f32,
+	end_time: f32,
+	fps: f32,
+	color: [u8; 3],
+	width: u32,
+	height: u32,
+	camera: *const aet_camera,
+	comp_count: u32,
+	comp: *const aet_comp,
+	video_count: u32,
+	video: *const aet_video,
+	audio_count: u32,
+	audio: *const aet_audio,
+}
+
+#[repr(C)]
+struct aet_set {
+	ready: bool,
+	modern: bool,
+	big_endian: bool,
+	is_x: bool,
+	scenes_count: u32,
+	scenes: *const *const aet_scene,
+}
+
+unsafe extern "C" {
+	fn kkdlib_aet_set_new() -> *mut aet_set;
+	fn kkdlib_aet_set_pack_file(set: *mut aet_set, data: *mut *mut c_void, size: *mut usize);
+	fn kkdlib_aet_set_delete_packed_file(data: *mut c_void);
+	fn kkdlib_aet_set_unpack_file(
+		set: *mut aet_set,
+		data: *const c_void,
+		size: usize,
+		modern: bool,
+	);
+	fn kkdlib_aet_set_delete(set: *mut aet_set);
+}
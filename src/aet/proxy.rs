@@ -0,0 +1,581 @@
+// Python reads of nested values would otherwise hand out copies, so `scene.root.layers[0].name = x`
+// would edit a copy and be lost. Nested values are handed out as a Ref instead, which finds the
+// value inside its owner again on every access and writes edits back into it
+use super::*;
+use pyo3::conversion::FromPyObjectOwned;
+use pyo3::pyclass::boolean_struct::False;
+use pyo3::types::{PyDict, PyList, PyString, PyTuple};
+use pyo3::{Bound, PyClass};
+use std::any::Any;
+
+#[derive(Debug, Clone)]
+pub(crate) enum Step {
+	Attr(String),
+	Index(usize),
+}
+
+// Python code never runs while the owner is borrowed: values are copied out, edited as plain
+// Python objects and then extracted and written back in separate steps
+pub(crate) trait Node {
+	// The nested value at `step` if it is handed out as a Ref, Some(None) for an unset Option
+	fn child(&mut self, _step: &Step) -> Option<Option<&mut dyn Node>> {
+		None
+	}
+
+	// For values indexed like lists, None to go through the Python value
+	fn len(&self) -> Option<usize> {
+		None
+	}
+
+	// The item at `i` < len() when it isn't handed out as a Ref
+	fn item(&self, _py: Python<'_>, _i: usize) -> PyResult<Py<PyAny>> {
+		Err(pyo3::exceptions::PyTypeError::new_err(
+			"value is not subscriptable",
+		))
+	}
+
+	fn to_object(&self, py: Python<'_>) -> PyResult<Py<PyAny>>;
+
+	// How to turn a Python object back into this type, called without the owner borrowed
+	fn extractor(&self) -> Extract;
+
+	// Takes a value from this node's extractor
+	fn set(&mut self, value: Box<dyn Any>);
+}
+
+type Extract = fn(&Bound<'_, PyAny>) -> PyResult<Box<dyn Any>>;
+
+macro_rules! node {
+	($ty:ty) => {
+		node!($ty, |_this, _step| None);
+	};
+	($ty:ty, |$this:ident, $step:ident| $child:expr $(, len: |$len_this:ident| $len:expr)?) => {
+		impl Node for $ty {
+			fn child(&mut self, $step: &Step) -> Option<Option<&mut dyn Node>> {
+				let $this = self;
+				$child
+			}
+
+			$(fn len(&self) -> Option<usize> {
+				let $len_this = self;
+				$len
+			})?
+
+			fn to_object(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+				Ok(Py::new(py, self.clone())?.into_any())
+			}
+
+			fn extractor(&self) -> Extract {
+				|value| Ok(Box::new(value.extract::<$ty>()?))
+			}
+
+			fn set(&mut self, value: Box<dyn Any>) {
+				if let Ok(value) = value.downcast() {
+					*self = *value;
+				}
+			}
+		}
+	};
+}
+
+// Lists of values that are edited in place index into their items, other lists only get the
+// list methods
+macro_rules! list {
+	($item:ty) => {
+		list!($item, |list, step| match step {
+			Step::Index(i) => Some(list.get_mut(*i).map(|item| item as &mut dyn Node)),
+			Step::Attr(_) => None,
+		});
+	};
+	($item:ty, |$this:ident, $step:ident| $child:expr) => {
+		impl Node for Vec<$item> {
+			fn child(&mut self, $step: &Step) -> Option<Option<&mut dyn Node>> {
+				let $this = self;
+				$child
+			}
+
+			fn len(&self) -> Option<usize> {
+				Some(self.len())
+			}
+
+			fn item(&self, py: Python<'_>, i: usize) -> PyResult<Py<PyAny>> {
+				Ok(self[i].clone().into_pyobject(py)?.into_any().unbind())
+			}
+
+			fn to_object(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+				Ok(self.clone().into_pyobject(py)?.into_any().unbind())
+			}
+
+			fn extractor(&self) -> Extract {
+				|value| Ok(Box::new(value.extract::<Vec<$item>>()?))
+			}
+
+			fn set(&mut self, value: Box<dyn Any>) {
+				if let Ok(value) = value.downcast() {
+					*self = *value;
+				}
+			}
+		}
+	};
+}
+
+fn attr<'a>(step: &Step, name: &str, node: &'a mut dyn Node) -> Option<Option<&'a mut dyn Node>> {
+	match step {
+		Step::Attr(attr) if attr == name => Some(Some(node)),
+		_ => None,
+	}
+}
+
+fn optional<'a, T: Node>(
+	step: &Step,
+	name: &str,
+	node: &'a mut Option<T>,
+) -> Option<Option<&'a mut dyn Node>> {
+	match step {
+		Step::Attr(attr) if attr == name => Some(node.as_mut().map(|node| node as &mut dyn Node)),
+		_ => None,
+	}
+}
+
+node!(Set, |set, step| attr(step, "scenes", &mut set.scenes));
+node!(Scene, |scene, step| {
+	optional(step, "camera", &mut scene.camera)
+		.or_else(|| attr(step, "root", &mut scene.root))
+		.or_else(|| attr(step, "comps", &mut scene.comps))
+		.or_else(|| attr(step, "videos", &mut scene.videos))
+		.or_else(|| attr(step, "audios", &mut scene.audios))
+});
+node!(
+	Composition,
+	|comp, step| match step {
+		Step::Index(i) => Some(comp.layers.get_mut(*i).map(|layer| layer as &mut dyn Node)),
+		Step::Attr(_) => attr(step, "layers", &mut comp.layers),
+	},
+	len: |comp| Some(comp.layers.len())
+);
+node!(Layer, |layer, step| {
+	optional(step, "video", &mut layer.video)
+		.or_else(|| optional(step, "audio", &mut layer.audio))
+		.or_else(|| attr(step, "markers", &mut layer.markers))
+});
+node!(LayerVideo, |video, step| {
+	let Step::Attr(name) = step else {
+		return None;
+	};
+	let curve = match name.as_str() {
+		"video_3d" => return optional(step, "video_3d", &mut video._3d),
+		"transfer_mode" => return Some(Some(&mut video.transfer_mode)),
+		"anchor_x" => &mut video.anchor_x,
+		"anchor_y" => &mut video.anchor_y,
+		"pos_x" => &mut video.pos_x,
+		"pos_y" => &mut video.pos_y,
+		"rot_z" => &mut video.rot_z,
+		"scale_x" => &mut video.scale_x,
+		"scale_y" => &mut video.scale_y,
+		"opacity" => &mut video.opacity,
+		_ => return None,
+	};
+	Some(Some(curve))
+});
+node!(LayerVideo3D, |video, step| {
+	let Step::Attr(name) = step else {
+		return None;
+	};
+	let curve = match name.as_str() {
+		"anchor_z" => &mut video.anchor_z,
+		"pos_z" => &mut video.pos_z,
+		"dir_x" => &mut video.dir_x,
+		"dir_y" => &mut video.dir_y,
+		"dir_z" => &mut video.dir_z,
+		"rot_x" => &mut video.rot_x,
+		"rot_y" => &mut video.rot_y,
+		"scale_z" => &mut video.scale_z,
+		_ => return None,
+	};
+	Some(Some(curve))
+});
+node!(LayerAudio, |audio, step| {
+	let Step::Attr(name) = step else {
+		return None;
+	};
+	let curve = match name.as_str() {
+		"volume_l" => &mut audio.volume_l,
+		"volume_r" => &mut audio.volume_r,
+		"pan_l" => &mut audio.pan_l,
+		"pan_r" => &mut audio.pan_r,
+		_ => return None,
+	};
+	Some(Some(curve))
+});
+node!(Camera, |camera, step| {
+	let Step::Attr(name) = step else {
+		return None;
+	};
+	let curve = match name.as_str() {
+		"eye_x" => &mut camera.eye_x,
+		"eye_y" => &mut camera.eye_y,
+		"eye_z" => &mut camera.eye_z,
+		"pos_x" => &mut camera.pos_x,
+		"pos_y" => &mut camera.pos_y,
+		"pos_z" => &mut camera.pos_z,
+		"dir_x" => &mut camera.dir_x,
+		"dir_y" => &mut camera.dir_y,
+		"dir_z" => &mut camera.dir_z,
+		"rot_x" => &mut camera.rot_x,
+		"rot_y" => &mut camera.rot_y,
+		"rot_z" => &mut camera.rot_z,
+		"zoom" => &mut camera.zoom,
+		_ => return None,
+	};
+	Some(Some(curve))
+});
+node!(Video, |video, step| attr(
+	step,
+	"sources",
+	&mut video.sources
+));
+node!(FCurve, |curve, step| attr(step, "keys", &mut curve.keys));
+node!(TransferMode);
+node!(VideoSource);
+node!(Audio);
+node!(FCurveKey);
+
+list!(Scene);
+list!(Composition);
+list!(Layer);
+list!(Video);
+list!(VideoSource);
+list!(Audio);
+list!(FCurveKey);
+list!((String, f32), |_list, _step| None);
+
+type Root = fn(&Bound<'_, PyAny>, &mut dyn FnMut(&mut dyn Node) -> PyResult<()>) -> PyResult<()>;
+
+fn root<T: Node + PyClass<Frozen = False>>(
+	owner: &Bound<'_, PyAny>,
+	f: &mut dyn FnMut(&mut dyn Node) -> PyResult<()>,
+) -> PyResult<()> {
+	let mut owner = owner.cast::<T>()?.try_borrow_mut()?;
+	f(&mut *owner)
+}
+
+// A value nested somewhere in `owner`, it acts like the value itself
+#[pyclass(frozen)]
+pub struct Ref {
+	owner: Py<PyAny>,
+	root: Root,
+	path: Vec<Step>,
+}
+
+// What a step from a Ref leads to
+enum Found {
+	Ref,
+	None,
+	Value(Py<PyAny>),
+}
+
+impl Ref {
+	pub(crate) fn new<T: Node + PyClass<Frozen = False>>(
+		owner: &Bound<'_, T>,
+		path: Vec<Step>,
+	) -> Self {
+		Self {
+			owner: owner.clone().into_any().unbind(),
+			root: root::<T>,
+			path,
+		}
+	}
+
+	fn clone_ref(&self, py: Python<'_>) -> Self {
+		Self {
+			owner: self.owner.clone_ref(py),
+			root: self.root,
+			path: self.path.clone(),
+		}
+	}
+
+	fn child(&self, py: Python<'_>, step: Step) -> Self {
+		let mut child = self.clone_ref(py);
+		child.path.push(step);
+		child
+	}
+
+	// Runs `f` on the value with the owner borrowed, `f` must not call into Python
+	fn with<T>(&self, py: Python<'_>, f: impl FnOnce(&mut dyn Node) -> PyResult<T>) -> PyResult<T> {
+		let mut f = Some(f);
+		let mut result = None;
+		(self.root)(self.owner.bind(py), &mut |node| {
+			let mut node = node;
+			for step in &self.path {
+				node = match node.child(step) {
+					Some(Some(child)) => child,
+					_ => {
+						return Err(pyo3::exceptions::PyLookupError::new_err(
+							"the value this refers to no longer exists",
+						));
+					}
+				};
+			}
+			if let Some(f) = f.take() {
+				result = Some(f(node)?);
+			}
+			Ok(())
+		})?;
+		result.ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("owner was not visited"))
+	}
+
+	fn value(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+		self.with(py, |node| node.to_object(py))
+	}
+
+	// Writes an edited copy from value() back into the owner
+	fn write_back(&self, py: Python<'_>, object: &Bound<'_, PyAny>) -> PyResult<()> {
+		let extract = self.with(py, |node| Ok(node.extractor()))?;
+		let value = extract(object)?;
+		self.with(py, |node| {
+			node.set(value);
+			Ok(())
+		})
+	}
+
+	// Everything that isn't handed out as a Ref is done on a copy, which is then written back
+	// in case it was changed
+	fn call(
+		&self,
+		py: Python<'_>,
+		name: &str,
+		args: &Bound<'_, PyTuple>,
+		kwargs: Option<&Bound<'_, PyDict>>,
+	) -> PyResult<Py<PyAny>> {
+		let args = args
+			.iter()
+			.map(|arg| unwrap(&arg))
+			.collect::<PyResult<Vec<_>>>()?;
+		let args = PyTuple::new(py, args)?;
+		let kwargs = match kwargs {
+			Some(kwargs) => {
+				let unwrapped = PyDict::new(py);
+				for (key, value) in kwargs.iter() {
+					unwrapped.set_item(key, unwrap(&value)?)?;
+				}
+				Some(unwrapped)
+			}
+			None => None,
+		};
+		let object = self.value(py)?.into_bound(py);
+		let result = object.call_method(name, &args, kwargs.as_ref())?;
+		self.write_back(py, &object)?;
+		Ok(result.unbind())
+	}
+
+	fn len(&self, py: Python<'_>) -> PyResult<usize> {
+		match self.with(py, |node| Ok(node.len()))? {
+			Some(len) => Ok(len),
+			None => self.value(py)?.bind(py).len(),
+		}
+	}
+}
+
+#[pymethods]
+impl Ref {
+	fn __getattr__(&self, py: Python<'_>, name: &str) -> PyResult<Py<PyAny>> {
+		let step = Step::Attr(name.to_string());
+		let found = self.with(py, |node| {
+			Ok(match node.child(&step) {
+				Some(Some(_)) => Found::Ref,
+				Some(None) => Found::None,
+				None => Found::Value(node.to_object(py)?),
+			})
+		})?;
+		let object = match found {
+			Found::Ref => return Ok(Py::new(py, self.child(py, step))?.into_any()),
+			Found::None => return Ok(py.None()),
+			Found::Value(object) => object,
+		};
+		let value = object.bind(py).getattr(name)?;
+		if !value.is_callable() {
+			return Ok(value.unbind());
+		}
+		let method = Method {
+			target: self.clone_ref(py),
+			name: name.to_string(),
+		};
+		Ok(Py::new(py, method)?.into_any())
+	}
+
+	fn __setattr__(&self, py: Python<'_>, name: &str, value: &Bound<'_, PyAny>) -> PyResult<()> {
+		let value = unwrap(value)?;
+		let object = self.value(py)?.into_bound(py);
+		object.setattr(name, &value)?;
+		self.write_back(py, &object)
+	}
+
+	// Only the indexed item is looked at, a Ref to it or a copy of it
+	fn __getitem__(&self, py: Python<'_>, index: isize) -> PyResult<Py<PyAny>> {
+		let found = self.with(py, |node| {
+			let Some(len) = node.len() else {
+				return Ok(None);
+			};
+			let len = len as isize;
+			let index = if index < 0 { index + len } else { index };
+			if index < 0 || index >= len {
+				return Err(pyo3::exceptions::PyIndexError::new_err(
+					"index out of range",
+				));
+			}
+			let step = Step::Index(index as usize);
+			Ok(Some(match node.child(&step) {
+				Some(Some(_)) => (step, Found::Ref),
+				_ => (step, Found::Value(node.item(py, index as usize)?)),
+			}))
+		})?;
+		match found {
+			Some((step, Found::Ref)) => Ok(Py::new(py, self.child(py, step))?.into_any()),
+			Some((_, Found::Value(item))) => Ok(item),
+			Some((_, Found::None)) => Ok(py.None()),
+			None => Ok(self.value(py)?.bind(py).get_item(index)?.unbind()),
+		}
+	}
+
+	fn __setitem__(&self, py: Python<'_>, index: isize, value: &Bound<'_, PyAny>) -> PyResult<()> {
+		let args = PyTuple::new(py, [index.into_pyobject(py)?.into_any(), value.clone()])?;
+		self.call(py, "__setitem__", &args, None)?;
+		Ok(())
+	}
+
+	fn __delitem__(&self, py: Python<'_>, index: isize) -> PyResult<()> {
+		self.call(py, "__delitem__", &PyTuple::new(py, [index])?, None)?;
+		Ok(())
+	}
+
+	fn __len__(&self, py: Python<'_>) -> PyResult<usize> {
+		self.len(py)
+	}
+
+	fn __bool__(&self, py: Python<'_>) -> PyResult<bool> {
+		match self.with(py, |node| Ok(node.len()))? {
+			Some(len) => Ok(len > 0),
+			None => self.value(py)?.bind(py).is_truthy(),
+		}
+	}
+
+	fn __iter__(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+		let items = (0..self.len(py)?)
+			.map(|i| self.__getitem__(py, i as isize))
+			.collect::<PyResult<Vec<_>>>()?;
+		Ok(PyList::new(py, items)?.try_iter()?.into_any().unbind())
+	}
+
+	fn __contains__(&self, py: Python<'_>, value: &Bound<'_, PyAny>) -> PyResult<bool> {
+		self.value(py)?.bind(py).contains(unwrap(value)?)
+	}
+
+	fn __eq__(&self, py: Python<'_>, other: &Bound<'_, PyAny>) -> PyResult<bool> {
+		self.value(py)?.bind(py).eq(unwrap(other)?)
+	}
+
+	fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+		Ok(self.value(py)?.bind(py).repr()?.to_string())
+	}
+
+	// `copy.copy(ref)` gives the plain value, detached from the owner
+	fn __copy__(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+		self.value(py)
+	}
+}
+
+// A method looked up through a Ref, called on a copy of the value which is then written back
+#[pyclass(frozen)]
+pub struct Method {
+	target: Ref,
+	name: String,
+}
+
+#[pymethods]
+impl Method {
+	#[pyo3(signature = (*args, **kwargs))]
+	fn __call__(
+		&self,
+		py: Python<'_>,
+		args: &Bound<'_, PyTuple>,
+		kwargs: Option<&Bound<'_, PyDict>>,
+	) -> PyResult<Py<PyAny>> {
+		self.target.call(py, &self.name, args, kwargs)
+	}
+}
+
+// Refs passed back in are turned into plain values, lists of them too
+pub(crate) fn unwrap<'py>(value: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+	let py = value.py();
+	if let Ok(value) = value.cast::<Ref>() {
+		return Ok(value.get().value(py)?.into_bound(py));
+	}
+	if let Ok(list) = value.cast::<PyList>() {
+		let items = list
+			.iter()
+			.map(|item| unwrap(&item))
+			.collect::<PyResult<Vec<_>>>()?;
+		return Ok(PyList::new(py, items)?.into_any());
+	}
+	Ok(value.clone())
+}
+
+pub(crate) fn extract<T>(value: &Bound<'_, PyAny>) -> PyResult<T>
+where
+	T: for<'py> FromPyObjectOwned<'py>,
+	for<'a, 'py> <T as FromPyObject<'a, 'py>>::Error: Into<PyErr>,
+{
+	unwrap(value)?.extract().map_err(Into::into)
+}
+
+// __getattribute__ of the owning classes, nested values come back as Refs into `slf`
+pub(crate) fn getattribute<T: Node + PyClass<Frozen = False>>(
+	slf: &Bound<'_, T>,
+	name: &Bound<'_, PyString>,
+) -> PyResult<Py<PyAny>> {
+	let py = slf.py();
+	if let Ok(mut this) = slf.try_borrow_mut() {
+		let step = Step::Attr(name.to_cow()?.into_owned());
+		match this.child(&step) {
+			Some(Some(_)) => {
+				drop(this);
+				return Ok(Py::new(py, Ref::new(slf, vec![step]))?.into_any());
+			}
+			Some(None) => return Ok(py.None()),
+			None => {}
+		}
+	}
+	unsafe {
+		let value = pyo3::ffi::PyObject_GenericGetAttr(slf.as_ptr(), name.as_ptr());
+		Bound::from_owned_ptr_or_err(py, value).map(Bound::unbind)
+	}
+}
+
+// __setattr__ of the owning classes, so a Ref can be assigned like the value it refers to
+pub(crate) fn setattr(
+	slf: &Bound<'_, PyAny>,
+	name: &Bound<'_, PyString>,
+	value: &Bound<'_, PyAny>,
+) -> PyResult<()> {
+	let value = unwrap(value)?;
+	let result =
+		unsafe { pyo3::ffi::PyObject_GenericSetAttr(slf.as_ptr(), name.as_ptr(), value.as_ptr()) };
+	match result {
+		-1 => Err(PyErr::fetch(slf.py())),
+		_ => Ok(()),
+	}
+}
+
+impl LayerPath {
+	// The path to the layer from its Scene
+	pub(crate) fn steps(self) -> Vec<Step> {
+		match self.comp {
+			Some(comp) => vec![
+				Step::Attr("comps".to_string()),
+				Step::Index(comp),
+				Step::Index(self.index),
+			],
+			None => vec![Step::Attr("root".to_string()), Step::Index(self.index)],
+		}
+	}
+}
@@ -0,0 +1,302 @@
+use super::*;
+use crate::database::sprite;
+use crate::spr;
+
+struct SpriteTexture {
+	width: usize,
+	height: usize,
+	// Premultiplied, top row first
+	pixels: Vec<[f32; 4]>,
+}
+
+// Where a sprite sits in its texture, in pixels from the top left
+struct SpriteRect {
+	texture: usize,
+	x: f32,
+	y: f32,
+	width: f32,
+	height: f32,
+}
+
+// Draws evaluated scenes on the CPU
+// Sprites are looked up through the database by id and then by name, only taking sprites of the
+// loaded set, falling back to the names in the sprite set when the database doesn't know them
+pub struct Renderer<'a> {
+	database: Option<&'a sprite::Database>,
+	// Name of the loaded set in the database, SPR_<set>
+	set_name: Option<String>,
+	textures: Vec<SpriteTexture>,
+	sprites: Vec<SpriteRect>,
+	names: HashMap<String, usize>,
+}
+
+impl<'a> Renderer<'a> {
+	pub fn new(database: Option<&'a sprite::Database>) -> Self {
+		Self {
+			database,
+			set_name: None,
+			textures: Vec::new(),
+			sprites: Vec::new(),
+			names: HashMap::new(),
+		}
+	}
+
+	// Decodes every texture of the set the scene was made for, replacing any loaded before
+	// Without the set's name in the database only sprite names matching a source exactly are found
+	pub fn load_sprites(&mut self, set: &spr::Set, set_name: Option<&str>) {
+		self.set_name = set_name.map(str::to_string);
+		self.textures = set
+			.textures()
+			.map(|(_, texture)| {
+				let mip = texture.get_mipmap(0, 0);
+				let width = mip.as_ref().map_or(0, |mip| mip.width()) as usize;
+				let height = mip.as_ref().map_or(0, |mip| mip.height()) as usize;
+				let rgba = if texture.is_ycbcr() {
+					texture.decode_ycbcr()
+				} else {
+					mip.and_then(|mip| mip.rgba())
+				}
+				.unwrap_or_default();
+
+				// Textures are stored bottom row first
+				let mut pixels = vec![[0.0; 4]; width * height];
+				for (i, px) in rgba.chunks_exact(4).take(width * height).enumerate() {
+					let a = px[3] as f32 / 255.0;
+					let (x, y) = (i % width, height - 1 - i / width);
+					pixels[y * width + x] = [
+						px[0] as f32 / 255.0 * a,
+						px[1] as f32 / 255.0 * a,
+						px[2] as f32 / 255.0 * a,
+						a,
+					];
+				}
+				SpriteTexture {
+					width,
+					height,
+					pixels,
+				}
+			})
+			.collect();
+
+		self.names.clear();
+		self.sprites = set
+			.sprites()
+			.enumerate()
+			.map(|(index, (name, info))| {
+				self.names.insert(name, index);
+				SpriteRect {
+					texture: info.texid() as usize,
+					x: info.px(),
+					y: info.py(),
+					width: info.width(),
+					height: info.height(),
+				}
+			})
+			.collect();
+	}
+
+	fn resolve(&self, source: &VideoSource) -> Option<&SpriteRect> {
+		let set_name = self.set_name.as_deref();
+		let index = self
+			.database
+			.zip(set_name)
+			.and_then(|(database, set_name)| database.get_spr_set_by_name(set_name))
+			.and_then(|set| {
+				set.get_spr_by_id(source.id)
+					.or_else(|| set.get_spr_by_name(&source.name))
+			})
+			.map(|entry| entry.index() as usize)
+			.or_else(|| self.names.get(&source.name).copied())
+			.or_else(|| {
+				// Sprite sets leave out the SPR_<set>_ prefix the scenes use
+				let name = source.name.strip_prefix(set_name?)?.strip_prefix('_')?;
+				self.names.get(name).copied()
+			})?;
+		self.sprites.get(index)
+	}
+
+	// Layers are drawn flat, 3D layers lose their depth and the camera is ignored
+	pub fn render(&self, scene: &Scene, frame: f32) -> image::RgbaImage {
+		let mut canvas = Canvas {
+			width: scene.width as usize,
+			height: scene.height as usize,
+			pixels: vec![[0.0; 4]; scene.width as usize * scene.height as usize],
+		};
+
+		for layer in scene.evaluate(frame) {
+			if !layer.visible {
+				continue;
+			}
+			let Some(video) = scene.videos.get(layer.video) else {
+				continue;
+			};
+
+			let quad = (video.width as f32, video.height as f32);
+			let fill = match layer.source {
+				None => {
+					let [r, g, b] = video.color.map(|c| c as f32 / 255.0);
+					Fill::Solid([r, g, b, 1.0])
+				}
+				Some(source) => match self.resolve(&video.sources[source]) {
+					Some(sprite) => match self.textures.get(sprite.texture) {
+						Some(texture) => Fill::Sprite(texture, sprite),
+						None => continue,
+					},
+					None => continue,
+				},
+			};
+
+			canvas.draw(
+				&layer.matrix_2d(),
+				quad,
+				&fill,
+				layer.opacity.clamp(0.0, 1.0),
+				layer.blend_mode,
+			);
+		}
+
+		let rgba = canvas
+			.pixels
+			.iter()
+			.flat_map(|&[r, g, b, a]| {
+				let unmul = if a > 0.0 { 1.0 / a } else { 0.0 };
+				[r * unmul, g * unmul, b * unmul, a]
+					.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+			})
+			.collect();
+		image::RgbaImage::from_raw(scene.width, scene.height, rgba).unwrap()
+	}
+}
+
+enum Fill<'a> {
+	Solid([f32; 4]),
+	Sprite(&'a SpriteTexture, &'a SpriteRect),
+}
+
+impl Fill<'_> {
+	// u and v are in quad pixels, the sprite is stretched over the whole quad
+	fn sample(&self, u: f32, v: f32, quad: (f32, f32)) -> [f32; 4] {
+		let (texture, rect) = match self {
+			Fill::Solid(color) => return *color,
+			Fill::Sprite(texture, rect) => (texture, rect),
+		};
+		if texture.width == 0 || texture.height == 0 {
+			return [0.0; 4];
+		}
+
+		// Bilinear, kept inside the sprite so neighbours in the atlas don't bleed in
+		let x = (rect.x + u / quad.0 * rect.width - 0.5)
+			.clamp(rect.x, (rect.x + rect.width - 1.0).max(rect.x));
+		let y = (rect.y + v / quad.1 * rect.height - 0.5)
+			.clamp(rect.y, (rect.y + rect.height - 1.0).max(rect.y));
+		let (x0, y0) = (x.floor(), y.floor());
+		let (fx, fy) = (x - x0, y - y0);
+		let texel = |x: f32, y: f32| {
+			let x = (x as usize).min(texture.width - 1);
+			let y = (y as usize).min(texture.height - 1);
+			texture.pixels[y * texture.width + x]
+		};
+		let x1 = (x0 + 1.0).min(rect.x + rect.width - 1.0).max(x0);
+		let y1 = (y0 + 1.0).min(rect.y + rect.height - 1.0).max(y0);
+		let (p00, p10, p01, p11) = (texel(x0, y0), texel(x1, y0), texel(x0, y1), texel(x1, y1));
+
+		std::array::from_fn(|i| {
+			let top = p00[i] + (p10[i] - p00[i]) * fx;
+			let bottom = p01[i] + (p11[i] - p01[i]) * fx;
+			top + (bottom - top) * fy
+		})
+	}
+}
+
+struct Canvas {
+	width: usize,
+	height: usize,
+	// Premultiplied
+	pixels: Vec<[f32; 4]>,
+}
+
+impl Canvas {
+	fn draw(
+		&mut self,
+		matrix: &[[f32; 3]; 3],
+		quad: (f32, f32),
+		fill: &Fill,
+		opacity: f32,
+		mode: BlendMode,
+	) {
+		let [[a, b, tx], [c, d, ty], _] = *matrix;
+		let det = a * d - b * c;
+		if det.abs() < f32::EPSILON || quad.0 <= 0.0 || quad.1 <= 0.0 {
+			return;
+		}
+
+		let corners = [(0.0, 0.0), (quad.0, 0.0), (0.0, quad.1), (quad.0, quad.1)]
+			.map(|(u, v)| (a * u + b * v + tx, c * u + d * v + ty));
+		let min_x = corners.iter().map(|p| p.0).fold(f32::INFINITY, f32::min);
+		let max_x = corners
+			.iter()
+			.map(|p| p.0)
+			.fold(f32::NEG_INFINITY, f32::max);
+		let min_y = corners.iter().map(|p| p.1).fold(f32::INFINITY, f32::min);
+		let max_y = corners
+			.iter()
+			.map(|p| p.1)
+			.fold(f32::NEG_INFINITY, f32::max);
+		let x_range = (min_x.floor().max(0.0) as usize)
+			..(max_x.ceil().clamp(0.0, self.width as f32) as usize);
+		let y_range = (min_y.floor().max(0.0) as usize)
+			..(max_y.ceil().clamp(0.0, self.height as f32) as usize);
+
+		for y in y_range {
+			for x in x_range.clone() {
+				// Pixel centres are mapped back into the quad
+				let (px, py) = (x as f32 + 0.5 - tx, y as f32 + 0.5 - ty);
+				let u = (d * px - b * py) / det;
+				let v = (a * py - c * px) / det;
+				if !(0.0..quad.0).contains(&u) || !(0.0..quad.1).contains(&v) {
+					continue;
+				}
+
+				let src = fill.sample(u, v, quad).map(|c| c * opacity);
+				let dst = &mut self.pixels[y * self.width + x];
+				*dst = blend(src, *dst, mode);
+			}
+		}
+	}
+}
+
+// Both colours premultiplied, separable modes use the W3C compositing formula
+fn blend(src: [f32; 4], dst: [f32; 4], mode: BlendMode) -> [f32; 4] {
+	let (sa, da) = (src[3], dst[3]);
+	let mix: fn(f32, f32) -> f32 = match mode {
+		BlendMode::Add | BlendMode::LinearDodge => |s, d| (s + d).min(1.0),
+		BlendMode::Multiply => |s, d| s * d,
+		BlendMode::Screen => |s, d| s + d - s * d,
+		BlendMode::Overlay => |s, d| hard_light(d, s),
+		BlendMode::HardLight => hard_light,
+		BlendMode::Darken => f32::min,
+		BlendMode::Lighten => f32::max,
+		BlendMode::Difference | BlendMode::ClassicDifference => |s, d| (s - d).abs(),
+		BlendMode::Exclusion => |s, d| s + d - 2.0 * s * d,
+		BlendMode::Subtract => |s, d| (d - s).max(0.0),
+		_ => |s, _| s,
+	};
+
+	let mut out = [0.0; 4];
+	for i in 0..3 {
+		let s = if sa > 0.0 { src[i] / sa } else { 0.0 };
+		let d = if da > 0.0 { dst[i] / da } else { 0.0 };
+		out[i] = src[i] * (1.0 - da) + dst[i] * (1.0 - sa) + sa * da * mix(s, d);
+	}
+	out[3] = sa + da * (1.0 - sa);
+	out
+}
+
+fn hard_light(s: f32, d: f32) -> f32 {
+	if s <= 0.5 {
+		d * 2.0 * s
+	} else {
+		let s = 2.0 * s - 1.0;
+		s + d - s * d
+	}
+}
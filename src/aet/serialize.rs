@@ -0,0 +1,151 @@
+use super::*;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// In bit order
+const LAYER_FLAGS: [&str; 16] = [
+	"video_active",
+	"audio_active",
+	"effects_active",
+	"motion_blur",
+	"frame_blending",
+	"locked",
+	"shy",
+	"collapse",
+	"auto_orient_rotation",
+	"adjustment_layer",
+	"time_remapping",
+	"is_3d",
+	"look_at_camera",
+	"look_at_point_of_interest",
+	"solo",
+	"markers_locked",
+];
+
+// Written as the names of the flags that are set
+impl Serialize for LayerFlags {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let bits = self.into_bits();
+		serializer.collect_seq(
+			LAYER_FLAGS
+				.iter()
+				.enumerate()
+				.filter(|(bit, _)| bits & (1 << bit) != 0)
+				.map(|(_, name)| name),
+		)
+	}
+}
+
+impl<'de> Deserialize<'de> for LayerFlags {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let mut bits = 0u16;
+		for name in Vec::<String>::deserialize(deserializer)? {
+			// Older files have the misspelling from the field name
+			let flag = match name.as_str() {
+				"time_remappingg" => "time_remapping",
+				name => name,
+			};
+			let bit = LAYER_FLAGS
+				.iter()
+				.position(|known| *known == flag)
+				.ok_or_else(|| D::Error::unknown_variant(&name, &LAYER_FLAGS))?;
+			bits |= 1 << bit;
+		}
+		Ok(LayerFlags::from_bits(bits))
+	}
+}
+
+// A parent is written as its name, or as its index in the composition when other layers share
+// the name
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum Parent {
+	Name(String),
+	Index(usize),
+}
+
+#[derive(Serialize)]
+struct LayerRef<'a> {
+	#[serde(flatten)]
+	layer: &'a Layer,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	parent: Option<Parent>,
+}
+
+#[derive(Deserialize)]
+struct LayerOwned {
+	#[serde(flatten)]
+	layer: Layer,
+	#[serde(default)]
+	parent: Option<Parent>,
+}
+
+// Written as the list of its layers
+impl Serialize for Composition {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let parent = |parent: LayerId| {
+			let index = self.layers.iter().position(|layer| layer.id == parent)?;
+			let name = &self.layers[index].name;
+			if self
+				.layers
+				.iter()
+				.filter(|layer| layer.name == *name)
+				.count() == 1
+			{
+				Some(Parent::Name(name.clone()))
+			} else {
+				Some(Parent::Index(index))
+			}
+		};
+		serializer.collect_seq(self.layers.iter().map(|layer| LayerRef {
+			layer,
+			parent: layer.parent.and_then(parent),
+		}))
+	}
+}
+
+impl<'de> Deserialize<'de> for Composition {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let layers = Vec::<LayerOwned>::deserialize(deserializer)?;
+		let ids = layers
+			.iter()
+			.map(|owned| (owned.layer.id, owned.layer.name.clone()))
+			.collect::<Vec<_>>();
+		let resolve = |layer: &Layer, parent: Parent| match parent {
+			Parent::Index(index) => ids.get(index).map(|(id, _)| *id).ok_or_else(|| {
+				D::Error::custom(format!(
+					"layer {:?} has parent {index} but there are only {} layers",
+					layer.name,
+					ids.len()
+				))
+			}),
+			Parent::Name(name) => {
+				let mut matches = ids.iter().filter(|(_, other)| *other == name);
+				match (matches.next(), matches.next()) {
+					(Some((id, _)), None) => Ok(*id),
+					(None, _) => Err(D::Error::custom(format!(
+						"layer {:?} has parent {name:?} which isn't in the composition",
+						layer.name
+					))),
+					(Some(_), Some(_)) => Err(D::Error::custom(format!(
+						"layer {:?} has parent {name:?} but several layers have that name, give \
+						 its index instead",
+						layer.name
+					))),
+				}
+			}
+		};
+		let layers = layers
+			.into_iter()
+			.map(|owned| {
+				let mut layer = owned.layer;
+				layer.parent = owned
+					.parent
+					.map(|parent| resolve(&layer, parent))
+					.transpose()?;
+				Ok(layer)
+			})
+			.collect::<Result<_, D::Error>>()?;
+		Ok(Self { layers })
+	}
+}
@@ -0,0 +1,142 @@
+use super::*;
+use crate::database::sprite;
+use crate::spr;
+
+// Missing is an empty tuple variant since python enums with fields can't have unit variants
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "pyo3", pyclass(eq))]
+pub enum Issue {
+	// Neither the id nor the name is in the database
+	Missing(),
+	// The sprite belongs to another set than the one the aet set draws from
+	OtherSet { set: String },
+	// The source points at a texture's entry rather than a sprite's
+	Texture(),
+	// The name is in the database under another id, fix_source_ids rewrites it
+	StaleId { expected: u32 },
+	// The id belongs to a sprite with another name
+	WrongName { expected: String },
+	// The database knows the sprite but the sprite set has nothing at its index
+	NotInSet { index: u16 },
+	// The sprite's size in the sprite set differs from the video's
+	WrongSize { width: f32, height: f32 },
+}
+
+// A video source that doesn't match the sprites, a source can have several
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "pyo3", pyclass(get_all))]
+pub struct SourceIssue {
+	// Indices into Set::scenes, Scene::videos and Video::sources
+	pub scene: usize,
+	pub video: usize,
+	pub source: usize,
+	pub name: String,
+	pub id: u32,
+	pub issue: Issue,
+}
+
+impl Set {
+	// Sources are looked up in the sprite set with sprite_set_id, the aet database's
+	// sprite_set_id for this set. Sizes are only checked when that set's sprites are given
+	pub fn validate_sources(
+		&self,
+		database: &sprite::Database,
+		sprite_set_id: u32,
+		sprites: Option<&spr::Set>,
+	) -> Vec<SourceIssue> {
+		let set = database.get_spr_set_by_id(sprite_set_id);
+		let mut issues = Vec::new();
+		for (scene_index, scene) in self.scenes.iter().enumerate() {
+			for (video_index, video) in scene.videos.iter().enumerate() {
+				for (source_index, source) in video.sources.iter().enumerate() {
+					let mut report = |issue| {
+						issues.push(SourceIssue {
+							scene: scene_index,
+							video: video_index,
+							source: source_index,
+							name: source.name.clone(),
+							id: source.id,
+							issue,
+						})
+					};
+
+					let by_name = set
+						.as_ref()
+						.and_then(|set| set.get_spr_by_name(&source.name));
+					let by_id = set.as_ref().and_then(|set| set.get_spr_by_id(source.id));
+					let entry = match (by_name, by_id) {
+						(None, None) => {
+							// Say where else the database has it
+							let other = database
+								.get_spr_by_name(&source.name)
+								.or_else(|| database.get_spr_by_id(source.id));
+							report(match other {
+								None => Issue::Missing(),
+								Some(entry) if entry.texture() => Issue::Texture(),
+								Some(entry) => Issue::OtherSet {
+									set: entry.set().map(|set| set.name()).unwrap_or_default(),
+								},
+							});
+							continue;
+						}
+						(Some(entry), _) => {
+							if entry.id() != source.id {
+								report(Issue::StaleId {
+									expected: entry.id(),
+								});
+							}
+							entry
+						}
+						(None, Some(entry)) => {
+							report(Issue::WrongName {
+								expected: entry.name(),
+							});
+							entry
+						}
+					};
+
+					let Some(sprites) = sprites else {
+						continue;
+					};
+					let index = entry.index();
+					let Some((_, info)) = sprites.sprites().nth(index as usize) else {
+						report(Issue::NotInSet { index });
+						continue;
+					};
+					let size = (info.width().round(), info.height().round());
+					if size != (video.width as f32, video.height as f32) {
+						report(Issue::WrongSize {
+							width: info.width(),
+							height: info.height(),
+						});
+					}
+				}
+			}
+		}
+		issues
+	}
+
+	// Sets each source's id to the one the sprite set with sprite_set_id has for its name, for
+	// after the database was renumbered. Sources the set doesn't know by name are left alone.
+	// Returns how many changed
+	pub fn fix_source_ids(&mut self, database: &sprite::Database, sprite_set_id: u32) -> usize {
+		let Some(set) = database.get_spr_set_by_id(sprite_set_id) else {
+			return 0;
+		};
+		let mut fixed = 0;
+		let sources = self
+			.scenes
+			.iter_mut()
+			.flat_map(|scene| &mut scene.videos)
+			.flat_map(|video| &mut video.sources);
+		for source in sources {
+			if let Some(entry) = set.get_spr_by_name(&source.name)
+				&& entry.id() != source.id
+			{
+				source.id = entry.id();
+				fixed += 1;
+			}
+		}
+		fixed
+	}
+}
@@ -0,0 +1,126 @@
+use pyo3::exceptions::PyBufferError;
+use pyo3::ffi;
+use pyo3::prelude::*;
+use std::any::Any;
+use std::ffi::{c_int, c_void};
+
+// Freed again in release_view
+struct Internal {
+	dims: Box<[isize]>,
+	// Held until the view is released, mutable owners pass a borrow of themselves so they can't
+	// be changed under the view
+	_guard: Option<Box<dyn Any>>,
+}
+
+// Read only buffer over memory owned by `owner`
+// The view is C contiguous with the given shape, so numpy.asarray on rgba data gives a
+// (height, width, 4) array without copying
+pub(crate) unsafe fn fill_view(
+	view: *mut ffi::Py_buffer,
+	flags: c_int,
+	data: &[u8],
+	shape: &[usize],
+	owner: Bound<'_, PyAny>,
+	guard: Option<Box<dyn Any>>,
+) -> PyResult<()> {
+	if view.is_null() {
+		return Err(PyBufferError::new_err("View is null"));
+	}
+
+	if (flags & ffi::PyBUF_WRITABLE) == ffi::PyBUF_WRITABLE {
+		return Err(PyBufferError::new_err("Object is not writable"));
+	}
+
+	let len = [data.len()];
+	let shape = if shape.iter().product::<usize>() == data.len() {
+		shape
+	} else {
+		&len
+	};
+
+	// Shape followed by strides
+	let ndim = shape.len();
+	let mut dims = vec![0isize; ndim * 2];
+	let mut stride = 1isize;
+	for i in (0..ndim).rev() {
+		dims[i] = shape[i] as isize;
+		dims[ndim + i] = stride;
+		stride *= shape[i] as isize;
+	}
+	let mut internal = Box::new(Internal {
+		dims: dims.into_boxed_slice(),
+		_guard: guard,
+	});
+	let dims = internal.dims.as_mut_ptr();
+
+	unsafe {
+		(*view).obj = owner.into_ptr();
+		(*view).buf = data.as_ptr() as *mut c_void;
+		(*view).len = data.len() as isize;
+		(*view).readonly = 1;
+		(*view).itemsize = 1;
+		(*view).format = if (flags & ffi::PyBUF_FORMAT) == ffi::PyBUF_FORMAT {
+			c"B".as_ptr() as *mut _
+		} else {
+			std::ptr::null_mut()
+		};
+		(*view).ndim = ndim as c_int;
+		(*view).shape = if (flags & ffi::PyBUF_ND) == ffi::PyBUF_ND {
+			dims
+		} else {
+			std::ptr::null_mut()
+		};
+		(*view).strides = if (flags & ffi::PyBUF_STRIDES) == ffi::PyBUF_STRIDES {
+			dims.add(ndim)
+		} else {
+			std::ptr::null_mut()
+		};
+		(*view).suboffsets = std::ptr::null_mut();
+		(*view).internal = Box::into_raw(internal) as *mut c_void;
+	}
+
+	Ok(())
+}
+
+pub(crate) unsafe fn release_view(view: *mut ffi::Py_buffer) {
+	unsafe { drop(Box::from_raw((*view).internal as *mut Internal)) };
+}
+
+// Owned bytes handed to python without another copy
+#[pyclass(frozen)]
+pub struct Buffer {
+	data: Vec<u8>,
+	shape: Vec<usize>,
+}
+
+impl Buffer {
+	pub(crate) fn new(data: Vec<u8>, shape: Vec<usize>) -> Self {
+		Self { data, shape }
+	}
+}
+
+#[pymethods]
+impl Buffer {
+	unsafe fn __getbuffer__(
+		slf: Bound<'_, Self>,
+		view: *mut ffi::Py_buffer,
+		flags: c_int,
+	) -> PyResult<()> {
+		let owner = slf.clone().into_any();
+		let buffer = slf.get();
+		unsafe { fill_view(view, flags, &buffer.data, &buffer.shape, owner, None) }
+	}
+
+	unsafe fn __releasebuffer__(&self, view: *mut ffi::Py_buffer) {
+		unsafe { release_view(view) };
+	}
+
+	fn __len__(&self) -> usize {
+		self.data.len()
+	}
+
+	#[getter]
+	fn shape(&self) -> Vec<usize> {
+		self.shape.clone()
+	}
+}
@@ -1 +1,760 @@
+use std::ffi::*;
+use std::marker::PhantomData;
 
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+
+#[cfg(feature = "pyo3")]
+#[cfg_attr(feature = "pyo3", pymodule(name = "aet", submodule))]
+pub(crate) mod aet_module {
+	#[pymodule_export]
+	use super::Database;
+	#[pymodule_export]
+	use super::PyScene;
+	#[pymodule_export]
+	use super::PySet;
+	#[pymodule_export]
+	use super::file::file_module;
+}
+
+pub mod file {
+	use std::ffi::*;
+	use std::marker::PhantomData;
+
+	#[cfg(feature = "pyo3")]
+	use pyo3::prelude::*;
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pymodule(name = "file", submodule))]
+	pub(crate) mod file_module {
+		#[pymodule_export]
+		use super::Database;
+		#[pymodule_export]
+		use super::Scene;
+		#[pymodule_export]
+		use super::Set;
+	}
+
+	#[cfg_attr(feature = "pyo3", pyclass)]
+	pub struct Scene {
+		ptr: *mut c_void,
+	}
+
+	unsafe impl Send for Scene {}
+	unsafe impl Sync for Scene {}
+
+	#[cfg_eval]
+	#[cfg_attr(feature = "pyo3", pymethods)]
+	impl Scene {
+		#[cfg_attr(feature = "pyo3", new)]
+		pub fn new() -> Self {
+			Self {
+				ptr: unsafe { kkdlib_aet_db_aet_file_new() },
+			}
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn id(&self) -> u32 {
+			unsafe { kkdlib_aet_db_aet_file_get_id(self.ptr) }
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_id(&mut self, id: u32) {
+			unsafe { kkdlib_aet_db_aet_file_set_id(self.ptr, id) };
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn name(&self) -> String {
+			let name = unsafe { kkdlib_aet_db_aet_file_get_name(self.ptr) };
+			let name = unsafe { CStr::from_ptr(name) };
+			name.to_string_lossy().to_string()
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_name(&mut self, name: &str) {
+			let Ok(name) = CString::new(name) else {
+				return;
+			};
+			unsafe { kkdlib_aet_db_aet_file_set_name(self.ptr, name.as_ptr()) };
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn index(&self) -> u16 {
+			unsafe { kkdlib_aet_db_aet_file_get_index(self.ptr) }
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_index(&mut self, index: u16) {
+			unsafe { kkdlib_aet_db_aet_file_set_index(self.ptr, index) };
+		}
+	}
+
+	impl Clone for Scene {
+		fn clone(&self) -> Self {
+			let mut scene = Self::new();
+			scene.set_id(self.id());
+			scene.set_name(&self.name());
+			scene.set_index(self.index());
+			scene
+		}
+	}
+
+	impl Drop for Scene {
+		fn drop(&mut self) {
+			unsafe { kkdlib_aet_db_aet_file_delete(self.ptr) };
+		}
+	}
+
+	pub struct SceneRef<'a> {
+		_ptr: *mut c_void,
+		phantom: PhantomData<&'a Scene>,
+	}
+
+	unsafe impl Send for SceneRef<'_> {}
+	unsafe impl Sync for SceneRef<'_> {}
+
+	impl SceneRef<'_> {
+		pub fn id(&self) -> u32 {
+			Scene::id(unsafe { std::mem::transmute(self) })
+		}
+
+		pub fn name(&self) -> String {
+			Scene::name(unsafe { std::mem::transmute(self) })
+		}
+
+		pub fn index(&self) -> u16 {
+			Scene::index(unsafe { std::mem::transmute(self) })
+		}
+
+		pub fn cloned(&self) -> Scene {
+			Scene::clone(unsafe { std::mem::transmute(self) })
+		}
+	}
+
+	pub struct SceneIter<'a> {
+		ptr: *mut c_void,
+		index: usize,
+		len: usize,
+		phantom: PhantomData<&'a Set>,
+	}
+
+	impl<'a> Iterator for SceneIter<'a> {
+		type Item = SceneRef<'a>;
+
+		fn next(&mut self) -> Option<Self::Item> {
+			if self.index == self.len {
+				return None;
+			}
+
+			let scene = unsafe { kkdlib_aet_db_aet_set_file_get_aet(self.ptr, self.index) };
+			if scene.is_null() {
+				return None;
+			}
+
+			self.index += 1;
+			Some(SceneRef {
+				_ptr: scene,
+				phantom: PhantomData,
+			})
+		}
+
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			(self.len, Some(self.len))
+		}
+	}
+
+	#[cfg_attr(feature = "pyo3", pyclass)]
+	pub struct Set {
+		ptr: *mut c_void,
+	}
+
+	unsafe impl Send for Set {}
+	unsafe impl Sync for Set {}
+
+	#[cfg_eval]
+	#[cfg_attr(feature = "pyo3", pymethods)]
+	impl Set {
+		#[cfg_attr(feature = "pyo3", new)]
+		pub fn new() -> Self {
+			Self {
+				ptr: unsafe { kkdlib_aet_db_aet_set_file_new() },
+			}
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn id(&self) -> u32 {
+			unsafe { kkdlib_aet_db_aet_set_file_get_id(self.ptr) }
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_id(&mut self, id: u32) {
+			unsafe { kkdlib_aet_db_aet_set_file_set_id(self.ptr, id) };
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn name(&self) -> String {
+			let name = unsafe { kkdlib_aet_db_aet_set_file_get_name(self.ptr) };
+			let name = unsafe { CStr::from_ptr(name) };
+			name.to_string_lossy().to_string()
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_name(&mut self, name: &str) {
+			let Ok(name) = CString::new(name) else {
+				return;
+			};
+			unsafe { kkdlib_aet_db_aet_set_file_set_name(self.ptr, name.as_ptr()) };
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn file_name(&self) -> String {
+			let name = unsafe { kkdlib_aet_db_aet_set_file_get_file_name(self.ptr) };
+			let name = unsafe { CStr::from_ptr(name) };
+			name.to_string_lossy().to_string()
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_file_name(&mut self, file_name: &str) {
+			let Ok(file_name) = CString::new(file_name) else {
+				return;
+			};
+			unsafe { kkdlib_aet_db_aet_set_file_set_file_name(self.ptr, file_name.as_ptr()) };
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn sprite_set_id(&self) -> u32 {
+			unsafe { kkdlib_aet_db_aet_set_file_get_sprite_set_id(self.ptr) }
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_sprite_set_id(&mut self, sprite_set_id: u32) {
+			unsafe { kkdlib_aet_db_aet_set_file_set_sprite_set_id(self.ptr, sprite_set_id) };
+		}
+
+		pub fn add_scene(&self, scene: &Scene) {
+			unsafe {
+				kkdlib_aet_db_aet_set_file_add_aet(self.ptr, scene.ptr);
+			}
+		}
+
+		// Copies, edits have to be added back with add_scene
+		#[cfg(feature = "pyo3")]
+		#[cfg_attr(feature = "pyo3", getter(scenes))]
+		pub fn py_scenes(&self) -> Vec<Scene> {
+			self.scenes().map(|scene| scene.cloned()).collect()
+		}
+	}
+
+	impl<'a> Set {
+		pub fn scenes(&'a self) -> SceneIter<'a> {
+			SceneIter {
+				ptr: self.ptr,
+				index: 0,
+				len: unsafe { kkdlib_aet_db_aet_set_file_get_aet_size(self.ptr) },
+				phantom: PhantomData,
+			}
+		}
+	}
+
+	impl Clone for Set {
+		fn clone(&self) -> Self {
+			let mut set = Self::new();
+			set.set_id(self.id());
+			set.set_name(&self.name());
+			set.set_file_name(&self.file_name());
+			set.set_sprite_set_id(self.sprite_set_id());
+			for scene in self.scenes() {
+				set.add_scene(&scene.cloned());
+			}
+			set
+		}
+	}
+
+	impl Drop for Set {
+		fn drop(&mut self) {
+			unsafe { kkdlib_aet_db_aet_set_file_delete(self.ptr) };
+		}
+	}
+
+	pub struct SetRef<'a> {
+		_ptr: *mut c_void,
+		phantom: PhantomData<&'a Set>,
+	}
+
+	unsafe impl Send for SetRef<'_> {}
+	unsafe impl Sync for SetRef<'_> {}
+
+	impl<'a> SetRef<'a> {
+		pub fn id(&self) -> u32 {
+			Set::id(unsafe { std::mem::transmute(self) })
+		}
+
+		pub fn name(&self) -> String {
+			Set::name(unsafe { std::mem::transmute(self) })
+		}
+
+		pub fn file_name(&self) -> String {
+			Set::file_name(unsafe { std::mem::transmute(self) })
+		}
+
+		pub fn sprite_set_id(&self) -> u32 {
+			Set::sprite_set_id(unsafe { std::mem::transmute(self) })
+		}
+
+		pub fn scenes(&'a self) -> SceneIter<'a> {
+			Set::scenes(unsafe { std::mem::transmute(self) })
+		}
+
+		pub fn cloned(&self) -> Set {
+			Set::clone(unsafe { std::mem::transmute(self) })
+		}
+	}
+
+	pub struct SetIter<'a> {
+		ptr: *mut c_void,
+		index: usize,
+		len: usize,
+		phantom: PhantomData<&'a Database>,
+	}
+
+	impl<'a> Iterator for SetIter<'a> {
+		type Item = SetRef<'a>;
+
+		fn next(&mut self) -> Option<Self::Item> {
+			if self.index == self.len {
+				return None;
+			}
+
+			let set = unsafe { kkdlib_aet_database_file_get_aet_set(self.ptr, self.index) };
+			if set.is_null() {
+				return None;
+			}
+
+			self.index += 1;
+			Some(SetRef {
+				_ptr: set,
+				phantom: PhantomData,
+			})
+		}
+
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			(self.len, Some(self.len))
+		}
+	}
+
+	#[cfg_attr(feature = "pyo3", pyclass)]
+	pub struct Database {
+		pub(crate) ptr: *mut c_void,
+	}
+
+	unsafe impl Send for Database {}
+	unsafe impl Sync for Database {}
+
+	#[cfg_eval]
+	#[cfg_attr(feature = "pyo3", pymethods)]
+	impl Database {
+		#[cfg_attr(feature = "pyo3", new)]
+		pub fn new() -> Self {
+			Self {
+				ptr: unsafe { kkdlib_aet_database_file_new() },
+			}
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn ready(&self) -> bool {
+			unsafe { kkdlib_aet_database_file_get_ready(self.ptr) }
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_ready(&mut self, ready: bool) {
+			unsafe { kkdlib_aet_database_file_set_ready(self.ptr, ready) };
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn modern(&self) -> bool {
+			unsafe { kkdlib_aet_database_file_get_modern(self.ptr) }
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_modern(&mut self, modern: bool) {
+			unsafe { kkdlib_aet_database_file_set_modern(self.ptr, modern) };
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn big_endian(&self) -> bool {
+			unsafe { kkdlib_aet_database_file_get_big_endian(self.ptr) }
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_big_endian(&mut self, big_endian: bool) {
+			unsafe { kkdlib_aet_database_file_set_big_endian(self.ptr, big_endian) };
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn is_x(&self) -> bool {
+			unsafe { kkdlib_aet_database_file_get_is_x(self.ptr) }
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_is_x(&mut self, is_x: bool) {
+			unsafe { kkdlib_aet_database_file_set_is_x(self.ptr, is_x) };
+		}
+
+		pub fn add_set(&mut self, set: &Set) {
+			unsafe { kkdlib_aet_database_file_add_aet_set(self.ptr, set.ptr) };
+		}
+
+		#[cfg_attr(feature = "pyo3", staticmethod)]
+		pub fn from_buf(data: &[u8], modern: bool) -> Self {
+			let database = Self::new();
+			unsafe {
+				kkdlib_aet_database_file_read_data(
+					database.ptr,
+					data.as_ptr() as *const c_void,
+					data.len(),
+					modern,
+				)
+			};
+			database
+		}
+
+		pub fn to_buf(&self) -> Option<Vec<u8>> {
+			let mut ptr = std::ptr::null_mut();
+			let mut size = 0usize;
+			unsafe {
+				kkdlib_aet_database_file_write_data(self.ptr, &mut ptr, &mut size);
+			}
+
+			if ptr.is_null() || size == 0 {
+				return None;
+			}
+
+			let slice = std::ptr::slice_from_raw_parts(ptr as *const u8, size);
+			let slice = unsafe { slice.as_ref()? };
+
+			let mut vec = Vec::with_capacity(size);
+			vec.extend_from_slice(slice);
+
+			unsafe { kkdlib_aet_database_file_delete_packed_data(ptr) };
+
+			Some(vec)
+		}
+
+		// Copies, edits have to be added back with add_set
+		#[cfg(feature = "pyo3")]
+		#[cfg_attr(feature = "pyo3", getter(sets))]
+		pub fn py_sets(&self) -> Vec<Set> {
+			self.sets().map(|set| set.cloned()).collect()
+		}
+	}
+
+	impl<'a> Database {
+		pub fn sets(&'a self) -> SetIter<'a> {
+			SetIter {
+				ptr: self.ptr,
+				index: 0,
+				len: unsafe { kkdlib_aet_database_file_get_aet_set_size(self.ptr) },
+				phantom: PhantomData,
+			}
+		}
+	}
+
+	impl Drop for Database {
+		fn drop(&mut self) {
+			unsafe { kkdlib_aet_database_file_delete(self.ptr) };
+		}
+	}
+
+	unsafe extern "C" {
+		fn kkdlib_aet_db_aet_file_new() -> *mut c_void;
+		fn kkdlib_aet_db_aet_file_get_id(aet_file: *mut c_void) -> u32;
+		fn kkdlib_aet_db_aet_file_set_id(aet_file: *mut c_void, id: u32);
+		fn kkdlib_aet_db_aet_file_get_name(aet_file: *mut c_void) -> *const c_char;
+		fn kkdlib_aet_db_aet_file_set_name(aet_file: *mut c_void, name: *const c_char);
+		fn kkdlib_aet_db_aet_file_get_index(aet_file: *mut c_void) -> u16;
+		fn kkdlib_aet_db_aet_file_set_index(aet_file: *mut c_void, index: u16);
+		fn kkdlib_aet_db_aet_file_delete(aet_file: *mut c_void);
+
+		fn kkdlib_aet_db_aet_set_file_new() -> *mut c_void;
+		fn kkdlib_aet_db_aet_set_file_get_id(set_file: *mut c_void) -> u32;
+		fn kkdlib_aet_db_aet_set_file_set_id(set_file: *mut c_void, id: u32);
+		fn kkdlib_aet_db_aet_set_file_get_name(set_file: *mut c_void) -> *const c_char;
+		fn kkdlib_aet_db_aet_set_file_set_name(set_file: *mut c_void, name: *const c_char);
+		fn kkdlib_aet_db_aet_set_file_get_file_name(set_file: *mut c_void) -> *const c_char;
+		fn kkdlib_aet_db_aet_set_file_set_file_name(set_file: *mut c_void, name: *const c_char);
+		fn kkdlib_aet_db_aet_set_file_get_sprite_set_id(set_file: *mut c_void) -> u32;
+		fn kkdlib_aet_db_aet_set_file_set_sprite_set_id(set_file: *mut c_void, sprite_set_id: u32);
+		fn kkdlib_aet_db_aet_set_file_get_aet_size(set_file: *mut c_void) -> usize;
+		fn kkdlib_aet_db_aet_set_file_get_aet(set_file: *mut c_void, index: usize) -> *mut c_void;
+		fn kkdlib_aet_db_aet_set_file_add_aet(set_file: *mut c_void, aet_file: *mut c_void);
+		fn kkdlib_aet_db_aet_set_file_delete(set_file: *mut c_void);
+
+		fn kkdlib_aet_database_file_new() -> *mut c_void;
+		fn kkdlib_aet_database_file_get_ready(database_file: *mut c_void) -> bool;
+		fn kkdlib_aet_database_file_set_ready(database_file: *mut c_void, ready: bool);
+		fn kkdlib_aet_database_file_get_modern(database_file: *mut c_void) -> bool;
+		fn kkdlib_aet_database_file_set_modern(database_file: *mut c_void, modern: bool);
+		fn kkdlib_aet_database_file_get_big_endian(database_file: *mut c_void) -> bool;
+		fn kkdlib_aet_database_file_set_big_endian(database_file: *mut c_void, big_endian: bool);
+		fn kkdlib_aet_database_file_get_is_x(database_file: *mut c_void) -> bool;
+		fn kkdlib_aet_database_file_set_is_x(database_file: *mut c_void, is_x: bool);
+		fn kkdlib_aet_database_file_get_aet_set_size(database_file: *mut c_void) -> usize;
+		fn kkdlib_aet_database_file_get_aet_set(
+			database_file: *mut c_void,
+			index: usize,
+		) -> *mut c_void;
+		fn kkdlib_aet_database_file_add_aet_set(database_file: *mut c_void, set_file: *mut c_void);
+		fn kkdlib_aet_database_file_read_data(
+			database_file: *mut c_void,
+			data: *const c_void,
+			size: usize,
+			modern: bool,
+		);
+		fn kkdlib_aet_database_file_write_data(
+			database_file: *mut c_void,
+			data: *mut *mut c_void,
+			size: *mut usize,
+		);
+		fn kkdlib_aet_database_file_delete_packed_data(data: *mut c_void);
+		fn kkdlib_aet_database_file_delete(database_file: *mut c_void);
+	}
+}
+
+pub struct SceneRef<'a> {
+	ptr: *const c_void,
+	phantom: PhantomData<&'a Database>,
+}
+
+unsafe impl Send for SceneRef<'_> {}
+unsafe impl Sync for SceneRef<'_> {}
+
+impl SceneRef<'_> {
+	pub fn id(&self) -> u32 {
+		unsafe { kkdlib_aet_db_aet_get_id(self.ptr) }
+	}
+
+	pub fn name(&self) -> String {
+		let ptr = unsafe { kkdlib_aet_db_aet_get_name(self.ptr) };
+		let cstr = unsafe { CStr::from_ptr(ptr) };
+		cstr.to_string_lossy().to_string()
+	}
+
+	pub fn index(&self) -> u16 {
+		unsafe { kkdlib_aet_db_aet_get_index(self.ptr) }
+	}
+
+	pub fn set_index(&self) -> u16 {
+		unsafe { kkdlib_aet_db_aet_get_set_index(self.ptr) }
+	}
+}
+
+pub struct SetRef<'a> {
+	ptr: *const c_void,
+	phantom: PhantomData<&'a Database>,
+}
+
+unsafe impl Send for SetRef<'_> {}
+unsafe impl Sync for SetRef<'_> {}
+
+impl SetRef<'_> {
+	pub fn id(&self) -> u32 {
+		unsafe { kkdlib_aet_db_aet_set_get_id(self.ptr) }
+	}
+
+	pub fn name(&self) -> String {
+		let ptr = unsafe { kkdlib_aet_db_aet_set_get_name(self.ptr) };
+		let cstr = unsafe { CStr::from_ptr(ptr) };
+		cstr.to_string_lossy().to_string()
+	}
+
+	pub fn file_name(&self) -> String {
+		let ptr = unsafe { kkdlib_aet_db_aet_set_get_file_name(self.ptr) };
+		let cstr = unsafe { CStr::from_ptr(ptr) };
+		cstr.to_string_lossy().to_string()
+	}
+
+	pub fn index(&self) -> u32 {
+		unsafe { kkdlib_aet_db_aet_set_get_index(self.ptr) }
+	}
+
+	pub fn sprite_set_id(&self) -> u32 {
+		unsafe { kkdlib_aet_db_aet_set_get_sprite_set_id(self.ptr) }
+	}
+}
+
+#[cfg_attr(feature = "pyo3", pyclass)]
+pub struct Database {
+	ptr: *mut c_void,
+}
+
+unsafe impl Send for Database {}
+unsafe impl Sync for Database {}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl Database {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new() -> Self {
+		Self {
+			ptr: unsafe { kkdlib_aet_database_new() },
+		}
+	}
+
+	pub fn add_file(&mut self, file: &file::Database) {
+		unsafe { kkdlib_aet_database_add_file(self.ptr, file.ptr) };
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "get_aet_set_by_name"))]
+	pub fn py_get_aet_set_by_name(&self, name: &str) -> Option<PySet> {
+		self.get_aet_set_by_name(name).map(PySet::from)
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "get_aet_set_by_id"))]
+	pub fn py_get_aet_set_by_id(&self, id: u32) -> Option<PySet> {
+		self.get_aet_set_by_id(id).map(PySet::from)
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "get_aet_by_name"))]
+	pub fn py_get_aet_by_name(&self, name: &str) -> Option<PyScene> {
+		self.get_aet_by_name(name).map(PyScene::from)
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "get_aet_by_id"))]
+	pub fn py_get_aet_by_id(&self, id: u32) -> Option<PyScene> {
+		self.get_aet_by_id(id).map(PyScene::from)
+	}
+}
+
+impl<'a> Database {
+	pub fn get_aet_set_by_name(&'a self, name: &str) -> Option<SetRef<'a>> {
+		let cstring = CString::new(name).ok()?;
+		let ptr = unsafe { kkdlib_aet_database_get_aet_set_by_name(self.ptr, cstring.as_ptr()) };
+		if ptr.is_null() || unsafe { kkdlib_aet_db_aet_set_get_id(ptr) } == u32::MAX {
+			None
+		} else {
+			Some(SetRef {
+				ptr,
+				phantom: PhantomData,
+			})
+		}
+	}
+
+	pub fn get_aet_set_by_id(&'a self, id: u32) -> Option<SetRef<'a>> {
+		let ptr = unsafe { kkdlib_aet_database_get_aet_set_by_id(self.ptr, id) };
+		if ptr.is_null() || unsafe { kkdlib_aet_db_aet_set_get_id(ptr) } == u32::MAX {
+			None
+		} else {
+			Some(SetRef {
+				ptr,
+				phantom: PhantomData,
+			})
+		}
+	}
+
+	pub fn get_aet_by_name(&'a self, name: &str) -> Option<SceneRef<'a>> {
+		let cstring = CString::new(name).ok()?;
+		let ptr = unsafe { kkdlib_aet_database_get_aet_by_name(self.ptr, cstring.as_ptr()) };
+		if ptr.is_null() || unsafe { kkdlib_aet_db_aet_get_id(ptr) } == u32::MAX {
+			None
+		} else {
+			Some(SceneRef {
+				ptr,
+				phantom: PhantomData,
+			})
+		}
+	}
+
+	pub fn get_aet_by_id(&'a self, id: u32) -> Option<SceneRef<'a>> {
+		let ptr = unsafe { kkdlib_aet_database_get_aet_by_id(self.ptr, id) };
+		if ptr.is_null() || unsafe { kkdlib_aet_db_aet_get_id(ptr) } == u32::MAX {
+			None
+		} else {
+			Some(SceneRef {
+				ptr,
+				phantom: PhantomData,
+			})
+		}
+	}
+}
+
+impl Drop for Database {
+	fn drop(&mut self) {
+		unsafe { kkdlib_aet_database_delete(self.ptr) };
+	}
+}
+
+// Lookups borrow from the database, python gets a snapshot instead
+#[cfg(feature = "pyo3")]
+#[cfg_attr(feature = "pyo3", pyclass(name = "Scene", get_all))]
+pub struct PyScene {
+	pub id: u32,
+	pub name: String,
+	pub index: u16,
+	pub set_index: u16,
+}
+
+#[cfg(feature = "pyo3")]
+impl From<SceneRef<'_>> for PyScene {
+	fn from(scene: SceneRef<'_>) -> Self {
+		Self {
+			id: scene.id(),
+			name: scene.name(),
+			index: scene.index(),
+			set_index: scene.set_index(),
+		}
+	}
+}
+
+#[cfg(feature = "pyo3")]
+#[cfg_attr(feature = "pyo3", pyclass(name = "Set", get_all))]
+pub struct PySet {
+	pub id: u32,
+	pub name: String,
+	pub file_name: String,
+	pub index: u32,
+	pub sprite_set_id: u32,
+}
+
+#[cfg(feature = "pyo3")]
+impl From<SetRef<'_>> for PySet {
+	fn from(set: SetRef<'_>) -> Self {
+		Self {
+			id: set.id(),
+			name: set.name(),
+			file_name: set.file_name(),
+			index: set.index(),
+			sprite_set_id: set.sprite_set_id(),
+		}
+	}
+}
+
+unsafe extern "C" {
+	fn kkdlib_aet_db_aet_get_id(aet: *const c_void) -> u32;
+	fn kkdlib_aet_db_aet_get_name(aet: *const c_void) -> *const c_char;
+	fn kkdlib_aet_db_aet_get_index(aet: *const c_void) -> u16;
+	fn kkdlib_aet_db_aet_get_set_index(aet: *const c_void) -> u16;
+
+	fn kkdlib_aet_db_aet_set_get_id(set: *const c_void) -> u32;
+	fn kkdlib_aet_db_aet_set_get_name(set: *const c_void) -> *const c_char;
+	fn kkdlib_aet_db_aet_set_get_file_name(set: *const c_void) -> *const c_char;
+	fn kkdlib_aet_db_aet_set_get_index(set: *const c_void) -> u32;
+	fn kkdlib_aet_db_aet_set_get_sprite_set_id(set: *const c_void) -> u32;
+
+	fn kkdlib_aet_database_new() -> *mut c_void;
+	fn kkdlib_aet_database_add_file(database: *mut c_void, file: *mut c_void);
+	fn kkdlib_aet_database_get_aet_set_by_name(
+		database: *mut c_void,
+		name: *const c_char,
+	) -> *const c_void;
+	fn kkdlib_aet_database_get_aet_set_by_id(database: *mut c_void, set_id: u32) -> *const c_void;
+	fn kkdlib_aet_database_get_aet_by_name(
+		database: *mut c_void,
+		name: *const c_char,
+	) -> *const c_void;
+	fn kkdlib_aet_database_get_aet_by_id(database: *mut c_void, id: u32) -> *const c_void;
+	fn kkdlib_aet_database_delete(database: *mut c_void);
+}
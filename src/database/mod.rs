@@ -1,2 +1,17 @@
 pub mod aet;
+pub mod pv;
 pub mod sprite;
+
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+
+#[cfg(feature = "pyo3")]
+#[cfg_attr(feature = "pyo3", pymodule(name = "database"))]
+pub(crate) mod database_module {
+	#[pymodule_export]
+	use super::aet::aet_module;
+	#[pymodule_export]
+	use super::pv::pv_module;
+	#[pymodule_export]
+	use super::sprite::sprite_module;
+}
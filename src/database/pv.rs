@@ -0,0 +1,840 @@
+use crate::database::{aet, sprite};
+use crate::key_val::{KeyValError, KeyValNode, KeyValWriter, WriterScope};
+use std::collections::BTreeMap;
+
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+
+#[cfg(feature = "pyo3")]
+#[cfg_attr(feature = "pyo3", pymodule(name = "pv", submodule))]
+pub(crate) mod pv_module {
+	#[pymodule_export]
+	use super::Chart;
+	#[pymodule_export]
+	use super::Database;
+	#[pymodule_export]
+	use super::Difficulty;
+	#[pymodule_export]
+	use super::Issue;
+	#[pymodule_export]
+	use super::Performer;
+	#[pymodule_export]
+	use super::Pv;
+	#[pymodule_export]
+	use super::PvIssue;
+	#[pymodule_export]
+	use super::Sabi;
+	#[pymodule_export]
+	use super::SongInfo;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "pyo3", pyclass(eq, hash, frozen))]
+pub enum Difficulty {
+	Easy,
+	Normal,
+	Hard,
+	Extreme,
+	Encore,
+}
+
+impl Difficulty {
+	pub const ALL: [Self; 5] = [
+		Self::Easy,
+		Self::Normal,
+		Self::Hard,
+		Self::Extreme,
+		Self::Encore,
+	];
+
+	pub fn key(self) -> &'static str {
+		match self {
+			Self::Easy => "easy",
+			Self::Normal => "normal",
+			Self::Hard => "hard",
+			Self::Extreme => "extreme",
+			Self::Encore => "encore",
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "pyo3", pyclass(get_all, set_all))]
+pub struct Chart {
+	pub difficulty: Difficulty,
+	// Slot under `difficulty.<name>`, which is the edition the game loads. Charts without one or
+	// with a slot already taken go in the first free slot
+	pub index: Option<usize>,
+	// 0 for the normal chart, 1 for extra extreme
+	pub edition: Option<u32>,
+	// PV_LV_07_5 is 7.5 stars
+	pub level: Option<String>,
+	pub level_sort_index: Option<u32>,
+	pub script_file_name: Option<String>,
+	// Kept as written, usually hex
+	pub script_format: Option<String>,
+	pub version: Option<u32>,
+	pub original: Option<bool>,
+	pub extra: Option<bool>,
+	// Keys under the chart without a field, written back with it
+	pub unknown: KeyValNode,
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl Chart {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new(difficulty: Difficulty) -> Self {
+		Self {
+			difficulty,
+			index: None,
+			edition: None,
+			level: None,
+			level_sort_index: None,
+			script_file_name: None,
+			script_format: None,
+			version: None,
+			original: None,
+			extra: None,
+			unknown: KeyValNode::new(),
+		}
+	}
+
+	pub fn stars(&self) -> Option<f32> {
+		let (whole, half) = self
+			.level
+			.as_deref()?
+			.strip_prefix("PV_LV_")?
+			.split_once('_')?;
+		let whole = whole.parse::<u8>().ok()?;
+		let half = half.parse::<u8>().ok()?;
+		Some(whole as f32 + half as f32 / 10.0)
+	}
+}
+
+impl Chart {
+	fn read(
+		difficulty: Difficulty,
+		index: usize,
+		fields: &mut Fields<'_>,
+	) -> Result<Self, KeyValError> {
+		Ok(Self {
+			difficulty,
+			index: Some(index),
+			edition: fields.take("edition", KeyValNode::read_u32)?,
+			level: fields.str("level"),
+			level_sort_index: fields.take("level_sort_index", KeyValNode::read_u32)?,
+			script_file_name: fields.str("script_file_name"),
+			script_format: fields.str("script_format"),
+			version: fields.take("version", KeyValNode::read_u32)?,
+			original: fields.take("attribute.original", KeyValNode::read_bool)?,
+			extra: fields.take("attribute.extra", KeyValNode::read_bool)?,
+			unknown: fields.rest(),
+		})
+	}
+
+	fn write(&self, scope: &mut WriterScope<'_>) {
+		scope.write_node("", &self.unknown);
+		write_u32(scope, "edition", self.edition);
+		write_str(scope, "level", &self.level);
+		write_u32(scope, "level_sort_index", self.level_sort_index);
+		write_str(scope, "script_file_name", &self.script_file_name);
+		write_str(scope, "script_format", &self.script_format);
+		write_u32(scope, "version", self.version);
+		write_bool(scope, "attribute.original", self.original);
+		write_bool(scope, "attribute.extra", self.extra);
+	}
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "pyo3", pyclass(get_all, set_all))]
+pub struct Performer {
+	pub chara: Option<String>,
+	// VOCAL, PSEUDO_DEFAULT, GUEST...
+	pub kind: Option<String>,
+	pub pv_costume: Option<u32>,
+	// Keys under the performer without a field, written back under its new index
+	pub unknown: KeyValNode,
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl Performer {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "pyo3", pyclass(get_all, set_all))]
+pub struct Sabi {
+	pub start_time: Option<f32>,
+	pub play_time: Option<f32>,
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl Sabi {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "pyo3", pyclass(get_all, set_all))]
+pub struct SongInfo {
+	pub music: Option<String>,
+	pub lyrics: Option<String>,
+	pub arranger: Option<String>,
+	pub guitar_player: Option<String>,
+	pub manipulator: Option<String>,
+	pub pv_editor: Option<String>,
+	pub illustrator: Option<String>,
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl SongInfo {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+}
+
+impl SongInfo {
+	const KEYS: [&str; 7] = [
+		"music",
+		"lyrics",
+		"arranger",
+		"guitar_player",
+		"manipulator",
+		"pv_editor",
+		"illustrator",
+	];
+
+	fn fields(&self) -> [&Option<String>; 7] {
+		[
+			&self.music,
+			&self.lyrics,
+			&self.arranger,
+			&self.guitar_player,
+			&self.manipulator,
+			&self.pv_editor,
+			&self.illustrator,
+		]
+	}
+
+	fn read(fields: &mut Fields<'_>, key: &str) -> Self {
+		let mut info = Self::new();
+		let values = [
+			&mut info.music,
+			&mut info.lyrics,
+			&mut info.arranger,
+			&mut info.guitar_player,
+			&mut info.manipulator,
+			&mut info.pv_editor,
+			&mut info.illustrator,
+		];
+		for (name, value) in Self::KEYS.iter().zip(values) {
+			*value = fields.str(&format!("{key}.{name}"));
+		}
+		info
+	}
+
+	fn write(&self, scope: &mut WriterScope<'_>, key: &str) {
+		for (name, value) in Self::KEYS.iter().zip(self.fields()) {
+			write_str(scope, &format!("{key}.{name}"), value);
+		}
+	}
+}
+
+// One `pv_xxx` entry. Keys without a field here are kept in `unknown` under their path
+// relative to the pv, so they're written back untouched
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "pyo3", pyclass(get_all, set_all))]
+pub struct Pv {
+	pub id: u32,
+	// How many digits the id is padded to in `pv_xxx`, 3 unless the file spelled it otherwise
+	pub id_digits: usize,
+	pub song_name: Option<String>,
+	pub song_name_reading: Option<String>,
+	pub song_name_en: Option<String>,
+	pub song_file_name: Option<String>,
+	pub bpm: Option<u32>,
+	// YYYYMMDD
+	pub date: Option<u32>,
+	pub sabi: Sabi,
+	pub songinfo: SongInfo,
+	pub songinfo_en: SongInfo,
+	// In file order, grouped by difficulty when written
+	pub charts: Vec<Chart>,
+	// `length` of each difficulty as read, written back unless the charts need more slots
+	pub chart_lengths: BTreeMap<Difficulty, usize>,
+	pub performers: Vec<Performer>,
+	// Motions of each performer, `motion` for the first and `motion2P`, `motion3P`... after
+	pub motions: Vec<Vec<String>>,
+	pub movie_file_name: Option<String>,
+	pub movie_surface: Option<String>,
+	pub movies: Vec<String>,
+	// Keyed by the number in `lyric.001`
+	pub lyrics: BTreeMap<u32, String>,
+	pub lyrics_en: BTreeMap<u32, String>,
+	// How many digits lyric numbers are padded to, like `id_digits`
+	pub lyric_digits: usize,
+	pub unknown: KeyValNode,
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl Pv {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new(id: u32) -> Self {
+		Self {
+			id,
+			id_digits: 3,
+			song_name: None,
+			song_name_reading: None,
+			song_name_en: None,
+			song_file_name: None,
+			bpm: None,
+			date: None,
+			sabi: Sabi::new(),
+			songinfo: SongInfo::new(),
+			songinfo_en: SongInfo::new(),
+			charts: Vec::new(),
+			chart_lengths: BTreeMap::new(),
+			performers: Vec::new(),
+			motions: Vec::new(),
+			movie_file_name: None,
+			movie_surface: None,
+			movies: Vec::new(),
+			lyrics: BTreeMap::new(),
+			lyrics_en: BTreeMap::new(),
+			lyric_digits: 3,
+			unknown: KeyValNode::new(),
+		}
+	}
+
+	pub fn key(&self) -> String {
+		format!("pv_{:0width$}", self.id, width = self.id_digits)
+	}
+
+	// Not referenced from pv_db, the game finds the song select sprites and scene by the pv's id
+	pub fn sprite_set_name(&self) -> String {
+		format!("SPR_SEL_PV{:03}", self.id)
+	}
+
+	pub fn aet_set_name(&self) -> String {
+		format!("AET_SEL_PV{:03}", self.id)
+	}
+
+	pub fn validate(&self) -> Vec<Issue> {
+		let mut issues = Vec::new();
+		if self.song_name.as_deref().is_none_or(str::is_empty) {
+			issues.push(Issue::MissingSongName());
+		}
+		if self.charts.is_empty() {
+			issues.push(Issue::NoCharts());
+		}
+
+		let mut editions = Vec::new();
+		for chart in &self.charts {
+			let edition = chart.edition.unwrap_or(0);
+			if editions.contains(&(chart.difficulty, edition)) {
+				issues.push(Issue::DuplicateEdition {
+					difficulty: chart.difficulty,
+					edition,
+				});
+			}
+			editions.push((chart.difficulty, edition));
+
+			if chart.script_file_name.as_deref().is_none_or(str::is_empty) {
+				issues.push(Issue::MissingScript {
+					difficulty: chart.difficulty,
+					edition,
+				});
+			}
+			if chart.stars().is_none() {
+				issues.push(Issue::BadLevel {
+					difficulty: chart.difficulty,
+					edition,
+					level: chart.level.clone(),
+				});
+			}
+		}
+
+		if self.motions.len() > self.performers.len().max(1) {
+			issues.push(Issue::MotionsWithoutPerformer {
+				performer: self.performers.len(),
+			});
+		}
+		issues
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[pyo3(name = "validate_sets")]
+	fn py_validate_sets(&self, sprites: &sprite::Database, aets: &aet::Database) -> Vec<Issue> {
+		self.validate_sets(sprites, aets)
+	}
+}
+
+impl Pv {
+	pub fn from_node(id: u32, id_digits: usize, node: &KeyValNode) -> Result<Self, KeyValError> {
+		let mut unknown = node.clone();
+		let prefix = format!("pv_{id:0id_digits$}");
+		let mut fields = Fields {
+			node: &mut unknown,
+			prefix: &prefix,
+			path: String::new(),
+		};
+
+		let mut charts = Vec::new();
+		let mut chart_lengths = BTreeMap::new();
+		for difficulty in Difficulty::ALL {
+			let key = format!("difficulty.{}", difficulty.key());
+			let indices = fields.indices(&key, "length")?;
+			if !indices.is_empty() {
+				chart_lengths.insert(difficulty, indices.end);
+			}
+			for i in indices {
+				let key = format!("{key}.{i}");
+				if fields.has_key(&key) {
+					charts.push(Chart::read(difficulty, i, &mut fields.scope(&key))?);
+				}
+			}
+		}
+
+		let mut performers = Vec::new();
+		for i in fields.indices("performer", "num")? {
+			let mut fields = fields.scope(&format!("performer.{i}"));
+			performers.push(Performer {
+				chara: fields.str("chara"),
+				kind: fields.str("type"),
+				pv_costume: fields.take("pv_costume", KeyValNode::read_u32)?,
+				unknown: fields.rest(),
+			});
+		}
+
+		let mut motions = Vec::new();
+		for performer in 0.. {
+			let key = motion_key(performer);
+			if !fields.has_key(&key) {
+				break;
+			}
+			let indices = fields.indices(&key, "length")?;
+			motions.push(
+				indices
+					.filter_map(|i| fields.str(&format!("{key}.{i}")))
+					.collect(),
+			);
+		}
+
+		let mut movies = Vec::new();
+		for i in fields.indices("movie_list", "length")? {
+			movies.extend(fields.str(&format!("movie_list.{i}.name")));
+		}
+
+		let lyric_digits = ["lyric", "lyric_en"]
+			.iter()
+			.filter_map(|key| fields.node.get(key))
+			.flat_map(KeyValNode::keys)
+			.filter(|i| i.parse::<u32>().is_ok())
+			.map(str::len)
+			.min()
+			.unwrap_or(3);
+
+		Ok(Self {
+			id,
+			id_digits,
+			song_name: fields.str("song_name"),
+			song_name_reading: fields.str("song_name_reading"),
+			song_name_en: fields.str("song_name_en"),
+			song_file_name: fields.str("song_file_name"),
+			bpm: fields.take("bpm", KeyValNode::read_u32)?,
+			date: fields.take("date", KeyValNode::read_u32)?,
+			sabi: Sabi {
+				start_time: fields.take("sabi.start_time", KeyValNode::read_f32)?,
+				play_time: fields.take("sabi.play_time", KeyValNode::read_f32)?,
+			},
+			songinfo: SongInfo::read(&mut fields, "songinfo"),
+			songinfo_en: SongInfo::read(&mut fields, "songinfo_en"),
+			charts,
+			chart_lengths,
+			performers,
+			motions,
+			movie_file_name: fields.str("movie_file_name"),
+			movie_surface: fields.str("movie_surface"),
+			movies,
+			lyrics: fields.lyrics("lyric"),
+			lyrics_en: fields.lyrics("lyric_en"),
+			lyric_digits,
+			unknown,
+		})
+	}
+
+	pub fn write(&self, writer: &mut KeyValWriter) {
+		let mut scope = writer.open_scope(&self.key());
+		scope.write_node("", &self.unknown);
+
+		write_str(&mut scope, "song_name", &self.song_name);
+		write_str(&mut scope, "song_name_reading", &self.song_name_reading);
+		write_str(&mut scope, "song_name_en", &self.song_name_en);
+		write_str(&mut scope, "song_file_name", &self.song_file_name);
+		write_u32(&mut scope, "bpm", self.bpm);
+		write_u32(&mut scope, "date", self.date);
+		write_f32(&mut scope, "sabi.start_time", self.sabi.start_time);
+		write_f32(&mut scope, "sabi.play_time", self.sabi.play_time);
+		self.songinfo.write(&mut scope, "songinfo");
+		self.songinfo_en.write(&mut scope, "songinfo_en");
+
+		// Charts go back in the slot they were read from, new ones fill the gaps
+		for difficulty in Difficulty::ALL {
+			let mut slots = BTreeMap::new();
+			let mut unplaced = Vec::new();
+			let charts = self
+				.charts
+				.iter()
+				.filter(|chart| chart.difficulty == difficulty);
+			for chart in charts {
+				match chart.index {
+					Some(index) if !slots.contains_key(&index) => {
+						slots.insert(index, chart);
+					}
+					_ => unplaced.push(chart),
+				}
+			}
+			let mut free = 0;
+			for chart in unplaced {
+				while slots.contains_key(&free) {
+					free += 1;
+				}
+				slots.insert(free, chart);
+			}
+
+			let mut scope = scope.open_scope(&format!("difficulty.{}", difficulty.key()));
+			for (&i, chart) in &slots {
+				chart.write(&mut scope.open_scope_num(i));
+			}
+			let length = slots.last_key_value().map_or(0, |(&i, _)| i + 1);
+			let length = length.max(self.chart_lengths.get(&difficulty).copied().unwrap_or(0));
+			if length > 0 {
+				scope.write_length("", length);
+			}
+		}
+
+		// The performer count is `num` rather than `length`
+		for (i, performer) in self.performers.iter().enumerate() {
+			let mut scope = scope.open_scope(&format!("performer.{i}"));
+			scope.write_node("", &performer.unknown);
+			write_str(&mut scope, "chara", &performer.chara);
+			write_str(&mut scope, "type", &performer.kind);
+			write_u32(&mut scope, "pv_costume", performer.pv_costume);
+		}
+		if !self.performers.is_empty() {
+			scope.write_u32("performer.num", self.performers.len() as u32);
+		}
+
+		for (performer, motions) in self.motions.iter().enumerate() {
+			scope.write_array(&motion_key(performer), motions, |scope, motion| {
+				scope.write_str("", motion)
+			});
+		}
+
+		write_str(&mut scope, "movie_file_name", &self.movie_file_name);
+		write_str(&mut scope, "movie_surface", &self.movie_surface);
+		scope.write_array("movie_list", &self.movies, |scope, movie| {
+			scope.write_str("name", movie)
+		});
+
+		let width = self.lyric_digits;
+		for (i, lyric) in &self.lyrics {
+			scope.write_str(&format!("lyric.{i:0width$}"), lyric);
+		}
+		for (i, lyric) in &self.lyrics_en {
+			scope.write_str(&format!("lyric_en.{i:0width$}"), lyric);
+		}
+	}
+
+	pub fn validate_sets(&self, sprites: &sprite::Database, aets: &aet::Database) -> Vec<Issue> {
+		let mut issues = Vec::new();
+		let name = self.sprite_set_name();
+		if sprites.get_spr_set_by_name(&name).is_none() {
+			issues.push(Issue::MissingSpriteSet { name });
+		}
+		let name = self.aet_set_name();
+		if aets.get_aet_set_by_name(&name).is_none() {
+			issues.push(Issue::MissingAetSet { name });
+		}
+		issues
+	}
+}
+
+// Missing fields are empty tuple variants since python enums with fields can't have unit variants
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "pyo3", pyclass(eq))]
+pub enum Issue {
+	// Another pv in the database has the same id
+	DuplicateId(),
+	MissingSongName(),
+	NoCharts(),
+	DuplicateEdition {
+		difficulty: Difficulty,
+		edition: u32,
+	},
+	MissingScript {
+		difficulty: Difficulty,
+		edition: u32,
+	},
+	// The level isn't of the PV_LV_07_5 form
+	BadLevel {
+		difficulty: Difficulty,
+		edition: u32,
+		level: Option<String>,
+	},
+	// There are motions for a performer past the last one
+	MotionsWithoutPerformer {
+		performer: usize,
+	},
+	MissingSpriteSet {
+		name: String,
+	},
+	MissingAetSet {
+		name: String,
+	},
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "pyo3", pyclass(get_all))]
+pub struct PvIssue {
+	pub pv: u32,
+	pub issue: Issue,
+}
+
+// A whole pv_db or mod_pv_db. Top level keys that aren't `pv_xxx` are kept in `unknown`
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "pyo3", pyclass(get_all, set_all))]
+pub struct Database {
+	pub pvs: Vec<Pv>,
+	pub unknown: KeyValNode,
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl Database {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	#[cfg_attr(feature = "pyo3", staticmethod)]
+	pub fn parse(data: &str) -> Result<Self, KeyValError> {
+		Self::from_node(&KeyValNode::parse(data))
+	}
+
+	pub fn validate(&self) -> Vec<PvIssue> {
+		let mut issues = Vec::new();
+		for (i, pv) in self.pvs.iter().enumerate() {
+			if self.pvs[..i].iter().any(|other| other.id == pv.id) {
+				issues.push(PvIssue {
+					pv: pv.id,
+					issue: Issue::DuplicateId(),
+				});
+			}
+			issues.extend(
+				pv.validate()
+					.into_iter()
+					.map(|issue| PvIssue { pv: pv.id, issue }),
+			);
+		}
+		issues
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[pyo3(name = "get")]
+	fn py_get(&self, id: u32) -> Option<Pv> {
+		self.get(id).cloned()
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[pyo3(name = "validate_sets")]
+	fn py_validate_sets(&self, sprites: &sprite::Database, aets: &aet::Database) -> Vec<PvIssue> {
+		self.validate_sets(sprites, aets)
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __str__(&self) -> String {
+		self.to_string()
+	}
+}
+
+impl Database {
+	pub fn from_node(node: &KeyValNode) -> Result<Self, KeyValError> {
+		let mut database = Self {
+			pvs: Vec::new(),
+			unknown: node.clone(),
+		};
+		for (key, child) in node.children() {
+			let Some(digits) = key.strip_prefix("pv_") else {
+				continue;
+			};
+			if let Ok(id) = digits.parse() {
+				database.pvs.push(Pv::from_node(id, digits.len(), child)?);
+				database.unknown.remove(key);
+			}
+		}
+		Ok(database)
+	}
+
+	pub fn get(&self, id: u32) -> Option<&Pv> {
+		self.pvs.iter().find(|pv| pv.id == id)
+	}
+
+	pub fn get_mut(&mut self, id: u32) -> Option<&mut Pv> {
+		self.pvs.iter_mut().find(|pv| pv.id == id)
+	}
+
+	pub fn writer(&self) -> KeyValWriter {
+		let mut writer = KeyValWriter::from(&self.unknown);
+		for pv in &self.pvs {
+			pv.write(&mut writer);
+		}
+		writer
+	}
+
+	pub fn validate_sets(&self, sprites: &sprite::Database, aets: &aet::Database) -> Vec<PvIssue> {
+		let issues = self.pvs.iter().flat_map(|pv| {
+			let issues = pv.validate_sets(sprites, aets);
+			issues.into_iter().map(|issue| PvIssue { pv: pv.id, issue })
+		});
+		issues.collect()
+	}
+}
+
+impl std::fmt::Display for Database {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		self.writer().fmt(f)
+	}
+}
+
+fn motion_key(performer: usize) -> String {
+	match performer {
+		0 => String::from("motion"),
+		_ => format!("motion{}P", performer + 1),
+	}
+}
+
+// Takes typed values out of a copy of the pv's node, whatever is left over is the pv's unknown keys
+struct Fields<'a> {
+	node: &'a mut KeyValNode,
+	// `pv_xxx`, for errors
+	prefix: &'a str,
+	// Where keys are looked up, relative to the pv
+	path: String,
+}
+
+impl Fields<'_> {
+	fn key(&self, key: &str) -> String {
+		match self.path.is_empty() {
+			true => key.to_string(),
+			false => format!("{}.{key}", self.path),
+		}
+	}
+
+	fn scope<'a>(&'a mut self, key: &str) -> Fields<'a> {
+		Fields {
+			path: self.key(key),
+			node: self.node,
+			prefix: self.prefix,
+		}
+	}
+
+	fn has_key(&self, key: &str) -> bool {
+		self.node.has_key(&self.key(key))
+	}
+
+	fn str(&mut self, key: &str) -> Option<String> {
+		self.node.take_value(&self.key(key))
+	}
+
+	fn take<T>(
+		&mut self,
+		key: &str,
+		read: fn(&KeyValNode, &str) -> Result<T, KeyValError>,
+	) -> Result<Option<T>, KeyValError> {
+		let key = self.key(key);
+		match read(self.node, &key) {
+			Ok(value) => {
+				self.node.take_value(&key);
+				Ok(Some(value))
+			}
+			Err(KeyValError::Missing { .. }) => Ok(None),
+			Err(KeyValError::Invalid {
+				key,
+				value,
+				expected,
+			}) => Err(KeyValError::Invalid {
+				key: format!("{}.{key}", self.prefix),
+				value,
+				expected,
+			}),
+		}
+	}
+
+	// Indices of the items under `key`, from its count key or by counting when that's missing
+	fn indices(&mut self, key: &str, count: &str) -> Result<std::ops::Range<usize>, KeyValError> {
+		let length = match self.take(&format!("{key}.{count}"), KeyValNode::read_u32)? {
+			Some(length) => length as usize,
+			None => self
+				.node
+				.get(&self.key(key))
+				.and_then(KeyValNode::length)
+				.unwrap_or(0),
+		};
+		Ok(0..length)
+	}
+
+	// Whatever is left under the scope, taken out of the pv's unknown keys
+	fn rest(&mut self) -> KeyValNode {
+		self.node.remove(&self.path).unwrap_or_default()
+	}
+
+	fn lyrics(&mut self, key: &str) -> BTreeMap<u32, String> {
+		let Some(node) = self.node.get(&self.key(key)) else {
+			return BTreeMap::new();
+		};
+		let numbers = node
+			.keys()
+			.filter_map(|i| Some((i.parse().ok()?, i.to_string())));
+		let numbers = numbers.collect::<Vec<(u32, String)>>();
+		let lyrics = numbers
+			.into_iter()
+			.filter_map(|(n, i)| Some((n, self.str(&format!("{key}.{i}"))?)));
+		lyrics.collect()
+	}
+}
+
+fn write_str(scope: &mut WriterScope<'_>, key: &str, value: &Option<String>) {
+	if let Some(value) = value {
+		scope.write_str(key, value);
+	}
+}
+
+fn write_u32(scope: &mut WriterScope<'_>, key: &str, value: Option<u32>) {
+	if let Some(value) = value {
+		scope.write_u32(key, value);
+	}
+}
+
+fn write_f32(scope: &mut WriterScope<'_>, key: &str, value: Option<f32>) {
+	if let Some(value) = value {
+		scope.write_f32(key, value);
+	}
+}
+
+fn write_bool(scope: &mut WriterScope<'_>, key: &str, value: Option<bool>) {
+	if let Some(value) = value {
+		scope.write_bool(key, value);
+	}
+}
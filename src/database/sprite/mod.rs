@@ -0,0 +1,1532 @@
+use std::collections::{HashMap, HashSet};
+use std::ffi::*;
+use std::hash::Hash;
+
+#[cfg(feature = "serde")]
+mod serialize;
+
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+
+#[cfg(feature = "pyo3")]
+#[cfg_attr(feature = "pyo3", pymodule(name = "sprite"))]
+pub(crate) mod sprite_module {
+	#[pymodule_export]
+	use super::Conflict;
+	#[pymodule_export]
+	use super::ConflictKind;
+	#[pymodule_export]
+	use super::Database;
+	#[pymodule_export]
+	use super::Occurrence;
+	#[pymodule_export]
+	use super::PyEntry;
+	#[pymodule_export]
+	use super::PySet;
+	#[pymodule_export]
+	use super::file::file_module;
+}
+
+pub mod file {
+	use std::ffi::*;
+	use std::marker::PhantomData;
+
+	#[cfg(feature = "pyo3")]
+	use pyo3::prelude::*;
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pymodule(name = "file"))]
+	pub(crate) mod file_module {
+		#[pymodule_export]
+		use super::Change;
+		#[pymodule_export]
+		use super::Database;
+		#[pymodule_export]
+		use super::Difference;
+		#[pymodule_export]
+		use super::Entry;
+		#[pymodule_export]
+		use super::Set;
+		#[pymodule_export]
+		use super::diff;
+	}
+
+	// Same layout as the Ref and Mut wrappers, which are transmuted to it
+	#[repr(transparent)]
+	#[cfg_attr(feature = "pyo3", pyclass)]
+	pub struct Entry {
+		ptr: *mut c_void,
+	}
+
+	unsafe impl Send for Entry {}
+	unsafe impl Sync for Entry {}
+
+	#[cfg_eval]
+	#[cfg_attr(feature = "pyo3", pymethods)]
+	impl Entry {
+		#[cfg_attr(feature = "pyo3", new)]
+		pub fn new() -> Self {
+			Self {
+				ptr: unsafe { kkdlib_spr_db_spr_file_new() },
+			}
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn id(&self) -> u32 {
+			unsafe { kkdlib_spr_db_spr_file_get_id(self.ptr) }
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_id(&mut self, id: u32) {
+			unsafe { kkdlib_spr_db_spr_file_set_id(self.ptr, id) };
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn name(&self) -> String {
+			let name = unsafe { kkdlib_spr_db_spr_file_get_name(self.ptr) };
+			let name = unsafe { CStr::from_ptr(name) };
+			name.to_string_lossy().to_string()
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_name(&mut self, name: &str) {
+			let Ok(name) = CString::new(name) else {
+				return;
+			};
+			unsafe { kkdlib_spr_db_spr_file_set_name(self.ptr, name.as_ptr()) };
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn index(&self) -> u16 {
+			unsafe { kkdlib_spr_db_spr_file_get_index(self.ptr) }
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_index(&mut self, index: u16) {
+			unsafe { kkdlib_spr_db_spr_file_set_index(self.ptr, index) };
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn texture(&self) -> bool {
+			unsafe { kkdlib_spr_db_spr_file_get_texture(self.ptr) }
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_texture(&mut self, texture: bool) {
+			unsafe { kkdlib_spr_db_spr_file_set_texture(self.ptr, texture) };
+		}
+
+		// The game looks sprites up by the murmurhash of their name
+		pub fn assign_id(&mut self) {
+			self.set_id(crate::hash::murmurhash(self.name()));
+		}
+	}
+
+	impl Clone for Entry {
+		fn clone(&self) -> Self {
+			let mut entry = Self::new();
+			entry.set_id(self.id());
+			entry.set_name(&self.name());
+			entry.set_index(self.index());
+			entry.set_texture(self.texture());
+			entry
+		}
+	}
+
+	impl Drop for Entry {
+		fn drop(&mut self) {
+			unsafe { kkdlib_spr_db_spr_file_delete(self.ptr) };
+		}
+	}
+
+	#[repr(transparent)]
+	pub struct EntryRef<'a> {
+		_ptr: *mut c_void,
+		phantom: PhantomData<&'a Entry>,
+	}
+
+	unsafe impl Send for EntryRef<'_> {}
+	unsafe impl Sync for EntryRef<'_> {}
+
+	impl EntryRef<'_> {
+		pub fn id(&self) -> u32 {
+			Entry::id(unsafe { std::mem::transmute(self) })
+		}
+
+		pub fn name(&self) -> String {
+			Entry::name(unsafe { std::mem::transmute(self) })
+		}
+
+		pub fn index(&self) -> u16 {
+			Entry::index(unsafe { std::mem::transmute(self) })
+		}
+
+		pub fn texture(&self) -> bool {
+			Entry::texture(unsafe { std::mem::transmute(self) })
+		}
+
+		pub fn cloned(&self) -> Entry {
+			Entry::clone(unsafe { std::mem::transmute(self) })
+		}
+	}
+
+	pub struct EntryIter<'a> {
+		ptr: *mut c_void,
+		index: usize,
+		len: usize,
+		phantom: PhantomData<&'a Set>,
+	}
+
+	impl<'a> Iterator for EntryIter<'a> {
+		type Item = EntryRef<'a>;
+
+		fn next(&mut self) -> Option<Self::Item> {
+			if self.index == self.len {
+				return None;
+			}
+
+			let entry = unsafe { kkdlib_spr_db_spr_set_file_get_sprite(self.ptr, self.index) };
+			if entry.is_null() {
+				return None;
+			}
+
+			self.index += 1;
+			Some(EntryRef {
+				_ptr: entry,
+				phantom: PhantomData,
+			})
+		}
+
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			(self.len, Some(self.len))
+		}
+	}
+
+	#[repr(transparent)]
+	pub struct EntryMut<'a> {
+		_ptr: *mut c_void,
+		phantom: PhantomData<&'a mut Set>,
+	}
+
+	unsafe impl Send for EntryMut<'_> {}
+	unsafe impl Sync for EntryMut<'_> {}
+
+	impl<'a> std::ops::Deref for EntryMut<'a> {
+		type Target = EntryRef<'a>;
+
+		fn deref(&self) -> &EntryRef<'a> {
+			unsafe { std::mem::transmute(self) }
+		}
+	}
+
+	// Only the setters are forwarded, a &mut Entry would let the entry be swapped out and
+	// deleted while the set still owns it
+	impl EntryMut<'_> {
+		fn entry(&mut self) -> &mut Entry {
+			unsafe { std::mem::transmute(self) }
+		}
+
+		pub fn set_id(&mut self, id: u32) {
+			self.entry().set_id(id);
+		}
+
+		pub fn set_name(&mut self, name: &str) {
+			self.entry().set_name(name);
+		}
+
+		pub fn set_index(&mut self, index: u16) {
+			self.entry().set_index(index);
+		}
+
+		pub fn set_texture(&mut self, texture: bool) {
+			self.entry().set_texture(texture);
+		}
+
+		pub fn assign_id(&mut self) {
+			self.entry().assign_id();
+		}
+	}
+
+	pub struct EntryIterMut<'a> {
+		ptr: *mut c_void,
+		index: usize,
+		len: usize,
+		phantom: PhantomData<&'a mut Set>,
+	}
+
+	impl<'a> Iterator for EntryIterMut<'a> {
+		type Item = EntryMut<'a>;
+
+		fn next(&mut self) -> Option<Self::Item> {
+			if self.index == self.len {
+				return None;
+			}
+
+			let entry = unsafe { kkdlib_spr_db_spr_set_file_get_sprite(self.ptr, self.index) };
+			if entry.is_null() {
+				return None;
+			}
+
+			self.index += 1;
+			Some(EntryMut {
+				_ptr: entry,
+				phantom: PhantomData,
+			})
+		}
+
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			(self.len, Some(self.len))
+		}
+	}
+
+	#[repr(transparent)]
+	#[cfg_attr(feature = "pyo3", pyclass)]
+	pub struct Set {
+		ptr: *mut c_void,
+	}
+
+	unsafe impl Send for Set {}
+	unsafe impl Sync for Set {}
+
+	#[cfg_eval]
+	#[cfg_attr(feature = "pyo3", pymethods)]
+	impl Set {
+		#[cfg_attr(feature = "pyo3", new)]
+		pub fn new() -> Self {
+			Self {
+				ptr: unsafe { kkdlib_spr_db_spr_set_file_new() },
+			}
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn id(&self) -> u32 {
+			unsafe { kkdlib_spr_db_spr_set_file_get_id(self.ptr) }
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_id(&mut self, id: u32) {
+			unsafe { kkdlib_spr_db_spr_set_file_set_id(self.ptr, id) };
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn name(&self) -> String {
+			let name = unsafe { kkdlib_spr_db_spr_set_file_get_name(self.ptr) };
+			let name = unsafe { CStr::from_ptr(name) };
+			name.to_string_lossy().to_string()
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_name(&mut self, name: &str) {
+			let Ok(name) = CString::new(name) else {
+				return;
+			};
+			unsafe { kkdlib_spr_db_spr_set_file_set_name(self.ptr, name.as_ptr()) };
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn file_name(&self) -> String {
+			let name = unsafe { kkdlib_spr_db_spr_set_file_get_file_name(self.ptr) };
+			let name = unsafe { CStr::from_ptr(name) };
+			name.to_string_lossy().to_string()
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_file_name(&mut self, file_name: &str) {
+			let Ok(file_name) = CString::new(file_name) else {
+				return;
+			};
+			unsafe { kkdlib_spr_db_spr_set_file_set_file_name(self.ptr, file_name.as_ptr()) };
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn index(&self) -> u32 {
+			unsafe { kkdlib_spr_db_spr_set_file_get_index(self.ptr) }
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_index(&mut self, index: u32) {
+			unsafe { kkdlib_spr_db_spr_set_file_set_index(self.ptr, index) };
+		}
+
+		pub fn add_sprite(&mut self, entry: &Entry) {
+			unsafe {
+				kkdlib_spr_db_spr_set_file_add_sprite(self.ptr, entry.ptr);
+			}
+		}
+
+		pub fn replace_sprite(&mut self, index: usize, entry: &Entry) -> bool {
+			if index >= self.sprite_count() {
+				return false;
+			}
+			unsafe { kkdlib_spr_db_spr_set_file_replace_sprite(self.ptr, index, entry.ptr) };
+			true
+		}
+
+		// The sprites after it move down to fill the gap
+		pub fn remove_sprite(&mut self, index: usize) -> Option<Entry> {
+			let entry = self.sprites().nth(index)?.cloned();
+			unsafe { kkdlib_spr_db_spr_set_file_remove_sprite(self.ptr, index) };
+			self.renumber();
+			Some(entry)
+		}
+
+		// Textures and sprites are numbered separately, in the order they appear
+		pub fn renumber(&mut self) {
+			let (mut textures, mut sprites) = (0, 0);
+			for mut entry in self.sprites_mut() {
+				let counter = if entry.texture() {
+					&mut textures
+				} else {
+					&mut sprites
+				};
+				entry.set_index(*counter);
+				*counter += 1;
+			}
+		}
+
+		// Gives the set and every sprite in it the id the game expects for its name
+		pub fn assign_ids(&mut self) {
+			self.set_id(crate::hash::murmurhash(self.name()));
+			for mut entry in self.sprites_mut() {
+				entry.assign_id();
+			}
+		}
+
+		// Entries for every sprite and then every texture of the set, named SPR_<set>_<sprite> and
+		// SPRTEX_<set>_<texture> like the game's own databases. set_name may leave out the SPR_
+		#[cfg_attr(feature = "pyo3", staticmethod)]
+		pub fn from_spr_set(spr_set: &crate::spr::Set, set_name: &str, file_name: &str) -> Self {
+			let base = set_name.strip_prefix("SPR_").unwrap_or(set_name);
+			let mut set = Self::new();
+			set.set_name(&format!("SPR_{base}"));
+			set.set_file_name(file_name);
+
+			let sprites = spr_set
+				.sprites()
+				.map(|(name, _)| (format!("SPR_{base}_{name}"), false));
+			let textures = spr_set
+				.textures()
+				.map(|(name, _)| (format!("SPRTEX_{base}_{name}"), true));
+			for (name, texture) in sprites.chain(textures) {
+				let mut entry = Entry::new();
+				entry.set_name(&name);
+				entry.set_texture(texture);
+				set.add_sprite(&entry);
+			}
+			set.renumber();
+			set.assign_ids();
+			set
+		}
+
+		// Copies, edits have to be put back with replace_sprite
+		#[cfg(feature = "pyo3")]
+		#[cfg_attr(feature = "pyo3", getter(sprites))]
+		pub fn py_sprites(&self) -> Vec<Entry> {
+			self.sprites().map(|entry| entry.cloned()).collect()
+		}
+	}
+
+	impl<'a> Set {
+		pub fn sprites(&'a self) -> EntryIter<'a> {
+			EntryIter {
+				ptr: self.ptr,
+				index: 0,
+				len: self.sprite_count(),
+				phantom: PhantomData,
+			}
+		}
+
+		pub fn sprites_mut(&'a mut self) -> EntryIterMut<'a> {
+			EntryIterMut {
+				ptr: self.ptr,
+				index: 0,
+				len: self.sprite_count(),
+				phantom: PhantomData,
+			}
+		}
+
+		pub fn sprite_mut(&'a mut self, index: usize) -> Option<EntryMut<'a>> {
+			self.sprites_mut().nth(index)
+		}
+
+		fn sprite_count(&self) -> usize {
+			unsafe { kkdlib_spr_db_spr_set_file_get_sprite_size(self.ptr) }
+		}
+	}
+
+	impl Clone for Set {
+		fn clone(&self) -> Self {
+			let mut set = Self::new();
+			set.set_id(self.id());
+			set.set_name(&self.name());
+			set.set_file_name(&self.file_name());
+			set.set_index(self.index());
+			for entry in self.sprites() {
+				set.add_sprite(&entry.cloned());
+			}
+			set
+		}
+	}
+
+	impl Drop for Set {
+		fn drop(&mut self) {
+			unsafe { kkdlib_spr_db_spr_set_file_delete(self.ptr) };
+		}
+	}
+
+	#[repr(transparent)]
+	pub struct SetRef<'a> {
+		_ptr: *mut c_void,
+		phantom: PhantomData<&'a Set>,
+	}
+
+	unsafe impl Send for SetRef<'_> {}
+	unsafe impl Sync for SetRef<'_> {}
+
+	impl<'a> SetRef<'a> {
+		pub fn id(&self) -> u32 {
+			Set::id(unsafe { std::mem::transmute(self) })
+		}
+
+		pub fn name(&self) -> String {
+			Set::name(unsafe { std::mem::transmute(self) })
+		}
+
+		pub fn file_name(&self) -> String {
+			Set::file_name(unsafe { std::mem::transmute(self) })
+		}
+
+		pub fn index(&self) -> u32 {
+			Set::index(unsafe { std::mem::transmute(self) })
+		}
+
+		pub fn sprites(&'a self) -> EntryIter<'a> {
+			Set::sprites(unsafe { std::mem::transmute(self) })
+		}
+
+		pub fn cloned(&self) -> Set {
+			Set::clone(unsafe { std::mem::transmute(self) })
+		}
+	}
+
+	pub struct SetIter<'a> {
+		ptr: *mut c_void,
+		index: usize,
+		len: usize,
+		phantom: PhantomData<&'a Database>,
+	}
+
+	impl<'a> Iterator for SetIter<'a> {
+		type Item = SetRef<'a>;
+
+		fn next(&mut self) -> Option<Self::Item> {
+			if self.index == self.len {
+				return None;
+			}
+
+			let set = unsafe { kkdlib_sprite_database_file_get_sprite_set(self.ptr, self.index) };
+			if set.is_null() {
+				return None;
+			}
+
+			self.index += 1;
+			Some(SetRef {
+				_ptr: set,
+				phantom: PhantomData,
+			})
+		}
+
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			(self.len, Some(self.len))
+		}
+	}
+
+	#[repr(transparent)]
+	pub struct SetMut<'a> {
+		_ptr: *mut c_void,
+		phantom: PhantomData<&'a mut Database>,
+	}
+
+	unsafe impl Send for SetMut<'_> {}
+	unsafe impl Sync for SetMut<'_> {}
+
+	impl<'a> std::ops::Deref for SetMut<'a> {
+		type Target = SetRef<'a>;
+
+		fn deref(&self) -> &SetRef<'a> {
+			unsafe { std::mem::transmute(self) }
+		}
+	}
+
+	// Same as EntryMut, the set stays owned by the database
+	impl SetMut<'_> {
+		fn set(&mut self) -> &mut Set {
+			unsafe { std::mem::transmute(self) }
+		}
+
+		pub fn set_id(&mut self, id: u32) {
+			self.set().set_id(id);
+		}
+
+		pub fn set_name(&mut self, name: &str) {
+			self.set().set_name(name);
+		}
+
+		pub fn set_file_name(&mut self, file_name: &str) {
+			self.set().set_file_name(file_name);
+		}
+
+		pub fn set_index(&mut self, index: u32) {
+			self.set().set_index(index);
+		}
+
+		pub fn add_sprite(&mut self, entry: &Entry) {
+			self.set().add_sprite(entry);
+		}
+
+		pub fn replace_sprite(&mut self, index: usize, entry: &Entry) -> bool {
+			self.set().replace_sprite(index, entry)
+		}
+
+		pub fn remove_sprite(&mut self, index: usize) -> Option<Entry> {
+			self.set().remove_sprite(index)
+		}
+
+		pub fn renumber(&mut self) {
+			self.set().renumber();
+		}
+
+		pub fn assign_ids(&mut self) {
+			self.set().assign_ids();
+		}
+
+		pub fn sprites_mut(&mut self) -> EntryIterMut<'_> {
+			self.set().sprites_mut()
+		}
+
+		pub fn sprite_mut(&mut self, index: usize) -> Option<EntryMut<'_>> {
+			self.set().sprite_mut(index)
+		}
+	}
+
+	pub struct SetIterMut<'a> {
+		ptr: *mut c_void,
+		index: usize,
+		len: usize,
+		phantom: PhantomData<&'a mut Database>,
+	}
+
+	impl<'a> Iterator for SetIterMut<'a> {
+		type Item = SetMut<'a>;
+
+		fn next(&mut self) -> Option<Self::Item> {
+			if self.index == self.len {
+				return None;
+			}
+
+			let set = unsafe { kkdlib_sprite_database_file_get_sprite_set(self.ptr, self.index) };
+			if set.is_null() {
+				return None;
+			}
+
+			self.index += 1;
+			Some(SetMut {
+				_ptr: set,
+				phantom: PhantomData,
+			})
+		}
+
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			(self.len, Some(self.len))
+		}
+	}
+
+	#[cfg_attr(feature = "pyo3", pyclass)]
+	pub struct Database {
+		pub(crate) ptr: *mut c_void,
+	}
+
+	unsafe impl Send for Database {}
+	unsafe impl Sync for Database {}
+
+	#[cfg_eval]
+	#[cfg_attr(feature = "pyo3", pymethods)]
+	impl Database {
+		#[cfg_attr(feature = "pyo3", new)]
+		pub fn new() -> Self {
+			Self {
+				ptr: unsafe { kkdlib_sprite_database_file_new() },
+			}
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn ready(&self) -> bool {
+			unsafe { kkdlib_sprite_database_file_get_ready(self.ptr) }
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_ready(&mut self, ready: bool) {
+			unsafe { kkdlib_sprite_database_file_set_ready(self.ptr, ready) };
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn modern(&self) -> bool {
+			unsafe { kkdlib_sprite_database_file_get_modern(self.ptr) }
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_modern(&mut self, modern: bool) {
+			unsafe { kkdlib_sprite_database_file_set_modern(self.ptr, modern) };
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn big_endian(&self) -> bool {
+			unsafe { kkdlib_sprite_database_file_get_big_endian(self.ptr) }
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_big_endian(&mut self, big_endian: bool) {
+			unsafe { kkdlib_sprite_database_file_set_big_endian(self.ptr, big_endian) };
+		}
+
+		#[cfg_attr(feature = "pyo3", getter)]
+		pub fn is_x(&self) -> bool {
+			unsafe { kkdlib_sprite_database_file_get_is_x(self.ptr) }
+		}
+
+		#[cfg_attr(feature = "pyo3", setter)]
+		pub fn set_is_x(&mut self, is_x: bool) {
+			unsafe { kkdlib_sprite_database_file_set_is_x(self.ptr, is_x) };
+		}
+
+		pub fn add_set(&mut self, set: &Set) {
+			unsafe { kkdlib_sprite_database_file_add_sprite_set(self.ptr, set.ptr) };
+		}
+
+		pub fn replace_set(&mut self, index: usize, set: &Set) -> bool {
+			if index >= self.set_count() {
+				return false;
+			}
+			unsafe { kkdlib_sprite_database_file_replace_sprite_set(self.ptr, index, set.ptr) };
+			true
+		}
+
+		// The sets after it move down to fill the gap
+		pub fn remove_set(&mut self, index: usize) -> Option<Set> {
+			let set = self.sets().nth(index)?.cloned();
+			unsafe { kkdlib_sprite_database_file_remove_sprite_set(self.ptr, index) };
+			self.renumber();
+			Some(set)
+		}
+
+		// Sets are numbered in the order they appear, and so are the sprites in each of them
+		pub fn renumber(&mut self) {
+			for (index, mut set) in self.sets_mut().enumerate() {
+				set.set_index(index as u32);
+				set.renumber();
+			}
+		}
+
+		pub fn assign_ids(&mut self) {
+			for mut set in self.sets_mut() {
+				set.assign_ids();
+			}
+		}
+
+		#[cfg_attr(feature = "pyo3", staticmethod)]
+		pub fn from_buf(data: &[u8], modern: bool) -> Self {
+			let database = Self::new();
+			unsafe {
+				kkdlib_sprite_database_file_read_data(
+					database.ptr,
+					data.as_ptr() as *const c_void,
+					data.len(),
+					modern,
+				)
+			};
+			database
+		}
+
+		pub fn to_buf(&self) -> Option<Vec<u8>> {
+			let mut ptr = std::ptr::null_mut();
+			let mut size = 0usize;
+			unsafe {
+				kkdlib_sprite_database_file_write_data(self.ptr, &mut ptr, &mut size);
+			}
+
+			if ptr.is_null() || size == 0 {
+				return None;
+			}
+
+			let slice = std::ptr::slice_from_raw_parts(ptr as *const u8, size);
+			let slice = unsafe { slice.as_ref()? };
+
+			let mut vec = Vec::with_capacity(size);
+			vec.extend_from_slice(slice);
+
+			unsafe { kkdlib_sprite_databse_file_delete_packed_data(ptr) };
+
+			Some(vec)
+		}
+
+		// Copies, edits have to be put back with replace_set
+		#[cfg(feature = "pyo3")]
+		#[cfg_attr(feature = "pyo3", getter(sets))]
+		pub fn py_sets(&self) -> Vec<Set> {
+			self.sets().map(|set| set.cloned()).collect()
+		}
+	}
+
+	impl<'a> Database {
+		pub fn sets(&'a self) -> SetIter<'a> {
+			SetIter {
+				ptr: self.ptr,
+				index: 0,
+				len: self.set_count(),
+				phantom: PhantomData,
+			}
+		}
+
+		pub fn sets_mut(&'a mut self) -> SetIterMut<'a> {
+			SetIterMut {
+				ptr: self.ptr,
+				index: 0,
+				len: self.set_count(),
+				phantom: PhantomData,
+			}
+		}
+
+		pub fn set_mut(&'a mut self, index: usize) -> Option<SetMut<'a>> {
+			self.sets_mut().nth(index)
+		}
+
+		fn set_count(&self) -> usize {
+			unsafe { kkdlib_sprite_database_file_get_sprite_set_size(self.ptr) }
+		}
+	}
+
+	impl Drop for Database {
+		fn drop(&mut self) {
+			unsafe { kkdlib_sprite_database_file_delete(self.ptr) };
+		}
+	}
+
+	#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+	#[cfg_attr(feature = "pyo3", pyclass(eq))]
+	pub enum Change {
+		Added,
+		Removed,
+		Changed,
+	}
+
+	#[derive(Debug, Clone, PartialEq)]
+	#[cfg_attr(feature = "pyo3", pyclass(get_all))]
+	pub struct Difference {
+		pub change: Change,
+		// Name of the set, or of the set the sprite is in
+		pub set: String,
+		// None when the difference is the set itself
+		pub sprite: Option<String>,
+		pub id: u32,
+	}
+
+	// Sets and sprites are matched by id. Sets that were added or removed are listed without
+	// their sprites
+	#[cfg_attr(feature = "pyo3", pyfunction)]
+	pub fn diff(a: &Database, b: &Database) -> Vec<Difference> {
+		let before = a.sets().map(|set| (set.id(), set)).collect::<Vec<_>>();
+		let after = b.sets().map(|set| (set.id(), set)).collect::<Vec<_>>();
+		let find =
+			|sets: &[(u32, SetRef)], id: u32| sets.iter().position(|(other, _)| *other == id);
+
+		let mut differences = Vec::new();
+		for (id, old) in &before {
+			if find(&after, *id).is_none() {
+				differences.push(Difference {
+					change: Change::Removed,
+					set: old.name(),
+					sprite: None,
+					id: *id,
+				});
+			}
+		}
+
+		for (id, new) in &after {
+			let Some(old) = find(&before, *id).map(|index| &before[index].1) else {
+				differences.push(Difference {
+					change: Change::Added,
+					set: new.name(),
+					sprite: None,
+					id: *id,
+				});
+				continue;
+			};
+			if old.name() != new.name() || old.file_name() != new.file_name() {
+				differences.push(Difference {
+					change: Change::Changed,
+					set: new.name(),
+					sprite: None,
+					id: *id,
+				});
+			}
+
+			let old_sprites = old.sprites().collect::<Vec<_>>();
+			let new_sprites = new.sprites().collect::<Vec<_>>();
+			let sprite = |change, entry: &EntryRef| Difference {
+				change,
+				set: new.name(),
+				sprite: Some(entry.name()),
+				id: entry.id(),
+			};
+			for entry in &old_sprites {
+				if !new_sprites.iter().any(|other| other.id() == entry.id()) {
+					differences.push(sprite(Change::Removed, entry));
+				}
+			}
+			for entry in &new_sprites {
+				match old_sprites.iter().find(|other| other.id() == entry.id()) {
+					None => differences.push(sprite(Change::Added, entry)),
+					Some(other)
+						if other.name() != entry.name()
+							|| other.index() != entry.index()
+							|| other.texture() != entry.texture() =>
+					{
+						differences.push(sprite(Change::Changed, entry))
+					}
+					Some(_) => {}
+				}
+			}
+		}
+		differences
+	}
+
+	unsafe extern "C" {
+		fn kkdlib_spr_db_spr_file_new() -> *mut c_void;
+		fn kkdlib_spr_db_spr_file_get_id(spr_file: *mut c_void) -> u32;
+		fn kkdlib_spr_db_spr_file_set_id(spr_file: *mut c_void, id: u32);
+		fn kkdlib_spr_db_spr_file_get_name(spr_file: *mut c_void) -> *const c_char;
+		fn kkdlib_spr_db_spr_file_set_name(spr_file: *mut c_void, name: *const c_char);
+		fn kkdlib_spr_db_spr_file_get_index(spr_file: *mut c_void) -> u16;
+		fn kkdlib_spr_db_spr_file_set_index(spr_file: *mut c_void, index: u16);
+		fn kkdlib_spr_db_spr_file_get_texture(spr_file: *mut c_void) -> bool;
+		fn kkdlib_spr_db_spr_file_set_texture(spr_file: *mut c_void, texture: bool);
+		fn kkdlib_spr_db_spr_file_delete(spr_file: *mut c_void);
+
+		fn kkdlib_spr_db_spr_set_file_new() -> *mut c_void;
+		fn kkdlib_spr_db_spr_set_file_get_id(set_file: *mut c_void) -> u32;
+		fn kkdlib_spr_db_spr_set_file_set_id(set_file: *mut c_void, id: u32);
+		fn kkdlib_spr_db_spr_set_file_get_name(set_file: *mut c_void) -> *const c_char;
+		fn kkdlib_spr_db_spr_set_file_set_name(set_file: *mut c_void, name: *const c_char);
+		fn kkdlib_spr_db_spr_set_file_get_file_name(set_file: *mut c_void) -> *const c_char;
+		fn kkdlib_spr_db_spr_set_file_set_file_name(set_file: *mut c_void, name: *const c_char);
+		fn kkdlib_spr_db_spr_set_file_get_index(set_file: *mut c_void) -> u32;
+		fn kkdlib_spr_db_spr_set_file_set_index(set_file: *mut c_void, index: u32);
+		fn kkdlib_spr_db_spr_set_file_get_sprite_size(set_file: *mut c_void) -> usize;
+		fn kkdlib_spr_db_spr_set_file_get_sprite(
+			set_file: *mut c_void,
+			index: usize,
+		) -> *mut c_void;
+		fn kkdlib_spr_db_spr_set_file_add_sprite(set_file: *mut c_void, spr_file: *mut c_void);
+		fn kkdlib_spr_db_spr_set_file_replace_sprite(
+			set_file: *mut c_void,
+			index: usize,
+			spr_file: *mut c_void,
+		);
+		fn kkdlib_spr_db_spr_set_file_remove_sprite(set_file: *mut c_void, index: usize);
+		fn kkdlib_spr_db_spr_set_file_delete(set_file: *mut c_void);
+
+		fn kkdlib_sprite_database_file_new() -> *mut c_void;
+		fn kkdlib_sprite_database_file_get_ready(database_file: *mut c_void) -> bool;
+		fn kkdlib_sprite_database_file_set_ready(database_file: *mut c_void, ready: bool);
+		fn kkdlib_sprite_database_file_get_modern(database_file: *mut c_void) -> bool;
+		fn kkdlib_sprite_database_file_set_modern(database_file: *mut c_void, modern: bool);
+		fn kkdlib_sprite_database_file_get_big_endian(database_file: *mut c_void) -> bool;
+		fn kkdlib_sprite_database_file_set_big_endian(database_file: *mut c_void, big_endian: bool);
+		fn kkdlib_sprite_database_file_get_is_x(database_file: *mut c_void) -> bool;
+		fn kkdlib_sprite_database_file_set_is_x(database_file: *mut c_void, is_x: bool);
+		fn kkdlib_sprite_database_file_get_sprite_set_size(database_file: *mut c_void) -> usize;
+		fn kkdlib_sprite_database_file_get_sprite_set(
+			database_file: *mut c_void,
+			index: usize,
+		) -> *mut c_void;
+		fn kkdlib_sprite_database_file_add_sprite_set(
+			database_file: *mut c_void,
+			set_file: *mut c_void,
+		);
+		fn kkdlib_sprite_database_file_replace_sprite_set(
+			database_file: *mut c_void,
+			index: usize,
+			set_file: *mut c_void,
+		);
+		fn kkdlib_sprite_database_file_remove_sprite_set(database_file: *mut c_void, index: usize);
+		fn kkdlib_sprite_database_file_read_data(
+			database_file: *mut c_void,
+			data: *const c_void,
+			size: usize,
+			modern: bool,
+		);
+		fn kkdlib_sprite_database_file_write_data(
+			database_file: *mut c_void,
+			data: *mut *mut c_void,
+			size: *mut usize,
+		);
+		fn kkdlib_sprite_databse_file_delete_packed_data(data: *mut c_void);
+		fn kkdlib_sprite_database_file_delete(database_file: *mut c_void);
+	}
+}
+
+pub struct EntryRef<'a> {
+	ptr: *const c_void,
+	database: &'a Database,
+}
+
+unsafe impl Send for EntryRef<'_> {}
+unsafe impl Sync for EntryRef<'_> {}
+
+impl<'a> EntryRef<'a> {
+	pub fn id(&self) -> u32 {
+		unsafe { kkdlib_spr_db_spr_get_id(self.ptr) }
+	}
+
+	pub fn name(&self) -> String {
+		let ptr = unsafe { kkdlib_spr_db_spr_get_name(self.ptr) };
+		let cstr = unsafe { CStr::from_ptr(ptr) };
+		cstr.to_string_lossy().to_string()
+	}
+
+	pub fn index(&self) -> u16 {
+		unsafe { kkdlib_spr_db_spr_get_index(self.ptr) }
+	}
+
+	pub fn set_index(&self) -> u16 {
+		unsafe { kkdlib_spr_db_spr_get_set_index(self.ptr) }
+	}
+
+	// Whether this is a texture's entry rather than a sprite's
+	pub fn texture(&self) -> bool {
+		self.file_entry().is_some_and(|entry| entry.texture())
+	}
+
+	pub fn set(&self) -> Option<SetRef<'a>> {
+		let contains = |set: &SetRef| set.sprites().any(|entry| entry.ptr == self.ptr);
+		self.database
+			.get_spr_set_by_index(self.set_index() as u32)
+			.filter(contains)
+			.or_else(|| self.database.sets().find(contains))
+	}
+
+	// The entry this one was added from
+	fn file_entry(&self) -> Option<file::EntryRef<'a>> {
+		let (id, name) = (self.id(), self.name());
+		self.database
+			.sets
+			.iter()
+			.rev()
+			.flat_map(|(_, set)| set.sprites())
+			.find(|entry| entry.id() == id && entry.name() == name)
+	}
+}
+
+pub struct SetRef<'a> {
+	ptr: *const c_void,
+	database: &'a Database,
+}
+
+unsafe impl Send for SetRef<'_> {}
+unsafe impl Sync for SetRef<'_> {}
+
+impl<'a> SetRef<'a> {
+	pub fn id(&self) -> u32 {
+		unsafe { kkdlib_spr_db_spr_set_get_id(self.ptr) }
+	}
+
+	pub fn name(&self) -> String {
+		let ptr = unsafe { kkdlib_spr_db_spr_set_get_name(self.ptr) };
+		let cstr = unsafe { CStr::from_ptr(ptr) };
+		cstr.to_string_lossy().to_string()
+	}
+
+	pub fn file_name(&self) -> String {
+		let ptr = unsafe { kkdlib_spr_db_spr_set_get_file_name(self.ptr) };
+		let cstr = unsafe { CStr::from_ptr(ptr) };
+		cstr.to_string_lossy().to_string()
+	}
+
+	pub fn index(&self) -> u32 {
+		unsafe { kkdlib_spr_db_spr_set_get_index(self.ptr) }
+	}
+
+	// Sprite and texture entries of the set, leaving out any that another set took over
+	pub fn sprites(&self) -> impl Iterator<Item = EntryRef<'a>> + use<'a> {
+		let (id, name) = (self.id(), self.name());
+		let database = self.database;
+		database
+			.sets
+			.iter()
+			.rev()
+			.find(|(_, set)| set.id() == id && set.name() == name)
+			.into_iter()
+			.flat_map(|(_, set)| set.sprites())
+			.filter_map(move |entry| {
+				database
+					.get_spr_by_id(entry.id())
+					.filter(|winner| winner.name() == entry.name())
+			})
+	}
+
+	// Sprite lookups that only see this set, texture entries don't count
+	pub fn get_spr_by_id(&self, id: u32) -> Option<EntryRef<'a>> {
+		self.sprites()
+			.find(|entry| entry.id() == id && !entry.texture())
+	}
+
+	pub fn get_spr_by_name(&self, name: &str) -> Option<EntryRef<'a>> {
+		self.sprites()
+			.find(|entry| entry.name() == name && !entry.texture())
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "pyo3", pyclass(eq))]
+pub enum ConflictKind {
+	SetId,
+	SetName,
+	SpriteId,
+	SpriteName,
+}
+
+// A set or sprite taking part in a conflict
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "pyo3", pyclass(get_all))]
+pub struct Occurrence {
+	// Files are numbered in the order they were added
+	pub file: usize,
+	// Same as name for sets
+	pub set: String,
+	pub id: u32,
+	pub name: String,
+}
+
+// An id or name used more than once across the added files. Names are only reported when they
+// belong to different ids, a set or sprite overriding another with the same id is an id conflict
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "pyo3", pyclass(get_all))]
+pub struct Conflict {
+	pub kind: ConflictKind,
+	pub occurrences: Vec<Occurrence>,
+	// Index into occurrences of the one lookups return, None if it can't be told apart
+	pub winner: Option<usize>,
+}
+
+#[cfg_attr(feature = "pyo3", pyclass)]
+pub struct Database {
+	ptr: *mut c_void,
+	// Copies of every added set and the file it came from, lookups only keep the winners
+	sets: Vec<(usize, file::Set)>,
+	files: usize,
+	// modern, big_endian and is_x of the first file
+	format: Option<(bool, bool, bool)>,
+}
+
+unsafe impl Send for Database {}
+unsafe impl Sync for Database {}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl Database {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new() -> Self {
+		Self {
+			ptr: unsafe { kkdlib_sprite_database_new() },
+			sets: Vec::new(),
+			files: 0,
+			format: None,
+		}
+	}
+
+	pub fn add_file(&mut self, file: &file::Database) {
+		unsafe { kkdlib_sprite_database_add_file(self.ptr, file.ptr) };
+		self.sets
+			.extend(file.sets().map(|set| (self.files, set.cloned())));
+		self.files += 1;
+		self.format
+			.get_or_insert((file.modern(), file.big_endian(), file.is_x()));
+	}
+
+	pub fn conflicts(&self) -> Vec<Conflict> {
+		let sets = self
+			.sets
+			.iter()
+			.map(|(file, set)| Occurrence {
+				file: *file,
+				set: set.name(),
+				id: set.id(),
+				name: set.name(),
+			})
+			.collect::<Vec<_>>();
+		let sprites = self
+			.sets
+			.iter()
+			.flat_map(|(file, set)| {
+				let set_name = set.name();
+				set.sprites().map(move |entry| Occurrence {
+					file: *file,
+					set: set_name.clone(),
+					id: entry.id(),
+					name: entry.name(),
+				})
+			})
+			.collect::<Vec<_>>();
+
+		let mut conflicts = Vec::new();
+		for occurrences in duplicates(&sets, |set| set.id) {
+			let winner = self.get_spr_set_by_id(occurrences[0].id).and_then(|set| {
+				occurrences
+					.iter()
+					.rposition(|other| other.name == set.name())
+			});
+			conflicts.push(Conflict {
+				kind: ConflictKind::SetId,
+				occurrences,
+				winner,
+			});
+		}
+		for occurrences in duplicates(&sets, |set| set.name.clone()) {
+			if occurrences
+				.iter()
+				.all(|other| other.id == occurrences[0].id)
+			{
+				continue;
+			}
+			let winner = self
+				.get_spr_set_by_name(&occurrences[0].name)
+				.and_then(|set| occurrences.iter().rposition(|other| other.id == set.id()));
+			conflicts.push(Conflict {
+				kind: ConflictKind::SetName,
+				occurrences,
+				winner,
+			});
+		}
+		for occurrences in duplicates(&sprites, |sprite| sprite.id) {
+			let winner = self.get_spr_by_id(occurrences[0].id).and_then(|spr| {
+				occurrences
+					.iter()
+					.rposition(|other| other.name == spr.name())
+			});
+			conflicts.push(Conflict {
+				kind: ConflictKind::SpriteId,
+				occurrences,
+				winner,
+			});
+		}
+		for occurrences in duplicates(&sprites, |sprite| sprite.name.clone()) {
+			if occurrences
+				.iter()
+				.all(|other| other.id == occurrences[0].id)
+			{
+				continue;
+			}
+			let winner = self
+				.get_spr_by_name(&occurrences[0].name)
+				.and_then(|spr| occurrences.iter().rposition(|other| other.id == spr.id()));
+			conflicts.push(Conflict {
+				kind: ConflictKind::SpriteName,
+				occurrences,
+				winner,
+			});
+		}
+		conflicts
+	}
+
+	// One file holding the sets and sprites lookups return, each set where its id first appeared.
+	// Sprite indices are kept since they point into the spr files
+	pub fn merge(&self) -> file::Database {
+		let mut sets: Vec<&file::Set> = Vec::new();
+		let mut set_ids = HashMap::new();
+		for (_, set) in &self.sets {
+			let won = self
+				.get_spr_set_by_id(set.id())
+				.is_none_or(|winner| winner.name() == set.name());
+			match set_ids.get(&set.id()) {
+				Some(&index) if won => sets[index] = set,
+				Some(_) => {}
+				None => {
+					set_ids.insert(set.id(), sets.len());
+					sets.push(set);
+				}
+			}
+		}
+
+		// Last winning copy of each sprite id
+		let mut owners = HashMap::new();
+		for (set_index, set) in sets.iter().enumerate() {
+			for (index, entry) in set.sprites().enumerate() {
+				let won = self
+					.get_spr_by_id(entry.id())
+					.is_none_or(|winner| winner.name() == entry.name());
+				if won || !owners.contains_key(&entry.id()) {
+					owners.insert(entry.id(), (set_index, index));
+				}
+			}
+		}
+
+		let mut database = file::Database::new();
+		if let Some((modern, big_endian, is_x)) = self.format {
+			database.set_modern(modern);
+			database.set_big_endian(big_endian);
+			database.set_is_x(is_x);
+		}
+		for (set_index, set) in sets.iter().enumerate() {
+			let mut merged = file::Set::new();
+			merged.set_id(set.id());
+			merged.set_name(&set.name());
+			merged.set_file_name(&set.file_name());
+			for (index, entry) in set.sprites().enumerate() {
+				if owners.get(&entry.id()) == Some(&(set_index, index)) {
+					merged.add_sprite(&entry.cloned());
+				}
+			}
+			database.add_set(&merged);
+		}
+		database
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "get_spr_set_by_name"))]
+	pub fn py_get_spr_set_by_name(&self, name: &str) -> Option<PySet> {
+		self.get_spr_set_by_name(name).map(PySet::from)
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "get_spr_set_by_id"))]
+	pub fn py_get_spr_set_by_id(&self, id: u32) -> Option<PySet> {
+		self.get_spr_set_by_id(id).map(PySet::from)
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "get_spr_set_by_index"))]
+	pub fn py_get_spr_set_by_index(&self, index: u32) -> Option<PySet> {
+		self.get_spr_set_by_index(index).map(PySet::from)
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "get_spr_by_name"))]
+	pub fn py_get_spr_by_name(&self, name: &str) -> Option<PyEntry> {
+		self.get_spr_by_name(name).map(PyEntry::from)
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "get_spr_by_id"))]
+	pub fn py_get_spr_by_id(&self, id: u32) -> Option<PyEntry> {
+		self.get_spr_by_id(id).map(PyEntry::from)
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", getter(sets))]
+	pub fn py_sets(&self) -> Vec<PySet> {
+		self.sets().map(PySet::from).collect()
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", getter(sprites))]
+	pub fn py_sprites(&self) -> Vec<PyEntry> {
+		self.sprites().map(PyEntry::from).collect()
+	}
+
+	#[cfg(feature = "pyo3")]
+	pub fn get_sprites_of_set(&self, set_id: u32) -> Vec<PyEntry> {
+		self.get_spr_set_by_id(set_id)
+			.map(|set| set.sprites().map(PyEntry::from).collect())
+			.unwrap_or_default()
+	}
+
+	#[cfg(feature = "pyo3")]
+	pub fn get_set_of_spr(&self, id: u32) -> Option<PySet> {
+		self.get_spr_by_id(id)?.set().map(PySet::from)
+	}
+}
+
+impl<'a> Database {
+	// Every set lookups can return, in the order they were first added
+	pub fn sets(&'a self) -> impl Iterator<Item = SetRef<'a>> {
+		let mut seen = HashSet::new();
+		self.sets
+			.iter()
+			.filter(move |(_, set)| seen.insert(set.id()))
+			.filter_map(|(_, set)| self.get_spr_set_by_id(set.id()))
+	}
+
+	// Every sprite and texture entry lookups can return, in the order they were first added
+	pub fn sprites(&'a self) -> impl Iterator<Item = EntryRef<'a>> {
+		let mut seen = HashSet::new();
+		self.sets
+			.iter()
+			.flat_map(|(_, set)| set.sprites())
+			.filter(move |entry| seen.insert(entry.id()))
+			.filter_map(|entry| self.get_spr_by_id(entry.id()))
+	}
+
+	pub fn get_spr_set_by_name(&'a self, name: &str) -> Option<SetRef<'a>> {
+		let cstring = CString::new(name).ok()?;
+		let ptr = unsafe { kkdlib_sprite_database_get_spr_set_by_name(self.ptr, cstring.as_ptr()) };
+		if ptr.is_null() || unsafe { kkdlib_spr_db_spr_set_get_id(ptr) } == u32::MAX {
+			None
+		} else {
+			Some(SetRef {
+				ptr,
+				database: self,
+			})
+		}
+	}
+
+	pub fn get_spr_set_by_id(&'a self, id: u32) -> Option<SetRef<'a>> {
+		let ptr = unsafe { kkdlib_sprite_database_get_spr_set_by_id(self.ptr, id) };
+		if ptr.is_null() || unsafe { kkdlib_spr_db_spr_set_get_id(ptr) } == u32::MAX {
+			None
+		} else {
+			Some(SetRef {
+				ptr,
+				database: self,
+			})
+		}
+	}
+
+	pub fn get_spr_set_by_index(&'a self, index: u32) -> Option<SetRef<'a>> {
+		let ptr = unsafe { kkdlib_sprite_database_get_spr_set_by_index(self.ptr, index) };
+		if ptr.is_null() || unsafe { kkdlib_spr_db_spr_set_get_id(ptr) } == u32::MAX {
+			None
+		} else {
+			Some(SetRef {
+				ptr,
+				database: self,
+			})
+		}
+	}
+
+	pub fn get_spr_by_name(&'a self, name: &str) -> Option<EntryRef<'a>> {
+		let cstring = CString::new(name).ok()?;
+		let ptr = unsafe { kkdlib_sprite_database_get_spr_by_name(self.ptr, cstring.as_ptr()) };
+		if ptr.is_null() || unsafe { kkdlib_spr_db_spr_get_id(ptr) } == u32::MAX {
+			None
+		} else {
+			Some(EntryRef {
+				ptr,
+				database: self,
+			})
+		}
+	}
+
+	pub fn get_spr_by_id(&'a self, id: u32) -> Option<EntryRef<'a>> {
+		let ptr = unsafe { kkdlib_sprite_database_get_spr_by_id(self.ptr, id) };
+		if ptr.is_null() || unsafe { kkdlib_spr_db_spr_get_id(ptr) } == u32::MAX {
+			None
+		} else {
+			Some(EntryRef {
+				ptr,
+				database: self,
+			})
+		}
+	}
+}
+
+impl Drop for Database {
+	fn drop(&mut self) {
+		unsafe { kkdlib_sprite_database_delete(self.ptr) };
+	}
+}
+
+// Groups sharing a key with more than one member, in the order the keys first appear
+fn duplicates<K: Hash + Eq>(
+	occurrences: &[Occurrence],
+	key: impl Fn(&Occurrence) -> K,
+) -> Vec<Vec<Occurrence>> {
+	let mut groups: Vec<Vec<Occurrence>> = Vec::new();
+	let mut keys = HashMap::new();
+	for occurrence in occurrences {
+		let index = *keys.entry(key(occurrence)).or_insert_with(|| {
+			groups.push(Vec::new());
+			groups.len() - 1
+		});
+		groups[index].push(occurrence.clone());
+	}
+	groups.retain(|group| group.len() > 1);
+	groups
+}
+
+// Lookups borrow from the database, python gets a snapshot instead
+#[cfg(feature = "pyo3")]
+#[cfg_attr(feature = "pyo3", pyclass(name = "Entry", get_all))]
+pub struct PyEntry {
+	pub id: u32,
+	pub name: String,
+	pub index: u16,
+	pub set_index: u16,
+	pub texture: bool,
+}
+
+#[cfg(feature = "pyo3")]
+impl From<EntryRef<'_>> for PyEntry {
+	fn from(entry: EntryRef<'_>) -> Self {
+		Self {
+			id: entry.id(),
+			name: entry.name(),
+			index: entry.index(),
+			set_index: entry.set_index(),
+			texture: entry.texture(),
+		}
+	}
+}
+
+#[cfg(feature = "pyo3")]
+#[cfg_attr(feature = "pyo3", pyclass(name = "Set", get_all))]
+pub struct PySet {
+	pub id: u32,
+	pub name: String,
+	pub file_name: String,
+	pub index: u32,
+}
+
+#[cfg(feature = "pyo3")]
+impl From<SetRef<'_>> for PySet {
+	fn from(set: SetRef<'_>) -> Self {
+		Self {
+			id: set.id(),
+			name: set.name(),
+			file_name: set.file_name(),
+			index: set.index(),
+		}
+	}
+}
+
+unsafe extern "C" {
+	fn kkdlib_spr_db_spr_get_id(spr: *const c_void) -> u32;
+	fn kkdlib_spr_db_spr_get_name(spr: *const c_void) -> *const c_char;
+	fn kkdlib_spr_db_spr_get_index(spr: *const c_void) -> u16;
+	fn kkdlib_spr_db_spr_get_set_index(spr: *const c_void) -> u16;
+
+	fn kkdlib_spr_db_spr_set_get_id(set: *const c_void) -> u32;
+	fn kkdlib_spr_db_spr_set_get_name(set: *const c_void) -> *const c_char;
+	fn kkdlib_spr_db_spr_set_get_file_name(set: *const c_void) -> *const c_char;
+	fn kkdlib_spr_db_spr_set_get_index(set: *const c_void) -> u32;
+
+	fn kkdlib_sprite_database_new() -> *mut c_void;
+	fn kkdlib_sprite_database_add_file(database: *mut c_void, file: *mut c_void);
+	fn kkdlib_sprite_database_get_spr_set_by_name(
+		database: *mut c_void,
+		name: *const c_char,
+	) -> *const c_void;
+	fn kkdlib_sprite_database_get_spr_set_by_id(
+		database: *mut c_void,
+		set_id: u32,
+	) -> *const c_void;
+	fn kkdlib_sprite_database_get_spr_set_by_index(
+		database: *mut c_void,
+		index: u32,
+	) -> *const c_void;
+	fn kkdlib_sprite_database_get_spr_by_name(
+		database: *mut c_void,
+		name: *const c_char,
+	) -> *const c_void;
+	fn kkdlib_sprite_database_get_spr_by_id(database: *mut c_void, id: u32) -> *const c_void;
+	fn kkdlib_sprite_database_delete(database: *mut c_void);
+}
@@ -0,0 +1,160 @@
+use super::file::{Database, Entry, Set};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// The text form is meant to be written by hand and reviewed, so ids that are just the murmurhash
+// of the name are left out and filled back in when reading. Indices count textures and sprites
+// separately when left out. Set indices always follow the order of the sets
+#[derive(Serialize, Deserialize)]
+struct EntryText {
+	name: String,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	id: Option<u32>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	index: Option<u16>,
+	#[serde(default, skip_serializing_if = "std::ops::Not::not")]
+	texture: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SetText {
+	name: String,
+	file_name: String,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	id: Option<u32>,
+	sprites: Vec<EntryText>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DatabaseText {
+	#[serde(default)]
+	modern: bool,
+	#[serde(default)]
+	big_endian: bool,
+	#[serde(default)]
+	is_x: bool,
+	sets: Vec<SetText>,
+}
+
+// None when it's the one assign_ids would give
+fn explicit_id(id: u32, name: &str) -> Option<u32> {
+	(id != crate::hash::murmurhash(name)).then_some(id)
+}
+
+impl From<&Entry> for EntryText {
+	fn from(entry: &Entry) -> Self {
+		let name = entry.name();
+		Self {
+			id: explicit_id(entry.id(), &name),
+			index: Some(entry.index()),
+			texture: entry.texture(),
+			name,
+		}
+	}
+}
+
+impl From<&Set> for SetText {
+	fn from(set: &Set) -> Self {
+		let name = set.name();
+		Self {
+			id: explicit_id(set.id(), &name),
+			file_name: set.file_name(),
+			sprites: set
+				.sprites()
+				.map(|entry| EntryText::from(&entry.cloned()))
+				.collect(),
+			name,
+		}
+	}
+}
+
+impl From<EntryText> for Entry {
+	fn from(text: EntryText) -> Self {
+		let mut entry = Entry::new();
+		entry.set_name(&text.name);
+		entry.set_texture(text.texture);
+		entry.set_index(text.index.unwrap_or_default());
+		match text.id {
+			Some(id) => entry.set_id(id),
+			None => entry.assign_id(),
+		}
+		entry
+	}
+}
+
+impl From<SetText> for Set {
+	fn from(text: SetText) -> Self {
+		let mut set = Set::new();
+		set.set_name(&text.name);
+		set.set_file_name(&text.file_name);
+		set.set_id(
+			text.id
+				.unwrap_or_else(|| crate::hash::murmurhash(text.name.as_str())),
+		);
+
+		let (mut textures, mut sprites) = (0, 0);
+		for mut sprite in text.sprites {
+			let counter = if sprite.texture {
+				&mut textures
+			} else {
+				&mut sprites
+			};
+			sprite.index = sprite.index.or(Some(*counter));
+			*counter += 1;
+			set.add_sprite(&Entry::from(sprite));
+		}
+		set
+	}
+}
+
+impl Serialize for Entry {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		EntryText::from(self).serialize(serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for Entry {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		EntryText::deserialize(deserializer).map(Entry::from)
+	}
+}
+
+impl Serialize for Set {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		SetText::from(self).serialize(serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for Set {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		SetText::deserialize(deserializer).map(Set::from)
+	}
+}
+
+impl Serialize for Database {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		DatabaseText {
+			modern: self.modern(),
+			big_endian: self.big_endian(),
+			is_x: self.is_x(),
+			sets: self
+				.sets()
+				.map(|set| SetText::from(&set.cloned()))
+				.collect(),
+		}
+		.serialize(serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for Database {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let text = DatabaseText::deserialize(deserializer)?;
+		let mut database = Database::new();
+		database.set_modern(text.modern);
+		database.set_big_endian(text.big_endian);
+		database.set_is_x(text.is_x);
+		for set in text.sets {
+			database.add_set(&Set::from(set));
+		}
+		Ok(database)
+	}
+}
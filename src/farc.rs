@@ -134,6 +134,7 @@ impl Farc {
 		unsafe { kkdlib_farc_get_ft(self.ptr) }
 	}
 
+	#[cfg_attr(feature = "pyo3", setter)]
 	pub fn set_ft(&mut self, ft: bool) {
 		unsafe { kkdlib_farc_set_ft(self.ptr, ft) };
 	}
@@ -214,6 +215,35 @@ impl Farc {
 
 		Some(vec)
 	}
+
+	// Entries are only copied out when asked for, unlike get_files
+	#[cfg(feature = "pyo3")]
+	pub fn names(&self) -> Vec<String> {
+		self.files().map(|file| file.name()).collect()
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __len__(&self) -> usize {
+		unsafe { kkdlib_farc_get_files_size(self.ptr) }
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __contains__(&self, name: &str) -> bool {
+		self.get_file(name).is_some()
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __getitem__(&self, name: &str) -> PyResult<&[u8]> {
+		self.get_file(name)
+			.and_then(|file| file.data())
+			.ok_or_else(|| pyo3::exceptions::PyKeyError::new_err(name.to_string()))
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __iter__(&self, py: Python<'_>) -> PyResult<Py<pyo3::types::PyIterator>> {
+		let names = pyo3::types::PyList::new(py, self.names())?;
+		Ok(names.try_iter()?.unbind())
+	}
 }
 
 // These funcs cannot be used as pymethods
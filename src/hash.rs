@@ -1,5 +1,56 @@
 use std::ffi::*;
 
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+
+#[cfg(feature = "pyo3")]
+#[cfg_attr(feature = "pyo3", pymodule(name = "hash"))]
+pub(crate) mod hash_module {
+	use pyo3::prelude::*;
+
+	// Names are usually hashed straight from a str, so accept either
+	#[derive(FromPyObject)]
+	enum Data {
+		Bytes(pyo3::pybacked::PyBackedBytes),
+		Str(String),
+	}
+
+	impl From<Data> for Vec<u8> {
+		fn from(data: Data) -> Self {
+			match data {
+				Data::Bytes(bytes) => bytes.to_vec(),
+				Data::Str(string) => string.into_bytes(),
+			}
+		}
+	}
+
+	#[pyfunction]
+	fn fnv1a64m(data: Data) -> u64 {
+		super::fnv1a64m(data)
+	}
+
+	#[pyfunction]
+	fn murmurhash(data: Data) -> u32 {
+		super::murmurhash(data)
+	}
+
+	#[pyfunction]
+	fn crc16_ccitt(data: Data) -> u16 {
+		super::crc16_ccitt(data)
+	}
+
+	#[pyfunction]
+	fn xxh3_64bits(data: Data) -> u64 {
+		super::xxh3_64bits(data)
+	}
+
+	#[pyfunction]
+	#[pyo3(signature = (data, adler = 1))]
+	fn adler32(data: Data, adler: u32) -> u32 {
+		super::adler32(data, adler)
+	}
+}
+
 pub fn fnv1a64m<T: Into<Vec<u8>>>(data: T) -> u64 {
 	let data = data.into();
 	unsafe { kkdlib_hash_fnv1a64m(data.as_ptr() as *const c_void, data.len()) }
@@ -0,0 +1,535 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+
+mod writer;
+
+pub use writer::{KeyValWriter, WriterScope};
+
+#[cfg(feature = "pyo3")]
+#[cfg_attr(feature = "pyo3", pymodule(name = "key_val"))]
+pub(crate) mod key_val_module {
+	#[pymodule_export]
+	use super::KeyVal;
+	#[pymodule_export]
+	use super::KeyValNode;
+	#[pymodule_export]
+	use super::KeyValWriter;
+	#[pymodule_export]
+	use super::Scope;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyValError {
+	Missing {
+		key: String,
+	},
+	Invalid {
+		key: String,
+		value: String,
+		expected: &'static str,
+	},
+}
+
+impl std::fmt::Display for KeyValError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Missing { key } => write!(f, "key {key:?} is missing"),
+			Self::Invalid {
+				key,
+				value,
+				expected,
+			} => write!(f, "key {key:?} has value {value:?}, expected {expected}"),
+		}
+	}
+}
+
+impl std::error::Error for KeyValError {}
+
+#[cfg(feature = "pyo3")]
+impl From<KeyValError> for PyErr {
+	fn from(err: KeyValError) -> Self {
+		match err {
+			KeyValError::Missing { .. } => pyo3::exceptions::PyKeyError::new_err(err.to_string()),
+			KeyValError::Invalid { .. } => pyo3::exceptions::PyValueError::new_err(err.to_string()),
+		}
+	}
+}
+
+// One dotted component of a key, `a.b.c=1` is stored as a -> b -> c with the value on c
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "pyo3", pyclass)]
+pub struct KeyValNode {
+	value: Option<String>,
+	// In the order they first appear in the text
+	children: Vec<(String, KeyValNode)>,
+	index: HashMap<String, usize>,
+}
+
+impl PartialEq for KeyValNode {
+	fn eq(&self, other: &Self) -> bool {
+		self.value == other.value && self.children == other.children
+	}
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl KeyValNode {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	#[cfg_attr(feature = "pyo3", staticmethod)]
+	pub fn parse(data: &str) -> Self {
+		let mut node = Self::new();
+		node.parse_into(data);
+		node
+	}
+
+	// Lines without a `=` and `#` comments are skipped, later lines overwrite earlier ones
+	pub fn parse_into(&mut self, data: &str) {
+		for line in data.lines() {
+			let line = line.trim_end_matches('\r');
+			if line.starts_with('#') {
+				continue;
+			}
+			let Some((key, value)) = line.split_once('=') else {
+				continue;
+			};
+			let key = key.trim();
+			if key.is_empty() {
+				continue;
+			}
+			self.insert(key, value);
+		}
+	}
+
+	pub fn insert(&mut self, key: &str, value: &str) {
+		let mut node = self;
+		for part in key.split('.') {
+			node = node.child_or_insert(part);
+		}
+		node.value = Some(value.to_string());
+	}
+
+	#[cfg_attr(feature = "pyo3", getter)]
+	pub fn value(&self) -> Option<&str> {
+		self.value.as_deref()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.value.is_none() && self.children.is_empty()
+	}
+
+	pub fn has_key(&self, key: &str) -> bool {
+		self.get(key).is_some()
+	}
+
+	// Removes `key` and everything under it, parents left without values or children go too
+	pub fn remove(&mut self, key: &str) -> Option<Self> {
+		let (first, rest) = match key.split_once('.') {
+			Some((first, rest)) => (first, Some(rest)),
+			None => (key, None),
+		};
+		let i = *self.index.get(first)?;
+		let Some(rest) = rest else {
+			return Some(self.remove_child(i));
+		};
+
+		let child = &mut self.children[i].1;
+		let removed = child.remove(rest)?;
+		if child.is_empty() {
+			self.remove_child(i);
+		}
+		Some(removed)
+	}
+
+	// Takes the value at `key` but leaves anything nested under it
+	pub fn take_value(&mut self, key: &str) -> Option<String> {
+		let node = self.get_mut(key)?;
+		let value = node.value.take();
+		if node.is_empty() {
+			self.remove(key);
+		}
+		value
+	}
+
+	// The number of items in `xxx.length`, or the run of numbered children starting at 0 if
+	// there's no length key
+	pub fn length(&self) -> Option<usize> {
+		if let Some(length) = self.child("length").and_then(|length| length.value()) {
+			return length.parse().ok();
+		}
+		let count = (0..)
+			.take_while(|i| self.child(&i.to_string()).is_some())
+			.count();
+		(count > 0).then_some(count)
+	}
+
+	pub fn read_bool(&self, key: &str) -> Result<bool, KeyValError> {
+		let value = self.read_str(key)?;
+		match value {
+			"true" => Ok(true),
+			"false" => Ok(false),
+			_ => int_prefix(value)
+				.map(|int| int != 0)
+				.ok_or_else(|| invalid(key, value, "a bool")),
+		}
+	}
+
+	pub fn read_f32(&self, key: &str) -> Result<f32, KeyValError> {
+		let value = self.read_str(key)?;
+		float_prefix(value).ok_or_else(|| invalid(key, value, "a float"))
+	}
+
+	pub fn read_i32(&self, key: &str) -> Result<i32, KeyValError> {
+		let value = self.read_str(key)?;
+		int_prefix(value)
+			.and_then(|int| i32::try_from(int).ok())
+			.ok_or_else(|| invalid(key, value, "an i32"))
+	}
+
+	pub fn read_u32(&self, key: &str) -> Result<u32, KeyValError> {
+		let value = self.read_str(key)?;
+		int_prefix(value)
+			.and_then(|int| u32::try_from(int).ok())
+			.ok_or_else(|| invalid(key, value, "a u32"))
+	}
+
+	pub fn read_str(&self, key: &str) -> Result<&str, KeyValError> {
+		self.get(key)
+			.and_then(|node| node.value())
+			.ok_or_else(|| KeyValError::Missing {
+				key: key.to_string(),
+			})
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[pyo3(name = "get")]
+	fn py_get(&self, key: &str) -> Option<Self> {
+		self.get(key).cloned()
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[pyo3(name = "keys")]
+	fn py_keys(&self) -> Vec<String> {
+		self.keys().map(String::from).collect()
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[pyo3(name = "children")]
+	fn py_children(&self) -> Vec<(String, Self)> {
+		self.children
+			.iter()
+			.map(|(key, node)| (key.clone(), node.clone()))
+			.collect()
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[pyo3(name = "items")]
+	fn py_items(&self) -> Vec<Option<Self>> {
+		self.items().map(|item| item.cloned()).collect()
+	}
+}
+
+impl KeyValNode {
+	fn child_or_insert(&mut self, key: &str) -> &mut Self {
+		let i = match self.index.get(key) {
+			Some(i) => *i,
+			None => {
+				self.index.insert(key.to_string(), self.children.len());
+				self.children.push((key.to_string(), Self::new()));
+				self.children.len() - 1
+			}
+		};
+		&mut self.children[i].1
+	}
+
+	fn remove_child(&mut self, i: usize) -> Self {
+		let (key, node) = self.children.remove(i);
+		self.index.remove(&key);
+		for (j, (key, _)) in self.children.iter().enumerate().skip(i) {
+			self.index.insert(key.clone(), j);
+		}
+		node
+	}
+
+	pub fn child(&self, key: &str) -> Option<&Self> {
+		self.index.get(key).map(|i| &self.children[*i].1)
+	}
+
+	pub fn get(&self, key: &str) -> Option<&Self> {
+		key.split('.').try_fold(self, |node, part| node.child(part))
+	}
+
+	pub fn get_mut(&mut self, key: &str) -> Option<&mut Self> {
+		key.split('.').try_fold(self, |node, part| {
+			let i = *node.index.get(part)?;
+			Some(&mut node.children[i].1)
+		})
+	}
+
+	pub fn keys(&self) -> impl Iterator<Item = &str> {
+		self.children.iter().map(|(key, _)| key.as_str())
+	}
+
+	pub fn children(&self) -> impl Iterator<Item = (&str, &Self)> {
+		self.children.iter().map(|(key, node)| (key.as_str(), node))
+	}
+
+	// Array items up to `length`, gaps in the numbering come out as None
+	pub fn items(&self) -> impl Iterator<Item = Option<&Self>> {
+		(0..self.length().unwrap_or(0)).map(|i| self.child(&i.to_string()))
+	}
+}
+
+// The game reads numbers with atoi and atof, which take the number at the start of the value and
+// ignore the rest, so `1.0` reads as the int 1 and `12abc` as 12. Values that don't start with a
+// number at all are still errors
+fn int_prefix(value: &str) -> Option<i64> {
+	let value = value.trim_start();
+	let digits = value.strip_prefix(['+', '-']).unwrap_or(value);
+	let len = value.len() - digits.len() + digits.bytes().take_while(u8::is_ascii_digit).count();
+	value[..len].parse().ok()
+}
+
+fn float_prefix(value: &str) -> Option<f32> {
+	let value = value.trim_start();
+	value
+		.char_indices()
+		.map(|(i, c)| i + c.len_utf8())
+		.rev()
+		.find_map(|end| value[..end].parse().ok())
+}
+
+fn invalid(key: &str, value: &str, expected: &'static str) -> KeyValError {
+	KeyValError::Invalid {
+		key: key.to_string(),
+		value: value.to_string(),
+		expected,
+	}
+}
+
+#[cfg_attr(feature = "pyo3", pyclass)]
+pub struct KeyVal {
+	root: KeyValNode,
+	// Key parts opened with open_scope or python's `with kv.scope(key):`, reads are relative to
+	// the node they lead to
+	scope: Mutex<Vec<String>>,
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl KeyVal {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new() -> Self {
+		Self {
+			root: KeyValNode::new(),
+			scope: Mutex::new(Vec::new()),
+		}
+	}
+
+	pub fn parse(&mut self, data: &str) {
+		self.root.parse_into(data);
+	}
+
+	#[cfg_attr(feature = "pyo3", staticmethod)]
+	pub fn from_data(data: &str) -> Self {
+		let mut kv = Self::new();
+		kv.parse(data);
+		kv
+	}
+
+	// Python can't hold on to a ScopeGuard, so the scope is opened by `with kv.scope(key):`
+	// and closed again when the block exits
+	#[cfg(feature = "pyo3")]
+	pub fn scope(slf: Py<Self>, key: ScopeKey) -> Scope {
+		Scope {
+			kv: slf,
+			key,
+			open: false,
+		}
+	}
+
+	pub fn has_key(&self, key: &str) -> bool {
+		self.node().has_key(key)
+	}
+
+	pub fn read_bool(&self, key: &str) -> Option<bool> {
+		self.node().read_bool(key).ok()
+	}
+
+	pub fn read_f32(&self, key: &str) -> Option<f32> {
+		self.node().read_f32(key).ok()
+	}
+
+	pub fn read_i32(&self, key: &str) -> Option<i32> {
+		self.node().read_i32(key).ok()
+	}
+
+	pub fn read_u32(&self, key: &str) -> Option<u32> {
+		self.node().read_u32(key).ok()
+	}
+
+	pub fn read_str(&self, key: &str) -> Option<&str> {
+		self.node().read_str(key).ok()
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[getter]
+	#[pyo3(name = "root")]
+	fn py_root(&self) -> KeyValNode {
+		self.root.clone()
+	}
+}
+
+impl KeyVal {
+	pub fn root(&self) -> &KeyValNode {
+		&self.root
+	}
+
+	// Scopes are only pushed once their node exists and parsing never removes nodes
+	fn node(&self) -> &KeyValNode {
+		let scope = self.scope.lock().unwrap();
+		scope
+			.iter()
+			.fold(&self.root, |node, key| node.child(key).unwrap())
+	}
+
+	// Moves the cursor into `key` until the guard is dropped, reads through the KeyVal and
+	// through any guard are relative to the innermost open scope
+	pub fn open_scope<'a>(&'a self, key: &str) -> Option<ScopeGuard<'a>> {
+		self.node().get(key)?;
+		let mut scope = self.scope.lock().unwrap();
+		let depth = scope.len();
+		scope.extend(key.split('.').map(String::from));
+		Some(ScopeGuard { kv: self, depth })
+	}
+
+	pub fn open_scope_num<'a>(&'a self, i: u32) -> Option<ScopeGuard<'a>> {
+		self.open_scope(&i.to_string())
+	}
+}
+
+impl Default for KeyVal {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+// Closes its scope again when dropped, like the cursor of the game's own key_val reader
+pub struct ScopeGuard<'a> {
+	kv: &'a KeyVal,
+	// Length of the cursor before this scope was opened
+	depth: usize,
+}
+
+impl<'a> ScopeGuard<'a> {
+	// The node the cursor is at now
+	pub fn node(&self) -> &'a KeyValNode {
+		self.kv.node()
+	}
+
+	pub fn open_scope(&self, key: &str) -> Option<ScopeGuard<'a>> {
+		self.kv.open_scope(key)
+	}
+
+	pub fn open_scope_num(&self, i: u32) -> Option<ScopeGuard<'a>> {
+		self.kv.open_scope_num(i)
+	}
+
+	pub fn has_key(&self, key: &str) -> bool {
+		self.kv.has_key(key)
+	}
+
+	pub fn read_bool(&self, key: &str) -> Option<bool> {
+		self.kv.read_bool(key)
+	}
+
+	pub fn read_f32(&self, key: &str) -> Option<f32> {
+		self.kv.read_f32(key)
+	}
+
+	pub fn read_i32(&self, key: &str) -> Option<i32> {
+		self.kv.read_i32(key)
+	}
+
+	pub fn read_u32(&self, key: &str) -> Option<u32> {
+		self.kv.read_u32(key)
+	}
+
+	pub fn read_str(&self, key: &str) -> Option<&'a str> {
+		self.kv.read_str(key)
+	}
+}
+
+impl Drop for ScopeGuard<'_> {
+	fn drop(&mut self) {
+		self.kv.scope.lock().unwrap().truncate(self.depth);
+	}
+}
+
+#[cfg(feature = "pyo3")]
+#[derive(FromPyObject)]
+pub enum ScopeKey {
+	Key(String),
+	Num(u32),
+}
+
+#[cfg(feature = "pyo3")]
+#[cfg_attr(feature = "pyo3", pyclass(unsendable))]
+pub struct Scope {
+	kv: Py<KeyVal>,
+	key: ScopeKey,
+	open: bool,
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl Scope {
+	fn __enter__(&mut self, py: Python<'_>) -> PyResult<Py<KeyVal>> {
+		if self.open {
+			return Err(pyo3::exceptions::PyRuntimeError::new_err(
+				"scope is already open",
+			));
+		}
+
+		let key = match &self.key {
+			ScopeKey::Key(key) => key.clone(),
+			ScopeKey::Num(i) => i.to_string(),
+		};
+		let kv = self.kv.borrow(py);
+		if kv.node().get(&key).is_none() {
+			return Err(match &self.key {
+				ScopeKey::Key(key) => pyo3::exceptions::PyKeyError::new_err(key.clone()),
+				ScopeKey::Num(i) => pyo3::exceptions::PyKeyError::new_err(*i),
+			});
+		}
+		kv.scope
+			.lock()
+			.unwrap()
+			.extend(key.split('.').map(String::from));
+
+		self.open = true;
+		Ok(self.kv.clone_ref(py))
+	}
+
+	#[pyo3(signature = (*_args))]
+	fn __exit__(&mut self, py: Python<'_>, _args: &Bound<'_, pyo3::types::PyTuple>) -> bool {
+		if self.open {
+			let depth = match &self.key {
+				ScopeKey::Key(key) => key.split('.').count(),
+				ScopeKey::Num(_) => 1,
+			};
+			let kv = self.kv.borrow(py);
+			let mut scope = kv.scope.lock().unwrap();
+			let len = scope.len() - depth;
+			scope.truncate(len);
+			self.open = false;
+		}
+		false
+	}
+}
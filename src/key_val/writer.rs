@@ -0,0 +1,190 @@
+use super::*;
+use std::collections::BTreeMap;
+
+// Builds key_val text, keys are kept in byte order which is how the game sorts them when loading
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "pyo3", pyclass)]
+pub struct KeyValWriter {
+	values: BTreeMap<String, String>,
+}
+
+#[cfg_eval]
+#[cfg_attr(feature = "pyo3", pymethods)]
+impl KeyValWriter {
+	#[cfg_attr(feature = "pyo3", new)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.values.is_empty()
+	}
+
+	// Writing a key twice keeps the last value
+	pub fn write_str(&mut self, key: &str, value: &str) {
+		self.values.insert(key.to_string(), value.to_string());
+	}
+
+	// The game reads bools with atoi so they're written as numbers
+	pub fn write_bool(&mut self, key: &str, value: bool) {
+		self.write_str(key, if value { "1" } else { "0" });
+	}
+
+	pub fn write_f32(&mut self, key: &str, value: f32) {
+		self.write_str(key, &format_f32(value));
+	}
+
+	pub fn write_i32(&mut self, key: &str, value: i32) {
+		self.write_str(key, &value.to_string());
+	}
+
+	pub fn write_u32(&mut self, key: &str, value: u32) {
+		self.write_str(key, &value.to_string());
+	}
+
+	pub fn write_length(&mut self, key: &str, length: usize) {
+		self.write_str(&join(key, "length"), &length.to_string());
+	}
+
+	// Every value under `node`, an empty key writes it at the top level
+	pub fn write_node(&mut self, key: &str, node: &KeyValNode) {
+		if let Some(value) = node.value() {
+			self.write_str(key, value);
+		}
+		for (child_key, child) in node.children() {
+			self.write_node(&join(key, child_key), child);
+		}
+	}
+
+	pub fn remove(&mut self, key: &str) -> Option<String> {
+		self.values.remove(key)
+	}
+
+	#[cfg(feature = "pyo3")]
+	fn __str__(&self) -> String {
+		self.to_string()
+	}
+}
+
+impl KeyValWriter {
+	pub fn open_scope<'a>(&'a mut self, key: &str) -> WriterScope<'a> {
+		WriterScope {
+			writer: self,
+			prefix: key.to_string(),
+		}
+	}
+
+	pub fn open_scope_num<'a>(&'a mut self, i: usize) -> WriterScope<'a> {
+		self.open_scope(&i.to_string())
+	}
+
+	pub fn write_array<T>(
+		&mut self,
+		key: &str,
+		items: impl IntoIterator<Item = T>,
+		f: impl FnMut(&mut WriterScope<'_>, T),
+	) {
+		self.open_scope("").write_array(key, items, f);
+	}
+}
+
+impl std::fmt::Display for KeyValWriter {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		for (key, value) in &self.values {
+			writeln!(f, "{key}={value}")?;
+		}
+		Ok(())
+	}
+}
+
+impl From<&KeyValNode> for KeyValWriter {
+	fn from(node: &KeyValNode) -> Self {
+		let mut writer = Self::new();
+		writer.write_node("", node);
+		writer
+	}
+}
+
+// Keys written through a scope are relative to its prefix, like reads through a ScopeGuard
+pub struct WriterScope<'a> {
+	writer: &'a mut KeyValWriter,
+	prefix: String,
+}
+
+impl WriterScope<'_> {
+	pub fn open_scope<'a>(&'a mut self, key: &str) -> WriterScope<'a> {
+		WriterScope {
+			prefix: join(&self.prefix, key),
+			writer: self.writer,
+		}
+	}
+
+	pub fn open_scope_num<'a>(&'a mut self, i: usize) -> WriterScope<'a> {
+		self.open_scope(&i.to_string())
+	}
+
+	pub fn write_str(&mut self, key: &str, value: &str) {
+		self.writer.write_str(&join(&self.prefix, key), value);
+	}
+
+	pub fn write_bool(&mut self, key: &str, value: bool) {
+		self.writer.write_bool(&join(&self.prefix, key), value);
+	}
+
+	pub fn write_f32(&mut self, key: &str, value: f32) {
+		self.writer.write_f32(&join(&self.prefix, key), value);
+	}
+
+	pub fn write_i32(&mut self, key: &str, value: i32) {
+		self.writer.write_i32(&join(&self.prefix, key), value);
+	}
+
+	pub fn write_u32(&mut self, key: &str, value: u32) {
+		self.writer.write_u32(&join(&self.prefix, key), value);
+	}
+
+	pub fn write_length(&mut self, key: &str, length: usize) {
+		self.writer.write_length(&join(&self.prefix, key), length);
+	}
+
+	pub fn write_node(&mut self, key: &str, node: &KeyValNode) {
+		self.writer.write_node(&join(&self.prefix, key), node);
+	}
+
+	// Writes each item under `key.<i>` followed by `key.length`, nothing is written for an
+	// empty array since the game treats a missing length as 0
+	pub fn write_array<T>(
+		&mut self,
+		key: &str,
+		items: impl IntoIterator<Item = T>,
+		mut f: impl FnMut(&mut WriterScope<'_>, T),
+	) {
+		let mut scope = self.open_scope(key);
+		let mut length = 0;
+		for item in items {
+			f(&mut scope.open_scope_num(length), item);
+			length += 1;
+		}
+		if length > 0 {
+			scope.write_length("", length);
+		}
+	}
+}
+
+fn join(prefix: &str, key: &str) -> String {
+	match (prefix.is_empty(), key.is_empty()) {
+		(true, _) => key.to_string(),
+		(_, true) => prefix.to_string(),
+		_ => format!("{prefix}.{key}"),
+	}
+}
+
+// Rust's shortest round trip formatting never uses exponents so atof reads it back exactly,
+// whole numbers keep a decimal point so they still read as floats
+fn format_f32(value: f32) -> String {
+	if value.is_finite() && value.fract() == 0.0 {
+		format!("{value:.1}")
+	} else {
+		value.to_string()
+	}
+}
@@ -6,6 +6,8 @@ extern crate libdeflate_sys;
 pub mod database;
 
 pub mod aet;
+#[cfg(feature = "pyo3")]
+pub mod buffer;
 pub mod farc;
 pub mod hash;
 pub mod key_val;
@@ -18,9 +20,19 @@ use pyo3::prelude::*;
 #[cfg(feature = "pyo3")]
 #[cfg_attr(feature = "pyo3", pymodule)]
 mod kkdlib {
+	#[pymodule_export]
+	use super::aet::aet_module;
+	#[pymodule_export]
+	use super::buffer::Buffer;
+	#[pymodule_export]
+	use super::database::database_module;
 	#[pymodule_export]
 	use super::farc::farc_module;
 	#[pymodule_export]
+	use super::hash::hash_module;
+	#[pymodule_export]
+	use super::key_val::key_val_module;
+	#[pymodule_export]
 	use super::spr::spr_module;
 	#[pymodule_export]
 	use super::txp::txp_module;
@@ -44,6 +56,40 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn spr_convert_resolution() {
+		let mut txp = txp::Set::new();
+		for format in [txp::Format::RGBA8, txp::Format::BC1] {
+			let mut texture = txp::Texture::new();
+			texture.set_has_cube_map(false);
+			texture.set_array_size(1);
+			texture.set_mipmaps_count(2);
+			for size in [30, 15] {
+				let data = vec![255; size as usize * size as usize * 4];
+				texture.add_mipmap(&txp::Mipmap::from_rgba(size, size, &data, format).unwrap());
+			}
+			txp.add_file(&texture);
+		}
+		let mut set = spr::Set::new();
+		set.set_txp(&txp, vec![String::from("RGBA"), String::from("BC1")]);
+
+		// 30 * 1.5 is 45, only the BC1 texture gets padded up to 48
+		let set = set
+			.convert_resolution(spr::ResolutionMode::HD, spr::ResolutionMode::FHD)
+			.unwrap();
+		let textures = set.textures().map(|(name, texture)| {
+			let mips = texture.mipmaps().map(|mip| (mip.width(), mip.height()));
+			(name, texture.mipmaps_count(), mips.collect::<Vec<_>>())
+		});
+		assert_eq!(
+			textures.collect::<Vec<_>>(),
+			[
+				(String::from("RGBA"), 2, vec![(45, 45), (22, 22)]),
+				(String::from("BC1"), 2, vec![(48, 48), (24, 24)]),
+			]
+		);
+	}
+
 	#[test]
 	fn spr_db() {
 		let data = std::fs::read("/home/vixen/Desktop/mod_spr_db.bin").unwrap();
@@ -64,27 +110,281 @@ mod tests {
 		dbg!(database.get_spr_set_by_id(54691697).is_some());
 	}
 
+	#[test]
+	fn spr_db_edit() {
+		let mut set = database::sprite::file::Set::new();
+		set.set_name("SPR_TEST");
+		set.set_file_name("spr_test.bin");
+		for (name, texture) in [
+			("SPRTEX_TEST_0", true),
+			("SPR_TEST_A", false),
+			("SPR_TEST_B", false),
+			("SPRTEX_TEST_1", true),
+		] {
+			let mut entry = database::sprite::file::Entry::new();
+			entry.set_name(name);
+			entry.set_texture(texture);
+			set.add_sprite(&entry);
+		}
+		set.renumber();
+		set.assign_ids();
+		assert_eq!(set.id(), hash::murmurhash("SPR_TEST"));
+		let indices = set.sprites().map(|entry| entry.index()).collect::<Vec<_>>();
+		assert_eq!(indices, [0, 0, 1, 1]);
+
+		let removed = set.remove_sprite(1).unwrap();
+		assert_eq!(removed.name(), "SPR_TEST_A");
+		let sprite = set.sprites().nth(1).unwrap();
+		assert_eq!((sprite.name().as_str(), sprite.index()), ("SPR_TEST_B", 0));
+		assert_eq!(sprite.id(), hash::murmurhash("SPR_TEST_B"));
+
+		set.sprite_mut(0).unwrap().set_name("SPRTEX_TEST_2");
+		assert_eq!(set.sprites().next().unwrap().name(), "SPRTEX_TEST_2");
+
+		let mut file = database::sprite::file::Database::new();
+		file.add_set(&set);
+		file.add_set(&set);
+		file.remove_set(0).unwrap();
+		assert_eq!(file.sets().next().unwrap().index(), 0);
+		assert!(file.remove_set(1).is_none());
+	}
+
+	#[test]
+	fn spr_db_from_spr_set() {
+		let mut spr_set = spr::Set::new();
+		spr_set.add_spr(&spr::Info::new(), "BG");
+		spr_set.add_spr(&spr::Info::new(), "LOGO");
+
+		let set = database::sprite::file::Set::from_spr_set(&spr_set, "TEST", "spr_test.bin");
+		assert_eq!(set.name(), "SPR_TEST");
+		assert_eq!(set.id(), hash::murmurhash("SPR_TEST"));
+		let sprites = set
+			.sprites()
+			.map(|entry| (entry.name(), entry.index(), entry.texture()))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			sprites,
+			[
+				("SPR_TEST_BG".to_string(), 0, false),
+				("SPR_TEST_LOGO".to_string(), 1, false)
+			]
+		);
+	}
+
+	#[test]
+	fn spr_db_conflicts() {
+		let file = |sprites: &[&str]| {
+			let mut set = database::sprite::file::Set::new();
+			set.set_name("SPR_TEST");
+			for name in sprites {
+				let mut entry = database::sprite::file::Entry::new();
+				entry.set_name(name);
+				set.add_sprite(&entry);
+			}
+			set.renumber();
+			set.assign_ids();
+			let mut file = database::sprite::file::Database::new();
+			file.add_set(&set);
+			file
+		};
+		let base = file(&["SPR_TEST_A", "SPR_TEST_B"]);
+		let the_mod = file(&["SPR_TEST_B", "SPR_TEST_C"]);
+
+		let differences = database::sprite::file::diff(&base, &the_mod);
+		let changes = differences
+			.iter()
+			.map(|difference| (difference.change, difference.sprite.as_deref()))
+			.collect::<Vec<_>>();
+		use database::sprite::file::Change;
+		assert_eq!(
+			changes,
+			[
+				(Change::Removed, Some("SPR_TEST_A")),
+				(Change::Changed, Some("SPR_TEST_B")),
+				(Change::Added, Some("SPR_TEST_C")),
+			]
+		);
+
+		let mut database = database::sprite::Database::new();
+		database.add_file(&base);
+		database.add_file(&the_mod);
+		let conflicts = database.conflicts();
+		let kinds = conflicts
+			.iter()
+			.map(|conflict| conflict.kind)
+			.collect::<Vec<_>>();
+		use database::sprite::ConflictKind;
+		assert_eq!(kinds, [ConflictKind::SetId, ConflictKind::SpriteId]);
+		assert_eq!(conflicts[1].occurrences[0].name, "SPR_TEST_B");
+		assert!(conflicts[1].winner.is_some());
+
+		let merged = database.merge();
+		assert_eq!(merged.sets().count(), 1);
+
+		assert_eq!(database.sets().count(), 1);
+		assert_eq!(database.sprites().count(), 3);
+		let set = database.sets().next().unwrap();
+		assert_eq!(set.sprites().count(), 2);
+		let entry = database.get_spr_by_name("SPR_TEST_C").unwrap();
+		assert_eq!(entry.set().unwrap().id(), set.id());
+		assert!(!entry.texture());
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn spr_db_serde() {
+		let text = r#"{
+			"sets": [{
+				"name": "SPR_TEST",
+				"file_name": "spr_test.bin",
+				"sprites": [
+					{ "name": "SPRTEX_TEST_0", "texture": true },
+					{ "name": "SPR_TEST_A" },
+					{ "name": "SPR_TEST_B", "id": 7 }
+				]
+			}]
+		}"#;
+		let file: database::sprite::file::Database = serde_json::from_str(text).unwrap();
+		let set = file.sets().next().unwrap();
+		assert_eq!(set.id(), hash::murmurhash("SPR_TEST"));
+		let sprites = set
+			.sprites()
+			.map(|entry| (entry.id(), entry.index(), entry.texture()))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			sprites,
+			[
+				(hash::murmurhash("SPRTEX_TEST_0"), 0, true),
+				(hash::murmurhash("SPR_TEST_A"), 0, false),
+				(7, 1, false),
+			]
+		);
+
+		let json = serde_json::to_string(&file).unwrap();
+		let read: database::sprite::file::Database = serde_json::from_str(&json).unwrap();
+		assert_eq!(read.to_buf(), file.to_buf());
+	}
+
+	#[test]
+	fn aet_validate_sources() {
+		let spr_set = spr::Set::new();
+		let mut spr_db = database::sprite::file::Database::new();
+		let mut entries = Vec::new();
+		for (id, set_name, sprites) in [
+			(1, "TEST", [("SPR_TEST_A", false), ("SPRTEX_TEST_T", true)]),
+			(
+				2,
+				"OTHER",
+				[("SPR_OTHER_C", false), ("SPRTEX_OTHER_T", true)],
+			),
+		] {
+			let file_name = format!("spr_{}.bin", set_name.to_lowercase());
+			let mut set = database::sprite::file::Set::from_spr_set(&spr_set, set_name, &file_name);
+			set.set_id(id);
+			for (name, texture) in sprites {
+				let mut entry = database::sprite::file::Entry::new();
+				entry.set_name(name);
+				entry.set_texture(texture);
+				entry.assign_id();
+				set.add_sprite(&entry);
+				entries.push(entry);
+			}
+			spr_db.add_set(&set);
+		}
+		let mut database = database::sprite::Database::new();
+		database.add_file(&spr_db);
+
+		// Only set 1 is drawn from, SPR_OTHER_C is found but in the other set
+		let mut scene = scene("validate");
+		scene.videos.push(video(
+			16,
+			16,
+			&[
+				("SPR_TEST_A", 1),
+				("SPR_TEST_B", 2),
+				("SPR_OTHER_C", 3),
+				("SPRTEX_TEST_T", 0),
+			],
+		));
+		let mut aet_set = aet::Set::new();
+		aet_set.scenes.push(scene);
+
+		let issues = aet_set
+			.validate_sources(&database, 1, Some(&spr_set))
+			.into_iter()
+			.map(|issue| (issue.source, issue.issue))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			issues,
+			[
+				(
+					0,
+					aet::Issue::StaleId {
+						expected: entries[0].id()
+					}
+				),
+				(0, aet::Issue::NotInSet { index: 0 }),
+				(1, aet::Issue::Missing()),
+				(
+					2,
+					aet::Issue::OtherSet {
+						set: "SPR_OTHER".to_string()
+					}
+				),
+				(3, aet::Issue::Texture()),
+			]
+		);
+
+		assert_eq!(aet_set.fix_source_ids(&database, 1), 1);
+		let sources = &aet_set.scenes[0].videos[0].sources;
+		assert_eq!(sources[0].id, entries[0].id());
+		assert_eq!(sources[2].id, 3);
+	}
+
+	#[test]
+	fn aet_db() {
+		let mut scene = database::aet::file::Scene::new();
+		scene.set_id(2);
+		scene.set_name("AET_GAM_CMN_MAIN");
+		let mut set = database::aet::file::Set::new();
+		set.set_id(1);
+		set.set_name("AET_GAM_CMN");
+		set.set_file_name("aet_gam_cmn.bin");
+		set.set_sprite_set_id(3);
+		set.add_scene(&scene);
+		let mut file = database::aet::file::Database::new();
+		file.add_set(&set);
+
+		let data = file.to_buf().unwrap();
+		let file = database::aet::file::Database::from_buf(&data, false);
+		assert_eq!(file.sets().count(), 1);
+
+		let mut database = database::aet::Database::new();
+		database.add_file(&file);
+		let set = database.get_aet_set_by_name("AET_GAM_CMN").unwrap();
+		assert_eq!(set.id(), 1);
+		assert_eq!(set.sprite_set_id(), 3);
+		let scene = database.get_aet_by_id(2).unwrap();
+		assert_eq!(scene.name(), "AET_GAM_CMN_MAIN");
+		assert!(database.get_aet_by_name("AET_GAM_CMN_MAIN").is_some());
+		assert!(database.get_aet_set_by_id(4).is_none());
+	}
+
 	#[test]
 	fn aet() {
 		let data = std::fs::read("/games/SteamLibrary/steamapps/common/Hatsune Miku Project DIVA Mega Mix Plus/mods-testing/Template Mod/rom_steam/rom/2d/aet_gam_cmn.bak.bin").unwrap();
-		let file = aet::Set::from_buf(&data, false);
+		let file = aet::Set::from_buf(&data, false).unwrap();
 
-		fn print_audios(comp: &aet::Composition) {
+		let scene = &file.scenes[0];
+		for comp in std::iter::once(&scene.root).chain(scene.comps.iter()) {
 			for layer in &comp.layers {
-				let layer = layer.try_lock().unwrap();
-				match &layer.item {
-					aet::Item::None => {}
-					aet::Item::Audio(audio) => {
-						dbg!(audio);
-					}
-					aet::Item::Video(_) => {}
-					aet::Item::Composition(comp) => print_audios(comp),
+				if let Some(aet::Item::Audio(index)) = layer.item {
+					dbg!(&scene.audios[index]);
 				}
 			}
 		}
-		print_audios(&file.scenes[0].root);
 		/*
-		let data = file.to_buf();
+		let data = file.to_buf().unwrap();
 		dbg!(data.len());
 		std::fs::write(
 			"/games/SteamLibrary/steamapps/common/Hatsune Miku Project DIVA Mega Mix Plus/mods/PS4 FT UI/rom_steam_en/rom/2d/aet_gam_cmn_new2.bin",
@@ -92,4 +392,806 @@ mod tests {
 		);
 		*/
 	}
+
+	fn fcurve(keys: &[(f32, f32, f32)]) -> aet::FCurve {
+		aet::FCurve {
+			keys: keys
+				.iter()
+				.map(|&(frame, value, tangent)| aet::FCurveKey::new(frame, value, tangent))
+				.collect(),
+		}
+	}
+
+	fn video(width: u16, height: u16, sprites: &[(&str, u32)]) -> aet::Video {
+		aet::Video {
+			color: [255, 255, 255],
+			width,
+			height,
+			fpf: 1.0,
+			sources: sprites
+				.iter()
+				.map(|&(name, id)| aet::VideoSource::new(name.to_string(), id))
+				.collect(),
+		}
+	}
+
+	fn scene(name: &str) -> aet::Scene {
+		let mut scene = aet::Scene::new();
+		scene.name = name.to_string();
+		scene.end_time = 120.0;
+		scene.color = [0, 0, 0];
+		scene.width = 1920;
+		scene.height = 1080;
+		scene
+	}
+
+	// Synthetic sets covering the parts of the format the game files use, so round trips are
+	// checked without needing any game data
+	fn aet_corpus() -> Vec<(&'static str, aet::Set)> {
+		let mut corpus = Vec::new();
+
+		// 2D video layers with animated transforms and markers
+		let mut scene_2d = scene("2d");
+		scene_2d.videos.push(video(256, 128, &[("SPR_BUTTON", 1)]));
+		scene_2d.videos.push(video(
+			64,
+			64,
+			&[
+				("SPR_FRAME_00", 2),
+				("SPR_FRAME_01", 3),
+				("SPR_FRAME_02", 4),
+			],
+		));
+		let mut layer = aet::Layer::new("button");
+		layer.end_time = 120.0;
+		layer.item = Some(aet::Item::Video(0));
+		layer.markers = vec![("ST_SP".to_string(), 0.0), ("ED_SP".to_string(), 60.0)];
+		let mut layer_video = aet::LayerVideo::new();
+		layer_video.transfer_mode.mode = aet::BlendMode::Add;
+		layer_video.anchor_x = aet::FCurve::constant(128.0);
+		layer_video.anchor_y = aet::FCurve::constant(64.0);
+		layer_video.pos_x = fcurve(&[(0.0, 0.0, 0.0), (30.0, 960.0, 2.5), (60.0, 1920.0, 0.0)]);
+		layer_video.pos_y = aet::FCurve::constant(540.0);
+		layer_video.opacity = fcurve(&[(0.0, 0.0, 0.0), (10.0, 1.0, 0.0)]);
+		layer.video = Some(layer_video);
+		scene_2d.root.layers.push(layer);
+		let mut layer = aet::Layer::new("frames");
+		layer.end_time = 60.0;
+		layer.time_scale = 0.5;
+		layer.quality = aet::LayerQuality::Best;
+		layer.item = Some(aet::Item::Video(1));
+		layer.video = Some(aet::LayerVideo::new());
+		scene_2d.root.layers.push(layer);
+		corpus.push((
+			"2d_classic",
+			aet::Set {
+				scenes: vec![scene_2d.clone()],
+				..Default::default()
+			},
+		));
+		corpus.push((
+			"2d_modern",
+			aet::Set {
+				modern: true,
+				scenes: vec![scene_2d],
+				..Default::default()
+			},
+		));
+
+		// 3D layers and a camera
+		let mut scene_3d = scene("3d");
+		scene_3d.videos.push(video(512, 512, &[("SPR_BG", 5)]));
+		let mut camera = aet::Camera::new();
+		camera.eye_z = aet::FCurve::constant(-1000.0);
+		camera.pos_x = fcurve(&[(0.0, 960.0, 0.0), (120.0, 1200.0, 0.0)]);
+		camera.pos_y = aet::FCurve::constant(540.0);
+		camera.zoom = aet::FCurve::constant(1.0);
+		scene_3d.camera = Some(camera);
+		let mut layer = aet::Layer::new("plane");
+		layer.end_time = 120.0;
+		layer.item = Some(aet::Item::Video(0));
+		let mut layer_video = aet::LayerVideo::new();
+		let mut layer_3d = aet::LayerVideo3D::new();
+		layer_3d.pos_z = fcurve(&[(0.0, 100.0, 0.0), (120.0, -100.0, 0.0)]);
+		layer_3d.rot_y = aet::FCurve::constant(45.0);
+		layer_video._3d = Some(layer_3d);
+		layer.video = Some(layer_video);
+		scene_3d.root.layers.push(layer);
+		corpus.push((
+			"3d",
+			aet::Set {
+				scenes: vec![scene_3d],
+				..Default::default()
+			},
+		));
+
+		// Audio layers, one sound shared by two layers
+		let mut scene_audio = scene("audio");
+		scene_audio.audios.push(aet::Audio::new(3));
+		scene_audio.audios.push(aet::Audio::new(7));
+		for (name, index, start) in [("se_a", 0, 0.0), ("se_b", 1, 30.0), ("se_a_again", 0, 60.0)] {
+			let mut layer = aet::Layer::new(name);
+			layer.start_time = start;
+			layer.end_time = start + 30.0;
+			layer.item = Some(aet::Item::Audio(index));
+			let mut layer_audio = aet::LayerAudio::new();
+			layer_audio.pan_l = aet::FCurve::constant(-1.0);
+			layer.audio = Some(layer_audio);
+			scene_audio.root.layers.push(layer);
+		}
+		corpus.push((
+			"audio",
+			aet::Set {
+				scenes: vec![scene_audio],
+				..Default::default()
+			},
+		));
+
+		// Parent links and a pre-comp used by two layers, itself using a video shared with the root
+		let mut scene_parent = scene("parent");
+		scene_parent.videos.push(video(32, 32, &[("SPR_DOT", 6)]));
+		let mut comp = aet::Composition::new();
+		let mut dot = aet::Layer::new("dot");
+		dot.end_time = 120.0;
+		dot.item = Some(aet::Item::Video(0));
+		dot.video = Some(aet::LayerVideo::new());
+		let mut follower = dot.clone();
+		follower.id = aet::LayerId::unique();
+		follower.name = "follower".to_string();
+		follower.parent = Some(dot.id);
+		// Parented to a layer of another composition, the file links layers across the scene
+		let mut cross = follower.clone();
+		cross.id = aet::LayerId::unique();
+		cross.name = "cross".to_string();
+		scene_parent.root.layers.push(cross);
+		comp.layers.push(dot);
+		comp.layers.push(follower);
+		scene_parent.comps.push(comp);
+		let mut root_dot = aet::Layer::new("root_dot");
+		root_dot.end_time = 120.0;
+		root_dot.item = Some(aet::Item::Video(0));
+		root_dot.video = Some(aet::LayerVideo::new());
+		let root_dot_id = root_dot.id;
+		scene_parent.root.layers.push(root_dot);
+		for name in ["group_a", "group_b"] {
+			let mut layer = aet::Layer::new(name);
+			layer.end_time = 120.0;
+			layer.item = Some(aet::Item::Composition(0));
+			let mut layer_video = aet::LayerVideo::new();
+			layer_video.rot_z = fcurve(&[(0.0, 0.0, 0.0), (120.0, 360.0, 0.0)]);
+			layer.video = Some(layer_video);
+			layer.parent = Some(root_dot_id);
+			scene_parent.root.layers.push(layer);
+		}
+		corpus.push((
+			"parent",
+			aet::Set {
+				scenes: vec![scene_parent],
+				..Default::default()
+			},
+		));
+
+		corpus
+	}
+
+	// Every corpus entry needs a golden file in tests/data/aet, run with KKDLIB_BLESS=1 to write
+	// them after an intended format change
+	#[test]
+	fn aet_round_trip() {
+		let golden_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/aet");
+		let bless = std::env::var_os("KKDLIB_BLESS").is_some();
+
+		for (name, set) in aet_corpus() {
+			let data = set.to_buf().unwrap();
+			let read = aet::Set::from_buf(&data, set.modern).unwrap();
+			assert_eq!(read, set, "{name} changed after reading it back");
+			assert!(read.to_buf().unwrap() == data, "{name} is not byte exact");
+
+			let path = golden_dir.join(format!("{name}.bin"));
+			if bless {
+				std::fs::create_dir_all(&golden_dir).unwrap();
+				std::fs::write(&path, &data).unwrap();
+				continue;
+			}
+
+			let golden = std::fs::read(&path).unwrap_or_else(|err| {
+				panic!(
+					"{name} has no golden at {} ({err}), run with KKDLIB_BLESS=1 to write it",
+					path.display()
+				)
+			});
+			assert!(golden == data, "{name} differs from {}", path.display());
+			let golden_set = aet::Set::from_buf(&golden, set.modern).unwrap();
+			assert!(
+				golden_set.to_buf().unwrap() == golden,
+				"{name} golden is not byte exact"
+			);
+		}
+	}
+
+	// A classic set with one scene whose only layer is a video layer pointing past the video table
+	#[test]
+	fn aet_dangling_item() {
+		let mut data = Vec::new();
+		let mut u32s = |values: &[u32]| {
+			for value in values {
+				data.extend_from_slice(&value.to_le_bytes());
+			}
+		};
+		// Scene offsets, terminated by 0
+		u32s(&[0x10, 0, 0, 0]);
+		// Scene at 0x10: name, times, fps, color, size, camera, comps, videos, audios
+		u32s(&[0x94, 0, 60f32.to_bits(), 60f32.to_bits(), 0, 1280, 720, 0]);
+		u32s(&[1, 0x48, 1, 0x50, 0, 0]);
+		// Root comp at 0x48
+		u32s(&[1, 0x64]);
+		// Video at 0x50: color, size, fpf, sources
+		u32s(&[0, 16 | 16 << 16, 1f32.to_bits(), 0, 0]);
+		// Layer at 0x64: name, times, flags with item type 1, item, parent, markers, video, audio
+		u32s(&[
+			0x9a,
+			0,
+			60f32.to_bits(),
+			0,
+			1f32.to_bits(),
+			1 | 1 << 24,
+			0x1234,
+		]);
+		u32s(&[0, 0, 0, 0, 0]);
+		data.extend_from_slice(b"scene\0layer\0");
+
+		assert_eq!(
+			aet::Set::from_buf(&data, false),
+			Err(aet::DecodeError::DanglingItem {
+				layer: "layer".to_string(),
+				item_type: 1,
+			})
+		);
+	}
+
+	// Links from_buf would refuse are rejected before anything is written
+	#[test]
+	fn aet_dangling_encode() {
+		let mut set = aet::Set::new();
+		let mut scene = aet::Scene::new();
+		let mut layer = aet::Layer::new("layer");
+		layer.item = Some(aet::Item::Composition(0));
+		scene.root.layers.push(layer);
+		set.scenes.push(scene);
+		assert_eq!(
+			set.to_buf(),
+			Err(aet::EncodeError::DanglingItem {
+				layer: "layer".to_string(),
+				item_type: 3,
+			})
+		);
+
+		let layer = &mut set.scenes[0].root.layers[0];
+		layer.item = None;
+		layer.parent = Some(aet::LayerId::unique());
+		assert_eq!(
+			set.to_buf(),
+			Err(aet::EncodeError::DanglingParent {
+				layer: "layer".to_string(),
+			})
+		);
+	}
+
+	#[test]
+	fn aet_evaluate() {
+		let mut scene = scene("evaluate");
+		scene
+			.videos
+			.push(video(16, 16, &[("SPR_0", 0), ("SPR_1", 1), ("SPR_2", 2)]));
+
+		let mut sprite = aet::Layer::new("sprite");
+		sprite.end_time = 100.0;
+		sprite.item = Some(aet::Item::Video(0));
+		let mut sprite_video = aet::LayerVideo::new();
+		sprite_video.pos_x = aet::FCurve::constant(10.0);
+		sprite.video = Some(sprite_video);
+		let mut comp = aet::Composition::new();
+		comp.layers.push(sprite);
+		scene.comps.push(comp);
+
+		// Moves everything parented to it without being drawn itself
+		let mut null = aet::Layer::new("null");
+		null.end_time = 100.0;
+		let mut null_video = aet::LayerVideo::new();
+		null_video.pos_x = aet::FCurve::constant(100.0);
+		null_video.pos_y = aet::FCurve::constant(50.0);
+		null.video = Some(null_video);
+
+		let mut group = aet::Layer::new("group");
+		group.start_time = 10.0;
+		group.end_time = 100.0;
+		group.time_scale = 2.0;
+		group.item = Some(aet::Item::Composition(0));
+		group.parent = Some(null.id);
+		let mut group_video = aet::LayerVideo::new();
+		group_video.opacity = aet::FCurve::constant(0.5);
+		group.video = Some(group_video);
+
+		scene.root.layers.push(group);
+		scene.root.layers.push(null);
+
+		assert!(scene.evaluate(5.0).is_empty());
+
+		let layers = scene.evaluate(11.0);
+		assert_eq!(layers.len(), 1);
+		let layer = &layers[0];
+		assert_eq!(layer.name, "sprite");
+		assert_eq!(layer.frame, 2.0);
+		assert_eq!(layer.source, Some(2));
+		assert_eq!(layer.sprite(&scene).unwrap().name, "SPR_2");
+		assert_eq!(layer.opacity, 0.5);
+		assert!(layer.visible);
+		assert_eq!(layer.matrix[0][3], 110.0);
+		assert_eq!(layer.matrix[1][3], 50.0);
+
+		// Past the last source it holds on it
+		assert_eq!(scene.evaluate(50.0)[0].source, Some(2));
+		// The inner layer ends at its own frame 100, which is root frame 60
+		assert!(scene.evaluate(60.0).is_empty());
+	}
+
+	#[test]
+	fn aet_render_solid() {
+		let mut scene = scene("render");
+		scene.width = 4;
+		scene.height = 4;
+
+		let mut solid =
+			|name: &str, color: [u8; 3], pos: f32, mode: aet::BlendMode, opacity: f32| {
+				let mut video = video(2, 2, &[]);
+				video.color = color;
+				scene.videos.push(video);
+
+				let mut layer = aet::Layer::new(name);
+				layer.end_time = 100.0;
+				layer.item = Some(aet::Item::Video(scene.videos.len() - 1));
+				let mut layer_video = aet::LayerVideo::new();
+				layer_video.transfer_mode.mode = mode;
+				layer_video.pos_x = aet::FCurve::constant(pos);
+				layer_video.pos_y = aet::FCurve::constant(pos);
+				layer_video.opacity = aet::FCurve::constant(opacity);
+				layer.video = Some(layer_video);
+				layer
+			};
+		let blue = solid("blue", [0, 0, 255], 2.0, aet::BlendMode::Add, 0.5);
+		let red = solid("red", [255, 0, 0], 1.0, aet::BlendMode::Normal, 1.0);
+		scene.root.layers.push(blue);
+		scene.root.layers.push(red);
+
+		let image = aet::Renderer::new(None).render(&scene, 0.0);
+		assert_eq!(image.get_pixel(0, 0).0, [0, 0, 0, 0]);
+		assert_eq!(image.get_pixel(1, 1).0, [255, 0, 0, 255]);
+		assert_eq!(image.get_pixel(2, 2).0, [255, 0, 128, 255]);
+		assert_eq!(image.get_pixel(3, 3).0, [0, 0, 255, 128]);
+	}
+
+	// Sources only resolve to sprites of the loaded set, never to another set's sprite or a texture
+	#[test]
+	fn aet_render_resolve() {
+		let mut txp = txp::Set::new();
+		let mut texture = txp::Texture::new();
+		texture.set_has_cube_map(false);
+		texture.set_array_size(1);
+		texture.set_mipmaps_count(1);
+		let data = vec![255; 2 * 2 * 4];
+		texture.add_mipmap(&txp::Mipmap::from_rgba(2, 2, &data, txp::Format::RGBA8).unwrap());
+		txp.add_file(&texture);
+		let mut spr_set = spr::Set::new();
+		spr_set.set_txp(&txp, vec![String::from("TEX")]);
+		let mut info = spr::Info::new();
+		info.set_width(2.0);
+		info.set_height(2.0);
+		spr_set.add_spr(&info, "A");
+
+		let mut spr_db = database::sprite::file::Database::new();
+		for name in ["TEST", "OTHER"] {
+			let set = database::sprite::file::Set::from_spr_set(&spr_set, name, "spr.bin");
+			spr_db.add_set(&set);
+		}
+		let mut database = database::sprite::Database::new();
+		database.add_file(&spr_db);
+
+		let drawn = |database: Option<&database::sprite::Database>, source: &str| {
+			let mut scene = scene("resolve");
+			scene.width = 2;
+			scene.height = 2;
+			let id = database
+				.and_then(|database| database.get_spr_by_name(source))
+				.map_or(0, |entry| entry.id());
+			scene.videos.push(video(2, 2, &[(source, id)]));
+			let mut layer = aet::Layer::new("sprite");
+			layer.end_time = 100.0;
+			layer.item = Some(aet::Item::Video(0));
+			layer.video = Some(aet::LayerVideo::new());
+			scene.root.layers.push(layer);
+
+			let mut renderer = aet::Renderer::new(database);
+			renderer.load_sprites(&spr_set, Some("SPR_TEST"));
+			renderer.render(&scene, 0.0).get_pixel(0, 0).0[3] != 0
+		};
+		assert!(drawn(Some(&database), "SPR_TEST_A"));
+		assert!(!drawn(Some(&database), "SPR_OTHER_A"));
+		assert!(!drawn(Some(&database), "SPRTEX_TEST_TEX"));
+		assert!(drawn(None, "SPR_TEST_A"));
+		assert!(!drawn(None, "SPR_OTHER_A"));
+	}
+
+	#[test]
+	fn aet_encode_range() {
+		use image::AnimationDecoder;
+
+		let mut scene = scene("range");
+		scene.width = 4;
+		scene.height = 4;
+		scene.fps = 30.0;
+		let mut solid = video(2, 2, &[]);
+		solid.color = [255, 0, 0];
+		scene.videos.push(solid);
+		let mut layer = aet::Layer::new("solid");
+		layer.end_time = 100.0;
+		layer.item = Some(aet::Item::Video(0));
+		// One pixel to the right every rendered frame
+		let mut layer_video = aet::LayerVideo::new();
+		layer_video.pos_x = fcurve(&[(0.0, 0.0, 0.5), (4.0, 2.0, 0.5)]);
+		layer.video = Some(layer_video);
+		scene.root.layers.push(layer);
+
+		let renderer = aet::Renderer::new(None);
+		let range = scene.render_range(&renderer, 0.0, 6.0, 2.0);
+		assert_eq!(range.len(), 3);
+		assert_eq!(range.frame_duration_ms(), 67);
+		let rendered = range.collect::<Vec<_>>();
+
+		let empty = scene.render_range(&renderer, 6.0, 0.0, 2.0);
+		assert!(matches!(
+			empty.encode(aet::AnimationFormat::Apng, Vec::new()),
+			Err(aet::AnimationError::Empty)
+		));
+
+		for format in [
+			aet::AnimationFormat::Apng,
+			aet::AnimationFormat::Gif,
+			aet::AnimationFormat::WebP,
+		] {
+			let mut data = Vec::new();
+			scene
+				.render_range(&renderer, 0.0, 6.0, 2.0)
+				.encode(format, &mut data)
+				.unwrap();
+			let data = std::io::Cursor::new(data);
+			let frames = match format {
+				aet::AnimationFormat::Apng => image::codecs::png::PngDecoder::new(data)
+					.unwrap()
+					.apng()
+					.unwrap()
+					.into_frames(),
+				aet::AnimationFormat::Gif => image::codecs::gif::GifDecoder::new(data)
+					.unwrap()
+					.into_frames(),
+				aet::AnimationFormat::WebP => image::codecs::webp::WebPDecoder::new(data)
+					.unwrap()
+					.into_frames(),
+			};
+			let frames = frames.collect_frames().unwrap();
+			assert_eq!(frames.len(), 3, "{format:?}");
+			for (frame, rendered) in frames.iter().zip(&rendered) {
+				// GIF can only quantize colors and has centisecond delays
+				if format == aet::AnimationFormat::Gif {
+					let alpha = |image: &image::RgbaImage| {
+						image.pixels().map(|px| px.0[3]).collect::<Vec<_>>()
+					};
+					assert_eq!(alpha(frame.buffer()), alpha(rendered));
+					continue;
+				}
+				assert_eq!(frame.buffer(), rendered, "{format:?}");
+				assert_eq!(frame.delay().numer_denom_ms(), (67, 1), "{format:?}");
+			}
+			assert_eq!(
+				frames[1].buffer().get_pixel(1, 0).0,
+				[255, 0, 0, 255],
+				"{format:?}"
+			);
+			assert_eq!(frames[1].buffer().get_pixel(0, 0).0[3], 0, "{format:?}");
+		}
+	}
+
+	#[test]
+	fn fcurve_editing() {
+		let close = |a: f32, b: f32, tolerance: f32| (a - b).abs() <= tolerance;
+		let frames = || (0..=220).map(|i| i as f32 * 0.1 - 1.0);
+
+		let mut curve = aet::FCurve::new();
+		assert_eq!(curve.insert_key(20.0, 0.0), 0);
+		assert_eq!(curve.insert_key(0.0, 0.0), 0);
+		assert_eq!(curve.insert_key(10.0, 10.0), 1);
+		assert_eq!(curve.insert_key(15.0, 8.0), 2);
+		assert_eq!(curve.keys.len(), 4);
+		for key in curve.keys.clone() {
+			assert_eq!(curve.interpolate(key.frame), key.value);
+		}
+		// The peak stays flat and clamped tangents don't overshoot it
+		assert_eq!(curve.keys[1].tangent, 0.0);
+		assert!(frames().all(|frame| curve.interpolate(frame) <= 10.0));
+
+		// Against finite differences of interpolate
+		for frame in frames().filter(|frame| (0.1..19.9).contains(frame)) {
+			let slope = (curve.interpolate(frame + 0.01) - curve.interpolate(frame - 0.01)) / 0.02;
+			assert!(close(curve.derivative(frame), slope, 0.05), "{frame}");
+		}
+		assert_eq!(curve.derivative(-1.0), 0.0);
+		assert_eq!(curve.derivative(21.0), 0.0);
+
+		let mut baked = curve.clone();
+		baked.bake(0.0, 20.0, 1.0);
+		assert_eq!(baked.keys.len(), 21);
+		assert!(frames().all(|frame| close(
+			baked.interpolate(frame),
+			curve.interpolate(frame),
+			1e-3
+		)));
+
+		baked.simplify(1e-3);
+		assert_eq!(baked.keys.len(), 4);
+		assert!(frames().all(|frame| close(
+			baked.interpolate(frame),
+			curve.interpolate(frame),
+			1e-3
+		)));
+
+		let mut retimed = curve.clone();
+		retimed.retime(2.0, 5.0);
+		assert!(frames().all(|frame| {
+			close(
+				retimed.interpolate(frame * 2.0 + 5.0),
+				curve.interpolate(frame),
+				1e-3,
+			)
+		}));
+		retimed.retime(-1.0, 0.0);
+		assert!(retimed.keys.is_sorted_by(|a, b| a.frame <= b.frame));
+		assert!(frames().all(|frame| {
+			close(
+				retimed.interpolate(-(frame * 2.0 + 5.0)),
+				curve.interpolate(frame),
+				1e-3,
+			)
+		}));
+
+		assert_eq!(curve.remove_key(1).map(|key| key.value), Some(10.0));
+		assert_eq!(curve.remove_key(3), None);
+		assert_eq!(curve.keys.len(), 3);
+	}
+
+	#[test]
+	fn aet_scene_graph() {
+		let mut scene = scene("graph");
+		scene
+			.videos
+			.push(video(16, 16, &[("SPR_A", 1), ("SPR_B", 2)]));
+
+		let mut inner = aet::Layer::new("inner");
+		inner.item = Some(aet::Item::Video(0));
+		inner.markers.push(("st_sp".to_string(), 5.0));
+		let mut comp = aet::Composition::new();
+		comp.layers.push(inner);
+		scene.comps.push(comp);
+
+		let null = aet::Layer::new("null");
+		let mut child = aet::Layer::new("child");
+		child.parent = Some(null.id);
+		let mut group = aet::Layer::new("group");
+		group.item = Some(aet::Item::Composition(0));
+		scene.root.layers.extend([child, group, null]);
+
+		let walk = scene
+			.layers_recursive()
+			.map(|(depth, _, layer)| (depth, layer.name.as_str()))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			walk,
+			[(0, "child"), (0, "group"), (1, "inner"), (0, "null")]
+		);
+		let inner_path = aet::LayerPath::new(Some(0), 0);
+		assert_eq!(scene.find_layer_by_name("inner"), Some(inner_path));
+		assert_eq!(scene.find_by_marker("st_sp"), [(inner_path, 5.0)]);
+		assert_eq!(scene.rename_sources("SPR_B", "SPR_C"), 1);
+		assert_eq!(scene.videos[0].sources[1].name, "SPR_C");
+
+		// The child comes first, so the copies go above it
+		let copy = scene.root.duplicate_layer(2).unwrap();
+		assert_eq!(copy, 1);
+		let names = scene.root.layers.iter().map(|layer| layer.name.as_str());
+		assert_eq!(
+			names.collect::<Vec<_>>(),
+			["child", "null", "child", "group", "null"]
+		);
+		assert_eq!(scene.root.layers[0].parent, Some(scene.root.layers[1].id));
+		assert_eq!(scene.root.layers[2].parent, Some(scene.root.layers[4].id));
+
+		let removed = scene.root.remove_layer(1).unwrap();
+		assert_eq!(removed.name, "null");
+		assert_eq!(scene.root.layers[0].parent, None);
+
+		assert!(scene.root.move_layer(0, 10));
+		assert_eq!(scene.root.layers.last().unwrap().name, "child");
+
+		let copy = scene.duplicate_comp(0).unwrap();
+		assert_eq!(scene.comps[copy], scene.comps[0]);
+		assert_ne!(scene.comps[copy].layers[0].id, scene.comps[0].layers[0].id);
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn aet_serde() {
+		for (name, set) in aet_corpus() {
+			let json = serde_json::to_string_pretty(&set).unwrap();
+			let back = serde_json::from_str::<aet::Set>(&json).unwrap();
+			assert!(back == set, "{name} changed going through json");
+		}
+
+		let mut comp = aet::Composition::new();
+		let null = aet::Layer::new("null");
+		let twin = aet::Layer::new("twin");
+		let mut child = aet::Layer::new("child");
+		child.parent = Some(null.id);
+		child.flags.set_time_remappingg(true);
+		let mut twin_child = aet::Layer::new("twin_child");
+		twin_child.parent = Some(twin.id);
+		comp.layers = vec![child, twin_child, null, twin.clone(), twin];
+		let json = serde_json::to_value(&comp).unwrap();
+		assert_eq!(json[0]["parent"], "null");
+		assert_eq!(
+			json[0]["flags"],
+			serde_json::json!(["video_active", "time_remapping"])
+		);
+		assert_eq!(json[1]["parent"], 3);
+		assert!(serde_json::from_value::<aet::Composition>(json.clone()).unwrap() == comp);
+
+		let mut json = json;
+		json[0]["flags"] = serde_json::json!(["time_remappingg"]);
+		json[1]["parent"] = "twin".into();
+		let err = serde_json::from_value::<aet::Composition>(json.clone()).unwrap_err();
+		assert!(err.to_string().contains("several layers"), "{err}");
+		json[1]["parent"] = "missing".into();
+		let err = serde_json::from_value::<aet::Composition>(json.clone()).unwrap_err();
+		assert!(
+			err.to_string().contains("isn't in the composition"),
+			"{err}"
+		);
+		json[1]["parent"] = serde_json::Value::Null;
+		let back = serde_json::from_value::<aet::Composition>(json).unwrap();
+		assert!(back.layers[0].flags.time_remappingg());
+	}
+
+	#[test]
+	fn key_val_tree() {
+		let kv = key_val::KeyVal::from_data(
+			"# comment\npv_001.song_name=Test\npv_001.bpm=150\npv_001.difficulty.easy.length=2\npv_001.difficulty.easy.0.level=PV_LV_03_0\npv_001.difficulty.easy.1.level=bad\npv_002.bpm=abc\nnum.int=1.0\nnum.trailing=12abc\nnum.float=2.5e1x\n",
+		);
+		let root = kv.root();
+		assert_eq!(root.keys().collect::<Vec<_>>(), ["pv_001", "pv_002", "num"]);
+		assert_eq!(root.read_u32("pv_001.bpm"), Ok(150));
+		assert!(matches!(
+			root.read_u32("pv_002.bpm"),
+			Err(key_val::KeyValError::Invalid { .. })
+		));
+		assert!(matches!(
+			root.read_str("pv_003.bpm"),
+			Err(key_val::KeyValError::Missing { .. })
+		));
+
+		let easy = root.get("pv_001.difficulty.easy").unwrap();
+		assert_eq!(easy.length(), Some(2));
+		assert_eq!(easy.items().count(), 2);
+
+		// Numbers are read like atoi and atof would
+		assert_eq!(root.read_i32("num.int"), Ok(1));
+		assert_eq!(root.read_u32("num.trailing"), Ok(12));
+		assert_eq!(root.read_f32("num.float"), Ok(25.0));
+		assert_eq!(kv.read_i32("num.float"), Some(2));
+
+		// Reads through the KeyVal follow the open scopes until their guards are dropped
+		{
+			let pv = kv.open_scope("pv_001").unwrap();
+			assert_eq!(kv.read_str("song_name"), Some("Test"));
+			assert_eq!(pv.read_u32("bpm"), Some(150));
+			let easy = pv.open_scope("difficulty.easy").unwrap();
+			let _level = easy.open_scope_num(0).unwrap();
+			assert_eq!(kv.read_str("level"), Some("PV_LV_03_0"));
+			assert!(!kv.has_key("song_name"));
+		}
+		assert_eq!(kv.read_str("song_name"), None);
+		assert_eq!(kv.read_u32("pv_001.bpm"), Some(150));
+	}
+
+	#[test]
+	fn key_val_writer() {
+		let mut writer = key_val::KeyValWriter::new();
+		let mut pv = writer.open_scope("pv_001");
+		pv.write_str("song_name", "Test");
+		pv.write_f32("sabi.play_time", 14.0);
+		pv.write_f32("sabi.start_time", 57.7);
+		pv.write_bool("disable_calc_motfrm_limit", true);
+		pv.write_array("difficulty.easy", 1..=11, |scope, level| {
+			scope.write_u32("level", level)
+		});
+
+		let text = writer.to_string();
+		assert!(text.starts_with("pv_001.difficulty.easy.0.level=1\npv_001.difficulty.easy.1.level=2\npv_001.difficulty.easy.10.level=11\n"));
+		assert!(text.contains("pv_001.difficulty.easy.length=11\n"));
+		assert!(text.contains("pv_001.sabi.play_time=14.0\n"));
+
+		let node = key_val::KeyValNode::parse(&text);
+		assert_eq!(node.read_f32("pv_001.sabi.start_time"), Ok(57.7));
+		assert_eq!(node.read_bool("pv_001.disable_calc_motfrm_limit"), Ok(true));
+		assert_eq!(
+			node.get("pv_001.difficulty.easy").unwrap().items().count(),
+			11
+		);
+		assert_eq!(key_val::KeyValWriter::from(&node).to_string(), text);
+	}
+
+	#[test]
+	fn pv_db() {
+		let text = "pv_001.bpm=150\npv_001.difficulty.easy.0.edition=0\npv_001.difficulty.easy.0.level=PV_LV_03_5\npv_001.difficulty.easy.0.script_file_name=rom/script/pv_001_easy.dsc\npv_001.difficulty.easy.length=1\npv_001.difficulty.hard.0.level=PV_LV_07\npv_001.difficulty.hard.length=1\npv_001.lyric.001=First\npv_001.motion.0=CMN_MRA00_13_01\npv_001.motion.length=1\npv_001.osage_init=1\npv_001.performer.0.chara=MIK\npv_001.performer.0.type=VOCAL\npv_001.performer.num=1\npv_001.sabi.play_time=14.0\npv_001.song_name=Test\npv_list.version=1\n";
+		let mut db = database::pv::Database::parse(text).unwrap();
+		assert_eq!(db.to_string(), text);
+
+		let pv = db.get_mut(1).unwrap();
+		assert_eq!(pv.bpm, Some(150));
+		assert_eq!(pv.charts[0].stars(), Some(3.5));
+		assert_eq!(pv.performers[0].chara.as_deref(), Some("MIK"));
+		assert_eq!(pv.motions, [["CMN_MRA00_13_01"]]);
+		assert_eq!(pv.lyrics[&1], "First");
+		assert_eq!(pv.unknown.read_str("osage_init"), Ok("1"));
+
+		use database::pv::{Difficulty, Issue};
+		let issues = db.validate();
+		let issues = issues.iter().map(|issue| &issue.issue).collect::<Vec<_>>();
+		assert_eq!(
+			issues,
+			[
+				&Issue::MissingScript {
+					difficulty: Difficulty::Hard,
+					edition: 0
+				},
+				&Issue::BadLevel {
+					difficulty: Difficulty::Hard,
+					edition: 0,
+					level: Some(String::from("PV_LV_07"))
+				}
+			]
+		);
+
+		// Non-padded keys keep their spelling, keys under charts and performers stay with them
+		let text = "pv_01.difficulty.easy.0.level=PV_LV_03_5\npv_01.difficulty.easy.0.se_name=01_button1\npv_01.difficulty.easy.length=1\npv_01.lyric.1=First\npv_01.lyric.12=Twelfth\npv_01.performer.0.chara=MIK\npv_01.performer.0.item.0=ITEM\npv_01.performer.num=1\npv_01.song_name=Test\n";
+		let db = database::pv::Database::parse(text).unwrap();
+		assert_eq!(db.to_string(), text);
+		let pv = db.get(1).unwrap();
+		assert_eq!(pv.lyrics[&12], "Twelfth");
+		assert_eq!(pv.charts[0].unknown.read_str("se_name"), Ok("01_button1"));
+		assert_eq!(pv.performers[0].unknown.read_str("item.0"), Ok("ITEM"));
+		assert!(pv.unknown.is_empty());
+
+		// Charts keep their slot and the length, the slot is the edition the game loads
+		let text = "pv_002.difficulty.easy.1.level=PV_LV_03_5\npv_002.difficulty.easy.1.se_name=01_button1\npv_002.difficulty.easy.length=2\npv_002.difficulty.hard.0.level=PV_LV_07_0\npv_002.difficulty.hard.length=3\n";
+		let mut db = database::pv::Database::parse(text).unwrap();
+		assert_eq!(db.to_string(), text);
+		let pv = db.get_mut(2).unwrap();
+		assert_eq!(pv.charts[0].index, Some(1));
+		let mut chart = database::pv::Chart::new(Difficulty::Easy);
+		chart.level = Some(String::from("PV_LV_04_0"));
+		pv.charts.push(chart);
+		assert!(db.to_string().starts_with(
+			"pv_002.difficulty.easy.0.level=PV_LV_04_0\npv_002.difficulty.easy.1.level=PV_LV_03_5\n"
+		));
+
+		let err = database::pv::Database::parse("pv_002.bpm=fast").unwrap_err();
+		assert!(matches!(err, key_val::KeyValError::Invalid { key, .. } if key == "pv_002.bpm"));
+	}
 }
@@ -224,8 +224,60 @@ impl Set {
 		Some(vec)
 	}
 
+	// Rescales every texture and sprite rect from one resolution to another, e.g. FT HD to MM+ FHD
+	pub fn convert_resolution(
+		&self,
+		from_mode: ResolutionMode,
+		to_mode: ResolutionMode,
+	) -> Option<Self> {
+		let (from_width, from_height) = from_mode.resolution();
+		let (to_width, to_height) = to_mode.resolution();
+		let scale_x = to_width as f32 / from_width as f32;
+		let scale_y = to_height as f32 / from_height as f32;
+
+		let mut txp = crate::txp::Set::new();
+		let mut names = Vec::new();
+		let mut scales = Vec::new();
+		for (name, texture) in self.textures() {
+			let mip = texture.get_mipmap(0, 0)?;
+			let mut width = ((mip.width() as f32 * scale_x).round() as i32).max(1);
+			let mut height = ((mip.height() as f32 * scale_y).round() as i32).max(1);
+			if mip.format().is_block_compressed() {
+				width = (width + 3) / 4 * 4;
+				height = (height + 3) / 4 * 4;
+			}
+			scales.push((
+				width as f32 / mip.width() as f32,
+				height as f32 / mip.height() as f32,
+			));
+			txp.add_file(&texture.resize(width, height)?);
+			names.push(name);
+		}
+
+		let mut set = Self::new();
+		set.set_modern(self.modern());
+		set.set_big_endian(self.big_endian());
+		set.set_is_x(self.is_x());
+		set.set_flag(self.flag());
+		set.set_txp(&txp, names);
+
+		for (name, info) in self.sprites() {
+			let (scale_x, scale_y) = *scales.get(info.texid() as usize)?;
+			let mut info = info.clone();
+			info.set_px(info.px() * scale_x);
+			info.set_py(info.py() * scale_y);
+			info.set_width(info.width() * scale_x);
+			info.set_height(info.height() * scale_y);
+			info.set_resolution_mode(to_mode);
+			set.add_spr(&info, &name);
+		}
+
+		set.set_ready(self.ready());
+		Some(set)
+	}
+
 	#[cfg(feature = "pyo3")]
-	#[cfg_attr(feature = "pyo3", getter)]
+	#[cfg_attr(feature = "pyo3", getter(sprites))]
 	pub fn py_sprites(&self) -> HashMap<String, Info> {
 		self.sprites()
 			.map(|(name, info)| (name, info.clone()))
@@ -233,7 +285,7 @@ impl Set {
 	}
 
 	#[cfg(feature = "pyo3")]
-	#[cfg_attr(feature = "pyo3", getter)]
+	#[cfg_attr(feature = "pyo3", getter(textures))]
 	pub fn py_textures(&self) -> HashMap<String, crate::txp::PyTexture> {
 		self.textures()
 			.map(|(name, texture)| {
@@ -44,6 +44,20 @@ pub enum Format {
 	BC6H = 127,
 }
 
+impl Format {
+	// Stored as 4x4 blocks, so the top mip's dimensions have to be multiples of 4
+	pub fn is_block_compressed(self) -> bool {
+		matches!(
+			self,
+			Self::BC1
+				| Self::BC1a | Self::BC2
+				| Self::BC3 | Self::BC4
+				| Self::BC5 | Self::BC6H
+				| Self::BC7
+		)
+	}
+}
+
 #[bitfields::bitfield(u16)]
 struct Rgb565 {
 	#[bits(5)]
@@ -181,7 +195,7 @@ impl Set {
 	}
 
 	#[cfg(feature = "pyo3")]
-	#[cfg_attr(feature = "pyo3", getter)]
+	#[cfg_attr(feature = "pyo3", getter(textures))]
 	pub fn py_textures(&self) -> Vec<PyTexture> {
 		self.textures()
 			.map(|tex| {
@@ -197,13 +211,48 @@ impl Set {
 }
 
 #[cfg(feature = "pyo3")]
-#[cfg_attr(feature = "pyo3", pyclass)]
+#[cfg_attr(feature = "pyo3", pyclass(name = "Image", frozen))]
 pub struct PyTexture {
 	pub width: i32,
 	pub height: i32,
 	pub rgba: Vec<u8>,
 }
 
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl PyTexture {
+	#[getter]
+	pub fn width(&self) -> i32 {
+		self.width
+	}
+
+	#[getter]
+	pub fn height(&self) -> i32 {
+		self.height
+	}
+
+	// A view of the texture itself, no copy is made
+	#[getter]
+	pub fn rgba<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, pyo3::types::PyMemoryView>> {
+		pyo3::types::PyMemoryView::from(slf.as_any())
+	}
+
+	unsafe fn __getbuffer__(
+		slf: Bound<'_, Self>,
+		view: *mut pyo3::ffi::Py_buffer,
+		flags: std::ffi::c_int,
+	) -> PyResult<()> {
+		let owner = slf.clone().into_any();
+		let texture = slf.get();
+		let shape = [texture.height as usize, texture.width as usize, 4];
+		unsafe { crate::buffer::fill_view(view, flags, &texture.rgba, &shape, owner, None) }
+	}
+
+	unsafe fn __releasebuffer__(&self, view: *mut pyo3::ffi::Py_buffer) {
+		unsafe { crate::buffer::release_view(view) };
+	}
+}
+
 impl Set {
 	pub fn textures<'a>(&'a self) -> TextureIterator<'a> {
 		TextureIterator {
@@ -447,8 +496,9 @@ impl Texture {
 		Some(texture)
 	}
 
-	#[cfg(all(feature = "pyo3"))]
+	#[cfg(feature = "pyo3")]
 	#[cfg_attr(feature = "pyo3", staticmethod)]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "from_rgba"))]
 	pub fn py_from_rgba(width: i32, height: i32, data: &[u8], format: Format) -> Option<Self> {
 		let mip = Mipmap::from_rgba(width, height, data, format)?;
 		let mut tex = Self::new();
@@ -458,6 +508,58 @@ impl Texture {
 		tex.add_mipmap(&mip);
 		Some(tex)
 	}
+
+	pub fn resize(&self, width: i32, height: i32) -> Option<Self> {
+		if self.is_ycbcr() {
+			let mip = self.get_mipmap(0, 0)?;
+			let rgba = self.decode_ycbcr()?;
+			let rgba = resize_rgba(&rgba, mip.width(), mip.height(), width, height)?;
+			return Self::encode_ycbcr(width as u32, height as u32, &rgba);
+		}
+
+		let mut texture = Self::new();
+		texture.set_has_cube_map(self.has_cube_map());
+		texture.set_array_size(self.array_size());
+		texture.set_mipmaps_count(self.mipmaps_count());
+
+		for array_index in 0..self.array_size() {
+			let mip = self.get_mipmap(array_index, 0)?;
+			let rgba = mip.rgba()?;
+			for mipmap_index in 0..self.mipmaps_count() {
+				let mip_width = (width >> mipmap_index).max(1);
+				let mip_height = (height >> mipmap_index).max(1);
+				let data = resize_rgba(&rgba, mip.width(), mip.height(), mip_width, mip_height)?;
+				let new_mip = Mipmap::from_rgba(mip_width, mip_height, &data, mip.format())?;
+				texture.add_mipmap(&new_mip);
+			}
+		}
+
+		Some(texture)
+	}
+}
+
+fn resize_rgba(
+	data: &[u8],
+	width: i32,
+	height: i32,
+	new_width: i32,
+	new_height: i32,
+) -> Option<Vec<u8>> {
+	if width == new_width && height == new_height {
+		return Some(data.to_vec());
+	}
+
+	let buffer = image::ImageBuffer::<image::Rgba<u8>, Vec<u8>>::from_raw(
+		width as u32,
+		height as u32,
+		data.to_vec(),
+	)?;
+	let buffer = image::DynamicImage::ImageRgba8(buffer).resize_exact(
+		new_width as u32,
+		new_height as u32,
+		image::imageops::FilterType::Lanczos3,
+	);
+	Some(buffer.into_bytes())
 }
 
 impl Texture {
@@ -536,6 +638,10 @@ impl<'a> TextureRef<'a> {
 	pub fn decode_ycbcr(&self) -> Option<Vec<u8>> {
 		Texture::decode_ycbcr(unsafe { std::mem::transmute(self) })
 	}
+
+	pub fn resize(&self, width: i32, height: i32) -> Option<Texture> {
+		Texture::resize(unsafe { std::mem::transmute(self) }, width, height)
+	}
 }
 
 unsafe impl Send for TextureRef<'_> {}
@@ -626,6 +732,193 @@ impl Mipmap {
 		unsafe { kkdlib_txp_mipmap_get_size(self.ptr) }
 	}
 
+	// A view of the mipmap's own data, no copy is made. The mipmap can't be changed while a view
+	// of it is alive
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "data"))]
+	pub fn py_data<'py>(
+		slf: &Bound<'py, Self>,
+	) -> PyResult<Option<Bound<'py, pyo3::types::PyMemoryView>>> {
+		if slf.borrow().data().is_none() {
+			return Ok(None);
+		}
+		pyo3::types::PyMemoryView::from(slf.as_any()).map(Some)
+	}
+
+	#[cfg(feature = "pyo3")]
+	unsafe fn __getbuffer__(
+		slf: Bound<'_, Self>,
+		view: *mut pyo3::ffi::Py_buffer,
+		flags: std::ffi::c_int,
+	) -> PyResult<()> {
+		let mip = slf.try_borrow()?;
+		let Some(data) = mip.data().map(|data| data as *const [u8]) else {
+			return Err(pyo3::exceptions::PyBufferError::new_err(
+				"Mipmap data doesn't match its size",
+			));
+		};
+		// The borrow is dropped in __releasebuffer__, which runs with the GIL held
+		let guard: PyRef<'static, Self> = unsafe { std::mem::transmute(mip) };
+		let owner = slf.into_any();
+		unsafe {
+			let data = &*data;
+			crate::buffer::fill_view(
+				view,
+				flags,
+				data,
+				&[data.len()],
+				owner,
+				Some(Box::new(guard)),
+			)
+		}
+	}
+
+	#[cfg(feature = "pyo3")]
+	unsafe fn __releasebuffer__(&self, view: *mut pyo3::ffi::Py_buffer) {
+		unsafe { crate::buffer::release_view(view) };
+	}
+
+	pub fn set_data(&mut self, data: &[u8]) {
+		assert_eq!(data.len(), self.size() as usize);
+		unsafe { kkdlib_txp_mipmap_set_data(self.ptr, data.as_ptr() as *const c_void) };
+	}
+
+	#[cfg(feature = "pyo3")]
+	#[cfg_attr(feature = "pyo3", pyo3(name = "rgba"))]
+	pub fn py_rgba(&self) -> Option<crate::buffer::Buffer> {
+		let shape = vec![self.height() as usize, self.width() as usize, 4];
+		Some(crate::buffer::Buffer::new(self.rgba()?, shape))
+	}
+
+	#[cfg_attr(feature = "pyo3", staticmethod)]
+	pub fn from_rgba(width: i32, height: i32, data: &[u8], format: Format) -> Option<Self> {
+		let mut mip = Mipmap::new();
+		mip.set_width(width);
+		mip.set_height(height);
+		mip.set_format(format);
+
+		let mut mip_data = vec![0; mip.size() as usize];
+
+		match format {
+			Format::A8 => {
+				for i in 0..(width as usize * height as usize) {
+					mip_data[i] = data[i * 4 + 3];
+				}
+			}
+			Format::RGB8 => {
+				for i in 0..(width as usize * height as usize) {
+					let offset = i * 4;
+					let mip_offset = i * 3;
+					mip_data[mip_offset + 0] = data[offset + 0];
+					mip_data[mip_offset + 1] = data[offset + 1];
+					mip_data[mip_offset + 2] = data[offset + 2];
+				}
+			}
+			Format::RGBA8 => {
+				mip_data.copy_from_slice(data);
+			}
+			Format::RGB5 => {
+				for i in 0..(width as usize * height as usize) {
+					let offset = i * 4;
+					let mip_offset = i * 2;
+					let rgb = Rgb565Builder::new()
+						.with_r(data[offset + 0] >> 3)
+						.with_g(data[offset + 1] >> 2)
+						.with_b(data[offset + 2] >> 3)
+						.build()
+						.into_bits();
+					mip_data[mip_offset + 0] = (rgb >> 0) as u8;
+					mip_data[mip_offset + 1] = (rgb >> 8) as u8;
+				}
+			}
+			Format::RGB5A1 => {
+				for i in 0..(width as usize * height as usize) {
+					let offset = i * 4;
+					let mip_offset = i * 2;
+					let rgba = Rgba5551Builder::new()
+						.with_r(data[offset + 0] >> 3)
+						.with_g(data[offset + 1] >> 3)
+						.with_b(data[offset + 2] >> 3)
+						.with_a(data[offset + 3] / 255)
+						.build()
+						.into_bits();
+					mip_data[mip_offset + 0] = (rgba >> 0) as u8;
+					mip_data[mip_offset + 1] = (rgba >> 8) as u8;
+				}
+			}
+			Format::RGBA4 => {
+				for i in 0..(width as usize * height as usize) {
+					let offset = i * 4;
+					let mip_offset = i * 2;
+					let rgba = Rgba4444Builder::new()
+						.with_r(data[offset + 0] >> 4)
+						.with_g(data[offset + 1] >> 4)
+						.with_b(data[offset + 2] >> 4)
+						.with_a(data[offset + 3] >> 4)
+						.build()
+						.into_bits();
+					mip_data[mip_offset + 0] = (rgba >> 0) as u8;
+					mip_data[mip_offset + 1] = (rgba >> 8) as u8;
+				}
+			}
+			Format::BC1 | Format::BC1a | Format::BC3 | Format::BC4 | Format::BC5 | Format::BC7 => {
+				let fmt = match format {
+					Format::BC1 | Format::BC1a => DXGI_FORMAT_BC1_UNORM,
+					Format::BC3 => DXGI_FORMAT_BC3_UNORM,
+					Format::BC4 => DXGI_FORMAT_BC4_UNORM,
+					Format::BC5 => DXGI_FORMAT_BC5_UNORM,
+					Format::BC7 => DXGI_FORMAT_BC7_UNORM,
+					_ => unreachable!(),
+				};
+
+				unsafe {
+					let ptr = rdo_encode_rgba(data.as_ptr(), width as u32, height as u32, fmt);
+					if ptr.is_null() {
+						return None;
+					}
+					mip_data.copy_from_slice(std::slice::from_raw_parts(ptr, mip.size() as usize));
+					rdo_free(ptr);
+				}
+			}
+			Format::BC2 | Format::BC6H => {
+				return None;
+			}
+			Format::L8 => {
+				for i in 0..(width as usize * height as usize) {
+					let offset = i * 4;
+					let r = data[offset + 0] as f32;
+					let g = data[offset + 1] as f32;
+					let b = data[offset + 2] as f32;
+
+					let y =
+						r * ycbcr::ENCODE[0][0] + g * ycbcr::ENCODE[0][1] + b * ycbcr::ENCODE[0][2];
+					mip_data[i] = y as u8;
+				}
+			}
+			Format::L8A8 => {
+				for i in 0..(width as usize * height as usize) {
+					let offset = i * 4;
+					let mip_offset = i * 2;
+					let r = data[offset + 0] as f32;
+					let g = data[offset + 1] as f32;
+					let b = data[offset + 2] as f32;
+					let a = data[offset + 3];
+
+					let y =
+						r * ycbcr::ENCODE[0][0] + g * ycbcr::ENCODE[0][1] + b * ycbcr::ENCODE[0][2];
+					mip_data[mip_offset + 0] = y as u8;
+					mip_data[mip_offset + 1] = a;
+				}
+			}
+		}
+
+		mip.set_data(&mip_data);
+		Some(mip)
+	}
+}
+
+// Python gets these as buffers instead
+impl Mipmap {
 	pub fn data<'a>(&'a self) -> Option<&'a [u8]> {
 		let ptr = unsafe { kkdlib_txp_mipmap_get_data(self.ptr) };
 		if ptr.is_null() {
@@ -635,11 +928,6 @@ impl Mipmap {
 		unsafe { slice.as_ref() }
 	}
 
-	pub fn set_data(&mut self, data: &[u8]) {
-		assert_eq!(data.len(), self.size() as usize);
-		unsafe { kkdlib_txp_mipmap_set_data(self.ptr, data.as_ptr() as *const c_void) };
-	}
-
 	pub fn rgba(&self) -> Option<Vec<u8>> {
 		let size = self.width() * self.height() * 4;
 		let mut out = vec![0; size as usize];
@@ -768,132 +1056,6 @@ impl Mipmap {
 
 		Some(out)
 	}
-
-	#[cfg_attr(feature = "pyo3", staticmethod)]
-	pub fn from_rgba(width: i32, height: i32, data: &[u8], format: Format) -> Option<Self> {
-		let mut mip = Mipmap::new();
-		mip.set_width(width);
-		mip.set_height(height);
-		mip.set_format(format);
-
-		let mut mip_data = vec![0; mip.size() as usize];
-
-		match format {
-			Format::A8 => {
-				for i in 0..(width as usize * height as usize) {
-					mip_data[i] = data[i * 4 + 3];
-				}
-			}
-			Format::RGB8 => {
-				for i in 0..(width as usize * height as usize) {
-					let offset = i * 4;
-					let mip_offset = i * 3;
-					mip_data[mip_offset + 0] = data[offset + 0];
-					mip_data[mip_offset + 1] = data[offset + 1];
-					mip_data[mip_offset + 2] = data[offset + 2];
-				}
-			}
-			Format::RGBA8 => {
-				mip_data.copy_from_slice(data);
-			}
-			Format::RGB5 => {
-				for i in 0..(width as usize * height as usize) {
-					let offset = i * 4;
-					let mip_offset = i * 2;
-					let rgb = Rgb565Builder::new()
-						.with_r(data[offset + 0] >> 3)
-						.with_g(data[offset + 1] >> 2)
-						.with_b(data[offset + 2] >> 3)
-						.build()
-						.into_bits();
-					mip_data[mip_offset + 0] = (rgb >> 0) as u8;
-					mip_data[mip_offset + 1] = (rgb >> 8) as u8;
-				}
-			}
-			Format::RGB5A1 => {
-				for i in 0..(width as usize * height as usize) {
-					let offset = i * 4;
-					let mip_offset = i * 2;
-					let rgba = Rgba5551Builder::new()
-						.with_r(data[offset + 0] >> 3)
-						.with_g(data[offset + 1] >> 3)
-						.with_b(data[offset + 2] >> 3)
-						.with_a(data[offset + 3] / 255)
-						.build()
-						.into_bits();
-					mip_data[mip_offset + 0] = (rgba >> 0) as u8;
-					mip_data[mip_offset + 1] = (rgba >> 8) as u8;
-				}
-			}
-			Format::RGBA4 => {
-				for i in 0..(width as usize * height as usize) {
-					let offset = i * 4;
-					let mip_offset = i * 2;
-					let rgba = Rgba4444Builder::new()
-						.with_r(data[offset + 0] >> 4)
-						.with_g(data[offset + 1] >> 4)
-						.with_b(data[offset + 2] >> 4)
-						.with_a(data[offset + 3] >> 4)
-						.build()
-						.into_bits();
-					mip_data[mip_offset + 0] = (rgba >> 0) as u8;
-					mip_data[mip_offset + 1] = (rgba >> 8) as u8;
-				}
-			}
-			Format::BC1 | Format::BC1a | Format::BC3 | Format::BC4 | Format::BC5 | Format::BC7 => {
-				let fmt = match format {
-					Format::BC1 | Format::BC1a => DXGI_FORMAT_BC1_UNORM,
-					Format::BC3 => DXGI_FORMAT_BC3_UNORM,
-					Format::BC4 => DXGI_FORMAT_BC4_UNORM,
-					Format::BC5 => DXGI_FORMAT_BC5_UNORM,
-					Format::BC7 => DXGI_FORMAT_BC7_UNORM,
-					_ => unreachable!(),
-				};
-
-				unsafe {
-					let ptr = rdo_encode_rgba(data.as_ptr(), width as u32, height as u32, fmt);
-					if ptr.is_null() {
-						return None;
-					}
-					mip_data.copy_from_slice(std::slice::from_raw_parts(ptr, mip.size() as usize));
-					rdo_free(ptr);
-				}
-			}
-			Format::BC2 | Format::BC6H => {
-				return None;
-			}
-			Format::L8 => {
-				for i in 0..(width as usize * height as usize) {
-					let offset = i * 4;
-					let r = data[offset + 0] as f32;
-					let g = data[offset + 1] as f32;
-					let b = data[offset + 2] as f32;
-
-					let y =
-						r * ycbcr::ENCODE[0][0] + g * ycbcr::ENCODE[0][1] + b * ycbcr::ENCODE[0][2];
-					mip_data[i] = y as u8;
-				}
-			}
-			Format::L8A8 => {
-				for i in 0..(width as usize * height as usize) {
-					let offset = i * 4;
-					let mip_offset = i * 2;
-					let r = data[offset + 0] as f32;
-					let g = data[offset + 1] as f32;
-					let b = data[offset + 2] as f32;
-					let a = data[offset + 3];
-
-					let y =
-						r * ycbcr::ENCODE[0][0] + g * ycbcr::ENCODE[0][1] + b * ycbcr::ENCODE[0][2];
-					mip_data[mip_offset + 0] = y as u8;
-					mip_data[mip_offset + 1] = a;
-				}
-			}
-		}
-
-		mip.set_data(&mip_data);
-		Some(mip)
-	}
 }
 
 impl Drop for Mipmap {